@@ -1,5 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -9,9 +10,16 @@ use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::parser::{Parser, SupportedLanguage};
+use super::parser::{
+    CallInfo, DocLinkKind, ImportInfo, ImportStyle, ImportUsage, InheritanceInfo, ParseResult, Parser,
+    ReExportInfo, SupportedLanguage,
+};
+use super::resolvers::packages::{classify_unresolved, ExternalPackage, Unresolved};
+use super::resolvers::{csharp, jvm, ImportResolver};
 use crate::error::{NexusError, NexusResult};
-use crate::storage::{FileRecord, RelationshipRecord, SymbolRecord};
+use crate::storage::{
+    BrokenLinkRecord, FileRecord, PackageRecord, RelationshipRecord, SymbolRecord, UnresolvedImportRecord,
+};
 
 /// Analysis status - aligned with frontend types
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -35,6 +43,29 @@ pub struct AnalysisStatistics {
     pub total_symbols: usize,
     /// Total files analyzed
     pub total_files: usize,
+    /// Total imports found
+    pub total_imports: usize,
+    /// Imports that did not resolve to a project file, external ones included
+    pub unresolved_imports: usize,
+    /// How well imports resolved, per language
+    pub import_coverage: Vec<ImportCoverage>,
+    /// Links in documents to files that do not exist
+    pub broken_links: usize,
+}
+
+/// Import resolution counts for one language
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportCoverage {
+    pub language: String,
+    /// Imports found
+    pub total: usize,
+    /// Imports resolved to project files
+    pub resolved: usize,
+    /// Imports of third-party packages and built-in modules
+    pub external: usize,
+    /// Imports of missing files, or that could not be classified at all
+    pub missing: usize,
 }
 
 /// Progress update for analysis - aligned with frontend types
@@ -86,7 +117,8 @@ impl AnalysisProgress {
         }
     }
 
-    pub fn completed(files: usize, symbols: usize, relationships: usize) -> Self {
+    pub fn completed(result: &AnalysisResult) -> Self {
+        let files = result.files.len();
         Self {
             status: AnalysisStatus::Complete,
             files_processed: files,
@@ -94,8 +126,12 @@ impl AnalysisProgress {
             percent_complete: 100.0,
             statistics: AnalysisStatistics {
                 total_files: files,
-                total_symbols: symbols,
-                total_relationships: relationships,
+                total_symbols: result.symbols.len(),
+                total_relationships: result.relationships.len(),
+                total_imports: result.import_coverage.iter().map(|c| c.total).sum(),
+                unresolved_imports: result.unresolved_imports.len(),
+                import_coverage: result.import_coverage.clone(),
+                broken_links: result.broken_links.len(),
             },
             ..Default::default()
         }
@@ -123,12 +159,30 @@ pub struct AnalysisResult {
     pub files: Vec<FileRecord>,
     pub symbols: Vec<SymbolRecord>,
     pub relationships: Vec<RelationshipRecord>,
+    pub packages: Vec<PackageRecord>,
+    pub unresolved_imports: Vec<UnresolvedImportRecord>,
+    pub import_coverage: Vec<ImportCoverage>,
+    pub broken_links: Vec<BrokenLinkRecord>,
+}
+
+/// Relationships between files and symbols, the packages imports point into,
+/// the imports that did not resolve to a project file and broken document links
+struct ResolvedRelationships {
+    relationships: Vec<RelationshipRecord>,
+    packages: Vec<PackageRecord>,
+    unresolved_imports: Vec<UnresolvedImportRecord>,
+    import_coverage: Vec<ImportCoverage>,
+    broken_links: Vec<BrokenLinkRecord>,
 }
 
 /// Main analysis engine
 pub struct AnalysisEngine {
     parser: Parser,
     cancelled: Arc<AtomicBool>,
+    /// Extra C/C++ include directories configured for the project
+    include_dirs: Vec<PathBuf>,
+    /// Attribute imports through barrel files to the files that define the names
+    follow_re_exports: bool,
 }
 
 impl AnalysisEngine {
@@ -136,9 +190,25 @@ impl AnalysisEngine {
         Self {
             parser: Parser::new(),
             cancelled: Arc::new(AtomicBool::new(false)),
+            include_dirs: Vec::new(),
+            follow_re_exports: true,
         }
     }
 
+    /// Search these directories (absolute or relative to the project root) for
+    /// `#include`s that compile_commands.json does not account for
+    pub fn with_include_dirs(mut self, include_dirs: Vec<PathBuf>) -> Self {
+        self.include_dirs = include_dirs;
+        self
+    }
+
+    /// Follow `export ... from` chains so that importing a name from a barrel
+    /// also links the importer to the file that defines it (on by default)
+    pub fn with_follow_re_exports(mut self, follow_re_exports: bool) -> Self {
+        self.follow_re_exports = follow_re_exports;
+        self
+    }
+
     /// Cancel the current analysis
     pub fn cancel(&self) {
         self.cancelled.store(true, Ordering::SeqCst);
@@ -212,12 +282,12 @@ impl AnalysisEngine {
         // Collect all files and symbols
         let mut all_files = Vec::new();
         let mut all_symbols = Vec::new();
-        let mut file_imports: HashMap<String, Vec<super::parser::ImportInfo>> = HashMap::new();
+        let mut parsed_files: HashMap<String, ParseResult> = HashMap::new();
 
-        for (file, symbols, imports) in parsed_results {
-            file_imports.insert(file.id.clone(), imports);
+        for (file, mut parsed) in parsed_results {
+            all_symbols.append(&mut parsed.symbols);
+            parsed_files.insert(file.id.clone(), parsed);
             all_files.push(file);
-            all_symbols.extend(symbols);
         }
 
         // Resolve relationships (report progress at 90%)
@@ -231,7 +301,21 @@ impl AnalysisEngine {
         });
 
         // Resolve relationships
-        let relationships = self.resolve_relationships(&all_files, &all_symbols, &file_imports)?;
+        let resolved = self.resolve_relationships(
+            project_id,
+            project_path,
+            &all_files,
+            &mut all_symbols,
+            &parsed_files,
+        )?;
+
+        // Images are only worth a node when a page, stylesheet or module links to them,
+        // and only those are read, to hash them
+        let linked: HashSet<&str> = resolved.relationships.iter().map(|r| r.target_id.as_str()).collect();
+        all_files.retain(|f| f.language != SupportedLanguage::Image.as_str() || linked.contains(f.id.as_str()));
+        for image in all_files.iter_mut().filter(|f| f.language == SupportedLanguage::Image.as_str()) {
+            image.content_hash = fs::read(&image.absolute_path).ok().map(|bytes| calculate_hash(&bytes));
+        }
 
         // Note: Don't send "complete" here - the command will send it AFTER storing to DB
         // to avoid race condition where frontend fetches data before it's stored
@@ -239,7 +323,11 @@ impl AnalysisEngine {
         Ok(AnalysisResult {
             files: all_files,
             symbols: all_symbols,
-            relationships,
+            relationships: resolved.relationships,
+            packages: resolved.packages,
+            unresolved_imports: resolved.unresolved_imports,
+            import_coverage: resolved.import_coverage,
+            broken_links: resolved.broken_links,
         })
     }
 
@@ -266,9 +354,14 @@ impl AnalysisEngine {
                 continue;
             }
 
-            // Check if it's a supported file type
+            // Check if it's a supported file type; extensionless scripts by their `#!` line
             let ext = entry_path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if SupportedLanguage::from_extension(ext).is_some() {
+            let is_supported = if ext.is_empty() {
+                is_shell_script(entry_path)
+            } else {
+                SupportedLanguage::from_extension(ext).is_some()
+            };
+            if is_supported {
                 // Discovery phase - report idle status with file being discovered
                 progress_callback(AnalysisProgress {
                     status: AnalysisStatus::Analyzing,
@@ -288,17 +381,26 @@ impl AnalysisEngine {
         project_id: &str,
         project_path: &Path,
         file_path: &Path,
-    ) -> NexusResult<(FileRecord, Vec<SymbolRecord>, Vec<super::parser::ImportInfo>)> {
+    ) -> NexusResult<(FileRecord, ParseResult)> {
         let ext = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let source = match SupportedLanguage::from_extension(ext) {
+            // Binary, and mostly never linked: hashed in `analyze` once known to be linked
+            Some(SupportedLanguage::Image) => String::new(),
+            _ => fs::read_to_string(file_path)?,
+        };
         let language = SupportedLanguage::from_extension(ext)
+            .or_else(|| SupportedLanguage::from_shebang(&source).filter(|_| ext.is_empty()))
             .ok_or_else(|| NexusError::ParseError {
                 file: file_path.display().to_string(),
                 line: 0,
                 message: "Unsupported file type".to_string(),
             })?;
 
-        let source = fs::read_to_string(file_path)?;
         let line_count = source.lines().count() as i32;
+        let language = match language {
+            SupportedLanguage::C if ext.eq_ignore_ascii_case("h") => SupportedLanguage::for_header(&source),
+            language => language,
+        };
 
         let file_id = Uuid::new_v4().to_string();
         let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
@@ -317,55 +419,431 @@ impl AnalysisEngine {
             language: language.as_str().to_string(),
             line_count,
             is_hidden: false,
-            content_hash: Some(calculate_hash(&source)),
+            content_hash: (language != SupportedLanguage::Image).then(|| calculate_hash(source.as_bytes())),
             last_modified: None,
         };
 
-        // For non-parseable languages (Swift, JSON, YAML, etc.), just return the file record
+        // For non-parseable languages (JSON, YAML, etc.), just return the file record
         // without symbol extraction
         if !language.requires_parsing() {
-            return Ok((file, vec![], vec![]));
+            return Ok((file, ParseResult::default()));
         }
 
         // Parse file with tree-sitter for full symbol extraction
         let parse_result = self.parser.parse_file(&file_id, language, &source)?;
 
-        Ok((file, parse_result.symbols, parse_result.imports))
+        Ok((file, parse_result))
     }
 
-    /// Resolve relationships between files and symbols
+    /// Resolve relationships between files and symbols, linking members declared
+    /// outside their type to their parent symbol along the way. Imports that do
+    /// not resolve to a project file are attributed to third-party packages and
+    /// recorded with the reason they did not resolve.
     fn resolve_relationships(
         &self,
+        project_id: &str,
+        project_path: &Path,
         files: &[FileRecord],
-        _symbols: &[SymbolRecord],
-        file_imports: &HashMap<String, Vec<super::parser::ImportInfo>>,
-    ) -> NexusResult<Vec<RelationshipRecord>> {
+        symbols: &mut [SymbolRecord],
+        parsed_files: &HashMap<String, ParseResult>,
+    ) -> NexusResult<ResolvedRelationships> {
         let mut relationships = Vec::new();
+        let mut packages: HashMap<ExternalPackage, PackageRecord> = HashMap::new();
+        // File -> file and file -> package edges, one per pair however many imports it has
+        let mut import_edges = ImportEdges::default();
 
-        // Build a map of file paths to file IDs
-        let path_to_id: HashMap<&str, &str> = files
-            .iter()
-            .map(|f| (f.path.as_str(), f.id.as_str()))
-            .collect();
+        let resolver = ImportResolver::new(project_path, files, parsed_files)
+            .with_include_dirs(&self.include_dirs)
+            .with_declarations(symbols);
 
-        // Also map file names for simpler resolution
-        let name_to_id: HashMap<&str, &str> = files
-            .iter()
-            .map(|f| (f.name.as_str(), f.id.as_str()))
-            .collect();
+        // Files each file imports, used to resolve symbols across files
+        let mut imported_files: HashMap<&str, Vec<&str>> = HashMap::new();
+        // Resolved imports and their target files, matched to symbols once members are linked
+        let mut resolved_imports: Vec<(&FileRecord, &ImportInfo, Vec<&str>)> = Vec::new();
+        let mut unresolved_imports = Vec::new();
+        let mut coverage: HashMap<&str, ImportCoverage> = HashMap::new();
 
         for file in files {
-            if let Some(imports) = file_imports.get(&file.id) {
-                for import in imports {
-                    // Try to resolve the import to a file
-                    let resolved = resolve_import(&import.source, &file.path, &path_to_id, &name_to_id);
+            if let Some(parsed) = parsed_files.get(&file.id) {
+                for import in &parsed.imports {
+                    // Try to resolve the import to files; a file importing itself adds nothing
+                    let mut targets = resolver.resolve(import, file);
+                    let resolves_to_self = targets.contains(&file.id.as_str());
+                    targets.retain(|&target_id| target_id != file.id);
+
+                    let counts = coverage.entry(file.language.as_str()).or_insert_with(|| ImportCoverage {
+                        language: file.language.clone(),
+                        ..Default::default()
+                    });
+                    counts.total += 1;
+
+                    if !targets.is_empty() || resolves_to_self {
+                        counts.resolved += 1;
+                        if !targets.is_empty() {
+                            imported_files.entry(file.id.as_str()).or_default().extend(&targets);
+                            resolved_imports.push((file, import, targets));
+                        }
+                        continue;
+                    }
+
+                    let unresolved = classify_unresolved(&file.language, import);
+                    match &unresolved {
+                        Unresolved::Package(_) | Unresolved::Builtin => counts.external += 1,
+                        Unresolved::MissingFile | Unresolved::Unknown => counts.missing += 1,
+                    }
+                    unresolved_imports.push(UnresolvedImportRecord {
+                        id: Uuid::new_v4().to_string(),
+                        project_id: project_id.to_string(),
+                        file_id: file.id.clone(),
+                        file_path: file.path.clone(),
+                        language: file.language.clone(),
+                        source: import.source.clone(),
+                        line: import.line,
+                        reason: unresolved.reason().to_string(),
+                    });
+
+                    if let Unresolved::Package(package) = unresolved {
+                        let package_id = &packages
+                            .entry(package)
+                            .or_insert_with_key(|package| PackageRecord {
+                                id: Uuid::new_v4().to_string(),
+                                project_id: project_id.to_string(),
+                                name: package.name.clone(),
+                                ecosystem: package.ecosystem.to_string(),
+                            })
+                            .id;
+
+                        import_edges.add(&file.id, package_id, import);
+                    }
+                }
+            }
+        }
+
+        let mut import_coverage: Vec<ImportCoverage> = coverage.into_values().collect();
+        import_coverage.sort_by(|a, b| a.language.cmp(&b.language));
+
+        // C# project references: .csproj -> .csproj edges between the referencing projects
+        let file_ids: HashMap<&Path, &str> = files.iter().map(|f| (Path::new(&f.path), f.id.as_str())).collect();
+        for project in csharp::load_projects(project_path, files) {
+            let Some(&source_id) = file_ids.get(project.path.as_path()) else { continue };
+            for reference in &project.references {
+                let Some(&target_id) = file_ids.get(reference.as_path()) else { continue };
+                relationships.push(RelationshipRecord {
+                    id: Uuid::new_v4().to_string(),
+                    source_id: source_id.to_string(),
+                    target_id: target_id.to_string(),
+                    kind: "imports".to_string(),
+                    metadata: None,
+                });
+            }
+        }
+
+        // Re-exports (`export * from './x'`): barrel -> file edges naming what is passed on
+        let mut re_exports = ReExports::default();
 
-                    if let Some(target_id) = resolved {
+        for file in files {
+            if let Some(parsed) = parsed_files.get(&file.id) {
+                for re_export in &parsed.re_exports {
+                    let import = ImportInfo {
+                        source: re_export.source.clone(),
+                        line: re_export.line,
+                        ..Default::default()
+                    };
+                    let targets = resolver.resolve(&import, file);
+                    for &target_id in &targets {
                         relationships.push(RelationshipRecord {
                             id: Uuid::new_v4().to_string(),
                             source_id: file.id.clone(),
                             target_id: target_id.to_string(),
-                            kind: "imports".to_string(),
+                            kind: "exports".to_string(),
+                            metadata: Some(serde_json::json!({ "names": re_export.exported_names() }).to_string()),
+                        });
+                    }
+                    if !targets.is_empty() {
+                        re_exports.by_file.entry(file.id.as_str()).or_default().push((re_export, targets));
+                    }
+                }
+            }
+        }
+
+        // Go packages, Swift modules and C# namespaces share declarations without importing each other
+        let modules = ModuleFiles::new(files, parsed_files);
+
+        // Link members declared outside their type (Rust impls, Go receivers, Swift extensions) to it
+        let parents = resolve_member_parents(files, symbols, parsed_files, &modules, &imported_files);
+        for symbol in symbols.iter_mut() {
+            if let Some(parent_id) = parents.get(symbol.id.as_str()) {
+                symbol.parent_id = Some(parent_id.clone());
+            }
+        }
+
+        let index = SymbolIndex::new(symbols);
+        let re_exports = self.follow_re_exports.then_some(&re_exports);
+
+        // Named re-exports: barrel -> symbol edges to the definitions they pass on
+        for (&file_id, entries) in re_exports.map(|r| &r.by_file).into_iter().flatten() {
+            for (re_export, targets) in entries {
+                for (name, _) in &re_export.names {
+                    let found = targets.iter().find_map(|target| {
+                        index.lookup_exported(target, name).or_else(|| {
+                            re_exports?.resolve(&index, target, name, &mut HashSet::new())
+                        })
+                    });
+                    if let Some(symbol) = found {
+                        relationships.push(RelationshipRecord {
+                            id: Uuid::new_v4().to_string(),
+                            source_id: file_id.to_string(),
+                            target_id: symbol.id.clone(),
+                            kind: "exports".to_string(),
+                            metadata: None,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Import edges: file -> file, plus file -> symbol for every name the import
+        // brings in. Names the target file does not define are recorded as broken
+        // on the file edge.
+        let files_by_id: HashMap<&str, &FileRecord> = files.iter().map(|f| (f.id.as_str(), f)).collect();
+        let mut seen_imports: HashSet<(&str, &str)> = HashSet::new();
+
+        for (file, import, target_ids) in &resolved_imports {
+            for &target_id in target_ids {
+                import_edges.add(&file.id, target_id, import).direct = true;
+            }
+
+            let targets: Vec<&FileRecord> = target_ids
+                .iter()
+                .filter_map(|id| files_by_id.get(id).copied())
+                .collect();
+            let matched = match_imported_names(&index, import, file, &targets, parsed_files, re_exports);
+
+            for symbol in matched.symbols {
+                // Reached through a barrel: the importer also depends on the defining file
+                if !target_ids.contains(&symbol.file_id.as_str()) {
+                    if let Some((&defining_id, _)) = files_by_id.get_key_value(symbol.file_id.as_str()) {
+                        if !import_edges.contains(&file.id, defining_id) {
+                            imported_files.entry(file.id.as_str()).or_default().push(defining_id);
+                        }
+                        let edge = import_edges.add(&file.id, defining_id, import);
+                        edge.via.get_or_insert(targets[0].path.as_str());
+                    }
+                }
+                if seen_imports.insert((file.id.as_str(), symbol.id.as_str())) {
+                    relationships.push(RelationshipRecord {
+                        id: Uuid::new_v4().to_string(),
+                        source_id: file.id.clone(),
+                        target_id: symbol.id.clone(),
+                        kind: "imports".to_string(),
+                        metadata: None,
+                    });
+                }
+            }
+
+            if !matched.missing.is_empty() {
+                tracing::debug!(
+                    "{}:{}: '{}' does not define {}",
+                    file.path,
+                    import.line,
+                    import.source,
+                    matched.missing.join(", ")
+                );
+                let names = &mut import_edges.add(&file.id, target_ids[0], import).broken_names;
+                for name in matched.missing {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+            }
+        }
+
+        relationships.extend(import_edges.into_relationships());
+
+        // Components a Vue or Svelte template renders: file -> file edges to the
+        // files the component names are imported from
+        for (file, import, target_ids) in &resolved_imports {
+            let Some(parsed) = parsed_files.get(&file.id) else { continue };
+            let components: Vec<&str> = parsed
+                .components
+                .iter()
+                .map(|c| c.name.as_str())
+                .filter(|&name| {
+                    import
+                        .imported_names
+                        .iter()
+                        .any(|n| n == name || n.strip_prefix("* as ") == Some(name))
+                })
+                .collect();
+            if components.is_empty() {
+                continue;
+            }
+            for &target_id in target_ids {
+                relationships.push(RelationshipRecord {
+                    id: Uuid::new_v4().to_string(),
+                    source_id: file.id.clone(),
+                    target_id: target_id.to_string(),
+                    kind: "references".to_string(),
+                    metadata: Some(serde_json::json!({ "components": components }).to_string()),
+                });
+            }
+        }
+
+        // Links and file mentions in Markdown documents: doc -> file edges, one
+        // per pair with the lines pointing at the file
+        let mut broken_links = Vec::new();
+
+        for file in files {
+            let Some(parsed) = parsed_files.get(&file.id) else { continue };
+            let mut linked: Vec<(&str, Vec<i32>)> = Vec::new();
+
+            for link in &parsed.links {
+                match resolver.resolve_doc_link(link, &file.path) {
+                    Some(target_id) if target_id != file.id => {
+                        match linked.iter_mut().find(|(id, _)| *id == target_id) {
+                            Some((_, lines)) => lines.push(link.line),
+                            None => linked.push((target_id, vec![link.line])),
+                        }
+                    }
+                    Some(_) => {}
+                    // Code blocks name files that are yet to be created or live elsewhere
+                    None if link.kind == DocLinkKind::Mention || resolver.doc_link_exists(link, &file.path) => {}
+                    None => broken_links.push(BrokenLinkRecord {
+                        id: Uuid::new_v4().to_string(),
+                        project_id: project_id.to_string(),
+                        file_id: file.id.clone(),
+                        file_path: file.path.clone(),
+                        target: link.target.clone(),
+                        line: link.line,
+                        kind: link.kind.as_str().to_string(),
+                    }),
+                }
+            }
+
+            for (target_id, lines) in linked {
+                relationships.push(RelationshipRecord {
+                    id: Uuid::new_v4().to_string(),
+                    source_id: file.id.clone(),
+                    target_id: target_id.to_string(),
+                    kind: "references".to_string(),
+                    metadata: Some(serde_json::json!({ "lines": lines }).to_string()),
+                });
+            }
+        }
+
+        // Files that build, tool and CI configurations point at: config -> file
+        // edges, one per pair with the keys naming the file
+        for file in files {
+            let mut targets: Vec<(&str, Vec<String>)> = Vec::new();
+
+            for (target_id, key) in resolver.resolve_config(file) {
+                if target_id == file.id {
+                    continue;
+                }
+                match targets.iter_mut().find(|(id, _)| *id == target_id) {
+                    Some((_, keys)) if !keys.contains(&key) => keys.push(key),
+                    Some(_) => {}
+                    None => targets.push((target_id, vec![key])),
+                }
+            }
+
+            for (target_id, keys) in targets {
+                relationships.push(RelationshipRecord {
+                    id: Uuid::new_v4().to_string(),
+                    source_id: file.id.clone(),
+                    target_id: target_id.to_string(),
+                    kind: "references".to_string(),
+                    metadata: Some(serde_json::json!({ "keys": keys }).to_string()),
+                });
+            }
+        }
+
+        // Resolve call sites to symbols: same file first, then imported files
+        let mut seen_calls: HashSet<(&str, &str)> = HashSet::new();
+
+        for file in files {
+            if let Some(parsed) = parsed_files.get(&file.id) {
+                let imported = imported_files
+                    .get(file.id.as_str())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+
+                for call in &parsed.calls {
+                    if let Some(target) = index.resolve_call(call, &file.id, imported) {
+                        // Skip recursion - a self-loop adds nothing to the graph
+                        if target.id == call.caller_id {
+                            continue;
+                        }
+
+                        if seen_calls.insert((call.caller_id.as_str(), target.id.as_str())) {
+                            relationships.push(RelationshipRecord {
+                                id: Uuid::new_v4().to_string(),
+                                source_id: call.caller_id.clone(),
+                                target_id: target.id.clone(),
+                                kind: "calls".to_string(),
+                                metadata: None,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        // Resolve supertypes: same file first, then its module, then imported files
+        let mut seen_inheritance: HashSet<(&str, &str)> = HashSet::new();
+
+        for file in files {
+            if let Some(parsed) = parsed_files.get(&file.id) {
+                let imported = imported_files
+                    .get(file.id.as_str())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+
+                for inheritance in &parsed.inheritance {
+                    let Some((source, target)) =
+                        index.resolve_inheritance(inheritance, &file.id, modules.of(&file.id), imported)
+                    else {
+                        continue;
+                    };
+
+                    if seen_inheritance.insert((source.id.as_str(), target.id.as_str())) {
+                        relationships.push(RelationshipRecord {
+                            id: Uuid::new_v4().to_string(),
+                            source_id: source.id.clone(),
+                            target_id: target.id.clone(),
+                            kind: inheritance_kind(source, target, &inheritance.kind).to_string(),
+                            metadata: None,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Types named in signatures, fields and generic arguments, resolved like supertypes
+        let mut seen_references: HashSet<(&str, &str)> = HashSet::new();
+
+        for file in files {
+            if let Some(parsed) = parsed_files.get(&file.id) {
+                let imported = imported_files
+                    .get(file.id.as_str())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+
+                for type_ref in &parsed.type_refs {
+                    let Some(target) = index.resolve_type(&type_ref.name, &file.id, modules.of(&file.id), imported) else {
+                        continue;
+                    };
+                    // Recursive types (`struct Node { next: Box<Node> }`) reference themselves
+                    if target.id == type_ref.symbol_id {
+                        continue;
+                    }
+
+                    if seen_references.insert((type_ref.symbol_id.as_str(), target.id.as_str())) {
+                        relationships.push(RelationshipRecord {
+                            id: Uuid::new_v4().to_string(),
+                            source_id: type_ref.symbol_id.clone(),
+                            target_id: target.id.clone(),
+                            kind: "references".to_string(),
                             metadata: None,
                         });
                     }
@@ -373,135 +851,1500 @@ impl AnalysisEngine {
             }
         }
 
-        Ok(relationships)
+        // Nesting: files contain their top-level symbols, symbols their members.
+        // A member whose parent lives in another file is also contained by its own file.
+        for symbol in symbols.iter() {
+            match symbol.parent_id.as_deref().and_then(|id| index.by_id.get(id)) {
+                Some(parent) => {
+                    relationships.push(contains(&parent.id, &symbol.id));
+                    if parent.file_id != symbol.file_id {
+                        relationships.push(contains(&symbol.file_id, &symbol.id));
+                    }
+                }
+                None => relationships.push(contains(&symbol.file_id, &symbol.id)),
+            }
+        }
+
+        Ok(ResolvedRelationships {
+            relationships,
+            packages: packages.into_values().collect(),
+            unresolved_imports,
+            import_coverage,
+            broken_links,
+        })
     }
 }
 
-impl Default for AnalysisEngine {
-    fn default() -> Self {
-        Self::new()
+/// Whether an extensionless file starts with a shell `#!` line. Git's object
+/// store is full of extensionless files and is never worth opening.
+fn is_shell_script(path: &Path) -> bool {
+    if path.components().any(|c| c.as_os_str() == ".git") {
+        return false;
     }
+    let mut head = [0u8; 128];
+    let Ok(read) = fs::File::open(path).and_then(|mut file| file.read(&mut head)) else { return false };
+    SupportedLanguage::from_shebang(&String::from_utf8_lossy(&head[..read])).is_some()
 }
 
-/// Calculate a simple hash of content
-fn calculate_hash(content: &str) -> String {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
+fn contains(source_id: &str, target_id: &str) -> RelationshipRecord {
+    RelationshipRecord {
+        id: Uuid::new_v4().to_string(),
+        source_id: source_id.to_string(),
+        target_id: target_id.to_string(),
+        kind: "contains".to_string(),
+        metadata: None,
+    }
+}
 
-    let mut hasher = DefaultHasher::new();
-    content.hash(&mut hasher);
-    format!("{:x}", hasher.finish())
+/// Parent symbol IDs for members declared outside their type, keyed by member ID.
+/// The type is looked up in the member's file, then the other files of its Go
+/// package or Swift module, then the files it imports.
+fn resolve_member_parents(
+    files: &[FileRecord],
+    symbols: &[SymbolRecord],
+    parsed_files: &HashMap<String, ParseResult>,
+    modules: &ModuleFiles,
+    imported_files: &HashMap<&str, Vec<&str>>,
+) -> HashMap<String, String> {
+    let index = SymbolIndex::new(symbols);
+
+    let mut parents = HashMap::new();
+    for file in files {
+        let Some(parsed) = parsed_files.get(&file.id) else { continue };
+        let imported = imported_files
+            .get(file.id.as_str())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        for member in &parsed.members {
+            let owner = index.resolve_type(&member.owner, &file.id, modules.of(&file.id), imported);
+
+            if let Some(owner) = owner.filter(|o| o.id != member.symbol_id) {
+                parents.insert(member.symbol_id.clone(), owner.id.clone());
+            }
+        }
+    }
+
+    parents
 }
 
-/// Extensions to try when resolving imports
-/// Includes code files and config files that code might import
-const IMPORT_EXTENSIONS: &[&str] = &[
-    "", ".ts", ".tsx", ".js", ".jsx", ".py", ".go", ".rs", ".c", ".h", ".swift",
-    // Config files that code might import
-    ".json", ".yaml", ".yml", ".md", ".html", ".css", ".scss", ".plist",
-];
-
-/// Resolve an import path to a file ID
-fn resolve_import<'a>(
-    import_source: &str,
-    current_path: &str,
-    path_to_id: &HashMap<&str, &'a str>,
-    name_to_id: &HashMap<&str, &'a str>,
-) -> Option<&'a str> {
-    // Handle relative imports
-    if import_source.starts_with('.') {
-        let current_dir = Path::new(current_path).parent()?;
-        let import_path = current_dir.join(import_source);
-
-        // Try with common extensions
-        for ext in IMPORT_EXTENSIONS {
-            let path_with_ext = if ext.is_empty() {
-                import_path.to_string_lossy().to_string()
-            } else {
-                format!("{}{}", import_path.to_string_lossy(), ext)
+/// Files whose top-level declarations are visible to each other without an
+/// import: the files of a Go package (one directory), of a Swift module (a
+/// SwiftPM target under `Sources/` or `Tests/`, otherwise the whole project),
+/// of a Java or Kotlin package, which may span several source roots, of a
+/// C# or PHP namespace, which may span several projects, and every Ruby file,
+/// since loaded classes and modules are global
+#[derive(Default)]
+struct ModuleFiles<'a> {
+    modules: Vec<Vec<&'a str>>,
+    by_file: HashMap<&'a str, usize>,
+}
+
+impl<'a> ModuleFiles<'a> {
+    fn new(files: &'a [FileRecord], parsed_files: &HashMap<String, ParseResult>) -> Self {
+        let mut keys: HashMap<(&str, &Path), usize> = HashMap::new();
+        let mut this = Self::default();
+
+        for file in files {
+            let path = Path::new(&file.path);
+            let (language, module) = match file.language.as_str() {
+                "go" => ("go", path.parent().unwrap_or(Path::new(""))),
+                "swift" => ("swift", swift_module_root(path)),
+                // Java and Kotlin share packages
+                "java" | "kotlin" => ("jvm", jvm::package_dir(path)),
+                // The first namespace a file declares; files declaring none share the global one
+                "csharp" | "php" => {
+                    let namespace = parsed_files.get(&file.id).and_then(|p| p.namespaces.first());
+                    (file.language.as_str(), Path::new(namespace.map_or("", String::as_str)))
+                }
+                "ruby" => ("ruby", Path::new("")),
+                _ => continue,
             };
+            let index = *keys.entry((language, module)).or_insert_with(|| {
+                this.modules.push(Vec::new());
+                this.modules.len() - 1
+            });
+            this.modules[index].push(file.id.as_str());
+            this.by_file.insert(file.id.as_str(), index);
+        }
 
-            if let Some(&id) = path_to_id.get(path_with_ext.as_str()) {
-                return Some(id);
-            }
+        this
+    }
+
+    /// Every file in the same module as `file_id`, itself included
+    fn of(&self, file_id: &str) -> &[&'a str] {
+        self.by_file
+            .get(file_id)
+            .map(|&index| self.modules[index].as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// `Sources/App/Models/User.swift` -> `Sources/App`; files outside a SwiftPM
+/// target directory all belong to one module rooted at the project
+fn swift_module_root(path: &Path) -> &Path {
+    path.ancestors()
+        .skip(1)
+        .filter(|dir| {
+            dir.parent()
+                .and_then(Path::file_name)
+                .is_some_and(|name| name == "Sources" || name == "Tests")
+        })
+        .last()
+        .unwrap_or(Path::new(""))
+}
+
+/// Symbols an import brings in by name, and the names its target files do not define
+#[derive(Default)]
+struct ImportedNames<'a> {
+    symbols: Vec<&'a SymbolRecord>,
+    missing: Vec<&'a str>,
+}
+
+/// Match the names an import brings in to symbols of its target files.
+///
+/// Namespace and wildcard imports bind no single symbol and are skipped. A name
+/// is only reported missing when a target file exports something, the name is
+/// not a module in its own right (`from pkg import submodule`,
+/// `use crate::{model}`) and the target does not re-import it.
+fn match_imported_names<'a>(
+    index: &SymbolIndex<'a>,
+    import: &'a ImportInfo,
+    file: &FileRecord,
+    targets: &[&'a FileRecord],
+    parsed_files: &'a HashMap<String, ParseResult>,
+    re_exports: Option<&ReExports<'a>>,
+) -> ImportedNames<'a> {
+    let mut matched = ImportedNames::default();
+    let mut names = import.imported_names.iter().map(String::as_str);
+
+    // A default import binds whatever the module exports as default, under any local name
+    if import.is_default && names.next().is_some() {
+        let default = targets.iter().find_map(|target| {
+            let parsed = parsed_files.get(&target.id)?;
+            let export = parsed.exports.iter().find(|e| e.is_default)?;
+            index.lookup(&target.id, &export.name).iter().find(|s| s.parent_id.is_none()).copied()
+        });
+        matched.symbols.extend(default);
+    }
+
+    // `use module::Type::{Variant, method}` brings in members of a type
+    let owners: Vec<&SymbolRecord> = match file.language.as_str() {
+        "rust" => import
+            .source
+            .rsplit("::")
+            .next()
+            .map(|owner| targets.iter().filter_map(|t| index.lookup_type(&t.id, owner)).collect())
+            .unwrap_or_default(),
+        // `import static a.b.Type.member`
+        "java" | "kotlin" => import
+            .source
+            .rsplit('.')
+            .next()
+            .map(|owner| targets.iter().filter_map(|t| index.lookup_type(&t.id, owner)).collect())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+
+    for name in names.filter(|n| !n.starts_with('*') && *n != "self") {
+        let found = targets.iter().find_map(|target| {
+            let exports = parsed_files.get(&target.id).map(|p| p.exports.as_slice()).unwrap_or_default();
+            // Rust child modules and Python importers can reach private items too
+            let visible = |s: &SymbolRecord| {
+                s.is_exported
+                    || exports.iter().any(|e| e.name == name)
+                    || matches!(target.language.as_str(), "rust" | "python")
+            };
+            index.lookup(&target.id, name).iter().copied().find(|s| {
+                if owners.is_empty() {
+                    s.parent_id.is_none() && visible(s)
+                } else {
+                    owners.iter().any(|o| s.parent_id.as_deref() == Some(o.id.as_str()))
+                }
+            })
+        });
+        let found = found.or_else(|| {
+            let re_exports = re_exports.filter(|_| owners.is_empty())?;
+            targets
+                .iter()
+                .find_map(|target| re_exports.resolve(index, &target.id, name, &mut HashSet::new()))
+        });
+
+        match found {
+            Some(symbol) => matched.symbols.push(symbol),
+            // Enum variants and other members are not all extracted as symbols
+            None if !owners.is_empty() => {}
+            None if targets.iter().any(|t| defines_name(index, t, name, parsed_files)) => {}
+            None => matched.missing.push(name),
         }
+    }
 
-        // Try index files
-        for index in &["index.ts", "index.tsx", "index.js", "index.jsx"] {
-            let index_path = import_path.join(index);
-            if let Some(&id) = path_to_id.get(index_path.to_string_lossy().as_ref()) {
-                return Some(id);
-            }
+    matched
+}
+
+/// Whether a target file could supply `name` without declaring it: the file is
+/// the module `name`, it exports or re-imports the name, or it exports nothing
+/// we can see (CommonJS, generated or empty modules) and so cannot be checked
+fn defines_name(
+    index: &SymbolIndex,
+    target: &FileRecord,
+    name: &str,
+    parsed_files: &HashMap<String, ParseResult>,
+) -> bool {
+    let path = Path::new(&target.path);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    let module = match stem {
+        "mod" | "__init__" | "index" => path.parent().and_then(|p| p.file_name()).and_then(|n| n.to_str()),
+        _ => Some(stem),
+    };
+    if module == Some(name) {
+        return true;
+    }
+
+    let Some(parsed) = parsed_files.get(&target.id) else { return true };
+    let exports_anything = index.exports_any(&target.id) || !parsed.exports.is_empty();
+    let has_symbols = SupportedLanguage::from_extension(path.extension().and_then(|e| e.to_str()).unwrap_or_default())
+        .is_some_and(|language| language.requires_parsing());
+
+    !has_symbols
+        || !exports_anything
+        || parsed.exports.iter().any(|e| e.name == name)
+        || parsed.re_exports.iter().any(|r| r.names.is_empty() && r.namespace.is_none())
+        || parsed
+            .imports
+            .iter()
+            .any(|i| i.imported_names.iter().any(|n| n == name || n == "*"))
+}
+
+/// File-level import edges, merging every import between the same two nodes
+#[derive(Default)]
+struct ImportEdges<'a> {
+    order: Vec<(&'a str, String)>,
+    edges: HashMap<(&'a str, String), ImportEdge<'a>>,
+}
+
+/// What the imports behind one file-level edge have in common
+#[derive(Default)]
+struct ImportEdge<'a> {
+    /// How the target is loaded, in order of first appearance
+    styles: Vec<ImportStyle>,
+    /// When the target is needed, in order of first appearance
+    usages: Vec<ImportUsage>,
+    /// Imported by the file itself rather than only reached through a barrel
+    direct: bool,
+    /// Barrel the imported names were re-exported from
+    via: Option<&'a str>,
+    /// Imported names the target does not define
+    broken_names: Vec<&'a str>,
+}
+
+impl<'a> ImportEdges<'a> {
+    fn contains(&self, source_id: &'a str, target_id: &str) -> bool {
+        self.edges.contains_key(&(source_id, target_id.to_string()))
+    }
+
+    /// The edge from `source_id` to `target_id`, recording how `import` loads it
+    fn add(&mut self, source_id: &'a str, target_id: &str, import: &ImportInfo) -> &mut ImportEdge<'a> {
+        let key = (source_id, target_id.to_string());
+        if !self.edges.contains_key(&key) {
+            self.order.push(key.clone());
+        }
+        let edge = self.edges.entry(key).or_default();
+        if !edge.styles.contains(&import.style) {
+            edge.styles.push(import.style);
+        }
+        if !edge.usages.contains(&import.usage) {
+            edge.usages.push(import.usage);
         }
+        edge
     }
 
-    // Try direct file name match
-    let file_name = Path::new(import_source)
-        .file_name()
-        .and_then(|n| n.to_str())?;
+    /// One `imports` relationship per edge. Style and usage are only recorded
+    /// when no import of the target is static or runtime respectively, since
+    /// one such import already makes the dependency unconditional.
+    fn into_relationships(mut self) -> impl Iterator<Item = RelationshipRecord> + 'a {
+        self.order.into_iter().map(move |key| {
+            let edge = self.edges.remove(&key).unwrap_or_default();
+            let mut metadata = serde_json::Map::new();
+            if !edge.styles.contains(&ImportStyle::Static) {
+                if let Some(style) = edge.styles.first() {
+                    metadata.insert("style".to_string(), style.as_str().into());
+                }
+            }
+            if !edge.usages.contains(&ImportUsage::Runtime) {
+                if let Some(usage) = edge.usages.first() {
+                    metadata.insert("usage".to_string(), usage.as_str().into());
+                }
+            }
+            if let Some(via) = edge.via.filter(|_| !edge.direct) {
+                metadata.insert("via".to_string(), via.into());
+            }
+            if !edge.broken_names.is_empty() {
+                metadata.insert("brokenNames".to_string(), edge.broken_names.into());
+            }
 
-    for ext in IMPORT_EXTENSIONS {
-        let name_with_ext = if ext.is_empty() {
-            file_name.to_string()
-        } else {
-            format!("{}{}", file_name, ext)
-        };
+            RelationshipRecord {
+                id: Uuid::new_v4().to_string(),
+                source_id: key.0.to_string(),
+                target_id: key.1,
+                kind: "imports".to_string(),
+                metadata: (!metadata.is_empty()).then(|| serde_json::Value::Object(metadata).to_string()),
+            }
+        })
+    }
+}
 
-        if let Some(&id) = name_to_id.get(name_with_ext.as_str()) {
-            return Some(id);
+/// `export ... from` statements of each file, with the files they resolve to
+#[derive(Default)]
+struct ReExports<'a> {
+    by_file: HashMap<&'a str, Vec<(&'a ReExportInfo, Vec<&'a str>)>>,
+}
+
+impl<'a> ReExports<'a> {
+    /// Follow the re-exports of `file_id` to the symbol exported as `name`
+    fn resolve(
+        &self,
+        index: &SymbolIndex<'a>,
+        file_id: &'a str,
+        name: &str,
+        visited: &mut HashSet<&'a str>,
+    ) -> Option<&'a SymbolRecord> {
+        // Barrels can re-export each other in a cycle
+        if !visited.insert(file_id) {
+            return None;
         }
+
+        self.by_file.get(file_id)?.iter().find_map(|(re_export, targets)| {
+            let original = match (re_export.names.is_empty(), &re_export.namespace) {
+                (true, None) => name,
+                (true, Some(_)) => return None,
+                (false, _) => {
+                    let (original, _) = re_export.names.iter().find(|(_, alias)| alias == name)?;
+                    original.as_str()
+                }
+            };
+            targets.iter().find_map(|target| {
+                index
+                    .lookup_exported(target, original)
+                    .or_else(|| self.resolve(index, target, original, visited))
+            })
+        })
     }
+}
 
-    None
+impl Default for AnalysisEngine {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::tempdir;
+/// Symbol kinds a call site can resolve to (classes/structs for constructor calls)
+const CALLABLE_KINDS: &[&str] = &["function", "method", "class", "struct"];
+
+/// Symbol kinds that can appear as a supertype or subtype (Ruby mixes modules in)
+const TYPE_KINDS: &[&str] = &["class", "interface", "trait", "struct", "enum", "type", "module"];
+
+/// Type kinds that are implemented rather than extended
+const INTERFACE_KINDS: &[&str] = &["interface", "trait"];
+
+/// Relationship kind for an inheritance edge. Interfaces always extend each other;
+/// anything else pointing at an interface implements it (Swift cannot tell a
+/// superclass from a protocol syntactically). Otherwise trust the extractor.
+fn inheritance_kind<'k>(source: &SymbolRecord, target: &SymbolRecord, declared: &'k str) -> &'k str {
+    if INTERFACE_KINDS.contains(&source.kind.as_str()) {
+        "extends"
+    } else if INTERFACE_KINDS.contains(&target.kind.as_str()) {
+        "implements"
+    } else {
+        declared
+    }
+}
 
-    #[test]
-    fn test_discover_files() {
-        let dir = tempdir().unwrap();
+/// Lookup tables over extracted symbols used when resolving references
+struct SymbolIndex<'a> {
+    by_id: HashMap<&'a str, &'a SymbolRecord>,
+    /// file_id -> symbol name -> symbols
+    by_file: HashMap<&'a str, HashMap<&'a str, Vec<&'a SymbolRecord>>>,
+}
 
-        // Create test files - all supported languages
-        fs::write(dir.path().join("test.ts"), "const x = 1;").unwrap();
-        fs::write(dir.path().join("test.py"), "x = 1").unwrap();
-        fs::write(dir.path().join("readme.md"), "# Readme").unwrap();
-        fs::write(dir.path().join("config.json"), "{}").unwrap();
-        fs::write(dir.path().join("styles.css"), "body {}").unwrap();
-        fs::write(dir.path().join("unknown.xyz"), "unknown").unwrap();
+impl<'a> SymbolIndex<'a> {
+    fn new(symbols: &'a [SymbolRecord]) -> Self {
+        let mut by_id = HashMap::new();
+        let mut by_file: HashMap<&str, HashMap<&str, Vec<&SymbolRecord>>> = HashMap::new();
+
+        for symbol in symbols {
+            by_id.insert(symbol.id.as_str(), symbol);
+            by_file
+                .entry(symbol.file_id.as_str())
+                .or_default()
+                .entry(symbol.name.as_str())
+                .or_default()
+                .push(symbol);
+        }
 
-        let engine = AnalysisEngine::new();
-        let files = engine.discover_files(dir.path(), &|_| {}).unwrap();
+        Self { by_id, by_file }
+    }
 
-        // Should find .ts, .py, .md, .json, .css but NOT .xyz
-        assert_eq!(files.len(), 5);
+    /// Symbols named `name` in the given file
+    fn lookup(&self, file_id: &str, name: &str) -> &[&'a SymbolRecord] {
+        self.by_file
+            .get(file_id)
+            .and_then(|names| names.get(name))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
     }
 
-    #[test]
-    fn test_parse_typescript_file() {
-        let dir = tempdir().unwrap();
-        let file_path = dir.path().join("test.ts");
+    /// Resolve a call site, looking in the calling file before its imports
+    fn resolve_call(
+        &self,
+        call: &CallInfo,
+        file_id: &str,
+        imported_files: &[&str],
+    ) -> Option<&'a SymbolRecord> {
+        if let Some(found) = self.best_call_target(self.lookup(file_id, &call.callee), call) {
+            return Some(found);
+        }
 
-        fs::write(&file_path, r#"
-            export function greet(name: string): string {
-                return `Hello, ${name}!`;
-            }
-        "#).unwrap();
+        imported_files.iter().find_map(|imported_id| {
+            let exported: Vec<_> = self
+                .lookup(imported_id, &call.callee)
+                .iter()
+                .copied()
+                .filter(|s| s.is_exported)
+                .collect();
+            self.best_call_target(&exported, call)
+        })
+    }
 
-        let engine = AnalysisEngine::new();
-        let (file, symbols, _) = engine.parse_file("project-1", dir.path(), &file_path).unwrap();
+    /// Whether the given file exports any symbol
+    fn exports_any(&self, file_id: &str) -> bool {
+        self.by_file
+            .get(file_id)
+            .is_some_and(|names| names.values().flatten().any(|s| s.is_exported))
+    }
 
-        assert_eq!(file.language, "typescript");
-        assert!(symbols.iter().any(|s| s.name == "greet"));
+    /// An exported top-level symbol named `name` in the given file
+    fn lookup_exported(&self, file_id: &str, name: &str) -> Option<&'a SymbolRecord> {
+        self.lookup(file_id, name)
+            .iter()
+            .find(|s| s.is_exported && s.parent_id.is_none())
+            .copied()
+    }
+
+    /// A type named `name` declared in the given file
+    fn lookup_type(&self, file_id: &str, name: &str) -> Option<&'a SymbolRecord> {
+        self.lookup(file_id, name)
+            .iter()
+            .find(|s| TYPE_KINDS.contains(&s.kind.as_str()))
+            .copied()
+    }
+
+    /// Resolve a type name, looking in the given file, then the rest of its
+    /// module, then its imports
+    fn resolve_type(
+        &self,
+        name: &str,
+        file_id: &str,
+        module_files: &[&str],
+        imported_files: &[&str],
+    ) -> Option<&'a SymbolRecord> {
+        self.lookup_type(file_id, name)
+            .or_else(|| module_files.iter().find_map(|module_file| self.lookup_type(module_file, name)))
+            .or_else(|| {
+                imported_files.iter().find_map(|imported_id| {
+                    self.lookup(imported_id, name)
+                        .iter()
+                        .find(|s| s.is_exported && TYPE_KINDS.contains(&s.kind.as_str()))
+                        .copied()
+                })
+            })
+    }
+
+    /// Resolve both ends of an extends/implements clause
+    fn resolve_inheritance(
+        &self,
+        inheritance: &InheritanceInfo,
+        file_id: &str,
+        module_files: &[&str],
+        imported_files: &[&str],
+    ) -> Option<(&'a SymbolRecord, &'a SymbolRecord)> {
+        let source = match &inheritance.symbol_id {
+            Some(id) => self.by_id.get(id.as_str()).copied(),
+            None => self.resolve_type(&inheritance.symbol_name, file_id, module_files, imported_files),
+        }?;
+        let target = self.resolve_type(&inheritance.base, file_id, module_files, imported_files)?;
+
+        (source.id != target.id).then_some((source, target))
+    }
+
+    /// Pick the most plausible callee among same-named candidates
+    fn best_call_target(
+        &self,
+        candidates: &[&'a SymbolRecord],
+        call: &CallInfo,
+    ) -> Option<&'a SymbolRecord> {
+        let callable: Vec<_> = candidates
+            .iter()
+            .copied()
+            .filter(|s| CALLABLE_KINDS.contains(&s.kind.as_str()))
+            .collect();
+
+        let preferred = match call.qualifier.as_deref() {
+            // Free function call: prefer top-level symbols over same-named methods
+            None => callable.iter().find(|s| s.parent_id.is_none()),
+            // this.foo() / self.foo(): prefer members of the caller's own type
+            Some("this" | "self" | "Self") => {
+                let caller_parent = self
+                    .by_id
+                    .get(call.caller_id.as_str())
+                    .and_then(|caller| caller.parent_id.as_deref());
+                callable
+                    .iter()
+                    .find(|s| caller_parent.is_some() && s.parent_id.as_deref() == caller_parent)
+            }
+            // Type::method() / Class.method(): prefer members of that type
+            Some(qualifier) => callable.iter().find(|s| {
+                s.parent_id
+                    .as_deref()
+                    .and_then(|parent| self.by_id.get(parent))
+                    .is_some_and(|parent| parent.name == qualifier)
+            }),
+        };
+
+        preferred.or_else(|| callable.first()).copied()
+    }
+}
+
+/// Calculate a simple hash of content
+fn calculate_hash(content: &[u8]) -> String {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    format!("{:x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_discover_files() {
+        let dir = tempdir().unwrap();
+
+        // Create test files - all supported languages
+        fs::write(dir.path().join("test.ts"), "const x = 1;").unwrap();
+        fs::write(dir.path().join("test.py"), "x = 1").unwrap();
+        fs::write(dir.path().join("readme.md"), "# Readme").unwrap();
+        fs::write(dir.path().join("config.json"), "{}").unwrap();
+        fs::write(dir.path().join("styles.css"), "body {}").unwrap();
+        fs::write(dir.path().join("unknown.xyz"), "unknown").unwrap();
+
+        let engine = AnalysisEngine::new();
+        let files = engine.discover_files(dir.path(), &|_| {}).unwrap();
+
+        // Should find .ts, .py, .md, .json, .css but NOT .xyz
+        assert_eq!(files.len(), 5);
+    }
+
+    #[test]
+    fn test_parse_typescript_file() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.ts");
+
+        fs::write(&file_path, r#"
+            export function greet(name: string): string {
+                return `Hello, ${name}!`;
+            }
+        "#).unwrap();
+
+        let engine = AnalysisEngine::new();
+        let (file, parsed) = engine.parse_file("project-1", dir.path(), &file_path).unwrap();
+
+        assert_eq!(file.language, "typescript");
+        assert!(parsed.symbols.iter().any(|s| s.name == "greet"));
+    }
+
+    #[test]
+    fn test_resolve_calls_across_files() {
+        let dir = tempdir().unwrap();
+
+        fs::write(dir.path().join("utils.ts"), r#"
+            export function helper() {}
+            function local() {}
+        "#).unwrap();
+        fs::write(dir.path().join("app.ts"), r#"
+            import { helper } from './utils';
+
+            function local() {}
+
+            export function main() {
+                helper();
+                local();
+            }
+        "#).unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let app = result.files.iter().find(|f| f.name == "app.ts").unwrap();
+        let utils = result.files.iter().find(|f| f.name == "utils.ts").unwrap();
+        let symbol = |file_id: &str, name: &str| {
+            result.symbols.iter().find(|s| s.file_id == file_id && s.name == name).unwrap().id.clone()
+        };
+
+        let calls: Vec<_> = result.relationships.iter().filter(|r| r.kind == "calls").collect();
+        assert_eq!(calls.len(), 2);
+        assert!(calls.iter().any(|r| r.source_id == symbol(&app.id, "main") && r.target_id == symbol(&utils.id, "helper")));
+        // Same-file definition wins over the imported file's private `local`
+        assert!(calls.iter().any(|r| r.source_id == symbol(&app.id, "main") && r.target_id == symbol(&app.id, "local")));
+    }
+
+    #[test]
+    fn test_resolve_inheritance_across_files() {
+        let dir = tempdir().unwrap();
+
+        fs::write(dir.path().join("shapes.ts"), r#"
+            export interface Shape {}
+            export interface Named {}
+            export class Base {}
+        "#).unwrap();
+        fs::write(dir.path().join("circle.ts"), r#"
+            import { Shape, Named, Base } from './shapes';
+
+            interface Round extends Shape {}
+            export class Circle extends Base implements Round, Named {}
+        "#).unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let shapes = result.files.iter().find(|f| f.name == "shapes.ts").unwrap();
+        let circle = result.files.iter().find(|f| f.name == "circle.ts").unwrap();
+        let symbol = |file_id: &str, name: &str| {
+            result.symbols.iter().find(|s| s.file_id == file_id && s.name == name).unwrap().id.clone()
+        };
+        let has_edge = |source: String, target: String, kind: &str| {
+            result.relationships.iter().any(|r| r.source_id == source && r.target_id == target && r.kind == kind)
+        };
+
+        assert!(has_edge(symbol(&circle.id, "Circle"), symbol(&shapes.id, "Base"), "extends"));
+        assert!(has_edge(symbol(&circle.id, "Circle"), symbol(&circle.id, "Round"), "implements"));
+        assert!(has_edge(symbol(&circle.id, "Circle"), symbol(&shapes.id, "Named"), "implements"));
+        assert!(has_edge(symbol(&circle.id, "Round"), symbol(&shapes.id, "Shape"), "extends"));
+    }
+
+    #[test]
+    fn test_swift_module_inheritance() {
+        let dir = tempdir().unwrap();
+        for target in ["Sources/App", "Sources/Other"] {
+            fs::create_dir_all(dir.path().join(target)).unwrap();
+        }
+
+        fs::write(dir.path().join("Sources/App/Animal.swift"), r#"
+            class Animal {}
+            protocol Named { var name: String { get } }
+        "#).unwrap();
+        fs::write(dir.path().join("Sources/App/Dog.swift"), r#"
+            import Foundation
+
+            final class Dog: Animal, Named {
+                var name: String { "dog" }
+                func owner() -> Owner? { nil }
+            }
+            extension Dog: CustomStringConvertible, Walkable {
+                var description: String { name }
+            }
+            protocol Walkable: Named {}
+            struct Owner {}
+        "#).unwrap();
+        // Another module's `Animal` is not visible without importing it
+        fs::write(dir.path().join("Sources/Other/Cat.swift"), "class Cat: Animal {}\n").unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+        let has_edge = |source: &str, target: &str, kind: &str| {
+            result.relationships.iter().any(|r| {
+                r.source_id == symbol(source).id && r.target_id == symbol(target).id && r.kind == kind
+            })
+        };
+
+        assert!(has_edge("Dog", "Animal", "extends"));
+        assert!(has_edge("Dog", "Named", "implements"));
+        assert!(has_edge("Dog", "Walkable", "implements"));
+        assert!(has_edge("Walkable", "Named", "extends"));
+        assert!(has_edge("owner", "Owner", "references"));
+        assert!(!has_edge("Cat", "Animal", "extends"));
+        // Extension members are linked to the extended type
+        assert_eq!(symbol("description").parent_id.as_deref(), Some(symbol("Dog").id.as_str()));
+    }
+
+    #[test]
+    fn test_java_kotlin_packages() {
+        let dir = tempdir().unwrap();
+        let model = dir.path().join("core/src/main/java/com/acme/model");
+        let app = dir.path().join("app/src/main/kotlin/com/acme/app");
+        fs::create_dir_all(&model).unwrap();
+        fs::create_dir_all(&app).unwrap();
+
+        fs::write(model.join("Entity.java"), "package com.acme.model;\n\npublic abstract class Entity {}\n").unwrap();
+        // Same package: no import needed
+        fs::write(model.join("Order.java"), r#"
+            package com.acme.model;
+
+            public class Order extends Entity {
+                public static Order create() { return new Order(); }
+            }
+        "#).unwrap();
+        fs::write(app.join("App.kt"), r#"
+            package com.acme.app
+
+            import com.acme.model.Order
+            import com.acme.model.Missing
+            import java.util.UUID
+
+            class App : Runnable {
+                override fun run() {
+                    val order = Order.create()
+                }
+            }
+        "#).unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+        let file = |name: &str| result.files.iter().find(|f| f.name == name).unwrap();
+        let has_edge = |source: &str, target: &str, kind: &str| {
+            result.relationships.iter().any(|r| r.source_id == source && r.target_id == target && r.kind == kind)
+        };
+
+        assert!(has_edge(&file("App.kt").id, &file("Order.java").id, "imports"));
+        assert!(has_edge(&symbol("Order").id, &symbol("Entity").id, "extends"));
+        assert!(has_edge(&symbol("run").id, &symbol("create").id, "calls"));
+
+        let unresolved: Vec<_> = result
+            .unresolved_imports
+            .iter()
+            .map(|u| (u.source.as_str(), u.reason.as_str()))
+            .collect();
+        assert_eq!(unresolved, vec![("com.acme.model", "unknown"), ("java.util", "builtin")]);
+    }
+
+    #[test]
+    fn test_csharp_namespaces_and_projects() {
+        let dir = tempdir().unwrap();
+        let core = dir.path().join("src/Core");
+        let app = dir.path().join("src/App");
+        fs::create_dir_all(core.join("Models")).unwrap();
+        fs::create_dir_all(&app).unwrap();
+
+        fs::write(core.join("Core.csproj"), "<Project Sdk=\"Microsoft.NET.Sdk\"></Project>").unwrap();
+        fs::write(app.join("App.csproj"), r#"
+            <Project Sdk="Microsoft.NET.Sdk">
+              <ItemGroup>
+                <ProjectReference Include="..\Core\Core.csproj" />
+              </ItemGroup>
+            </Project>
+        "#).unwrap();
+        fs::write(core.join("Models/Entity.cs"), "namespace Acme.Models;\n\npublic abstract class Entity {}\n").unwrap();
+        // Same namespace: no using needed
+        fs::write(core.join("Models/Order.cs"), r#"
+            namespace Acme.Models
+            {
+                public class Order : Entity
+                {
+                    public static Order Create() { return new Order(); }
+                }
+            }
+        "#).unwrap();
+        fs::write(app.join("Program.cs"), r#"
+            using System;
+            using Acme.Models;
+            using static Acme.Models.Order;
+
+            namespace Acme.App;
+
+            public class Program
+            {
+                public static void Main() { var order = Order.Create(); }
+            }
+        "#).unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+        let file = |name: &str| result.files.iter().find(|f| f.name == name).unwrap();
+        let has_edge = |source: &str, target: &str, kind: &str| {
+            result.relationships.iter().any(|r| r.source_id == source && r.target_id == target && r.kind == kind)
+        };
+
+        // `using Acme.Models` reaches every file of the namespace
+        assert!(has_edge(&file("Program.cs").id, &file("Order.cs").id, "imports"));
+        assert!(has_edge(&file("Program.cs").id, &file("Entity.cs").id, "imports"));
+        assert!(has_edge(&symbol("Order").id, &symbol("Entity").id, "extends"));
+        assert!(has_edge(&symbol("Main").id, &symbol("Create").id, "calls"));
+
+        // Projects are files of their own, not packages
+        assert_eq!(file("App.csproj").language, "msbuild");
+        assert!(has_edge(&file("App.csproj").id, &file("Core.csproj").id, "imports"));
+        assert!(result.packages.is_empty());
+
+        let unresolved: Vec<_> = result
+            .unresolved_imports
+            .iter()
+            .map(|u| (u.source.as_str(), u.reason.as_str()))
+            .collect();
+        assert_eq!(unresolved, vec![("System", "builtin")]);
+    }
+
+    #[test]
+    fn test_ruby_and_php() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("lib/billing")).unwrap();
+        fs::create_dir_all(dir.path().join("src/Models")).unwrap();
+
+        fs::write(dir.path().join("lib/billing/printable.rb"), "module Printable\n  def print_all; end\nend\n").unwrap();
+        fs::write(dir.path().join("lib/billing/invoice.rb"), r#"
+require 'json'
+require_relative 'printable'
+
+class Invoice
+  include Printable
+
+  def render
+    print_all()
+    JSON.generate({})
+  end
+end
+        "#).unwrap();
+
+        fs::write(dir.path().join("composer.json"), r#"{ "autoload": { "psr-4": { "App\\": "src/" } } }"#).unwrap();
+        fs::write(dir.path().join("src/Models/User.php"), r#"<?php
+namespace App\Models;
+
+class User
+{
+    public static function find(int $id): self { return new User(); }
+}
+        "#).unwrap();
+        fs::write(dir.path().join("index.php"), r#"<?php
+require_once __DIR__ . '/src/Models/User.php';
+
+use App\Models\User;
+
+function show(int $id): User { return User::find($id); }
+        "#).unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+        let file = |name: &str| result.files.iter().find(|f| f.name == name).unwrap();
+        let has_edge = |source: &str, target: &str, kind: &str| {
+            result.relationships.iter().any(|r| r.source_id == source && r.target_id == target && r.kind == kind)
+        };
+
+        assert!(has_edge(&file("invoice.rb").id, &file("printable.rb").id, "imports"));
+        assert!(has_edge(&symbol("Invoice").id, &symbol("Printable").id, "implements"));
+        assert!(has_edge(&symbol("render").id, &symbol("print_all").id, "calls"));
+
+        // PSR-4 `use` and `require_once` both reach the class file
+        assert!(has_edge(&file("index.php").id, &file("User.php").id, "imports"));
+        assert!(has_edge(&symbol("show").id, &symbol("find").id, "calls"));
+        assert!(has_edge(&symbol("show").id, &symbol("User").id, "references"));
+
+        let unresolved: Vec<_> = result
+            .unresolved_imports
+            .iter()
+            .map(|u| (u.source.as_str(), u.reason.as_str()))
+            .collect();
+        assert_eq!(unresolved, vec![("json", "builtin")]);
+    }
+
+    #[test]
+    fn test_vue_and_svelte_components() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/components")).unwrap();
+
+        fs::write(dir.path().join("src/components/UserCard.vue"), r#"<template><div>{{ user.name }}</div></template>
+<script setup lang="ts">
+import { formatName } from '../format'
+defineProps<{ user: User }>()
+</script>
+"#).unwrap();
+        fs::write(dir.path().join("src/components/Badge.svelte"), "<script>\n  export let label;\n</script>\n<span>{label}</span>\n").unwrap();
+        fs::write(dir.path().join("src/format.ts"), "export function formatName(n: string) { return n }\n").unwrap();
+        fs::write(dir.path().join("src/App.vue"), r#"<template>
+  <user-card :user="me" />
+  <Badge label="new" />
+</template>
+
+<script setup>
+import UserCard from './components/UserCard.vue'
+import Badge from './components/Badge.svelte'
+import { formatName } from './format'
+const me = { name: formatName('Ada') }
+</script>
+"#).unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let file = |name: &str| result.files.iter().find(|f| f.name == name).unwrap();
+        let edge = |source: &str, target: &str, kind: &str| {
+            result.relationships.iter().find(|r| r.source_id == source && r.target_id == target && r.kind == kind)
+        };
+        let app = &file("App.vue").id;
+
+        assert_eq!(file("App.vue").language, "vue");
+        assert!(edge(app, &file("UserCard.vue").id, "imports").is_some());
+        assert!(edge(&file("UserCard.vue").id, &file("format.ts").id, "imports").is_some());
+
+        let rendered = edge(app, &file("UserCard.vue").id, "references").unwrap();
+        assert_eq!(rendered.metadata.as_deref(), Some(r#"{"components":["UserCard"]}"#));
+        assert!(edge(app, &file("Badge.svelte").id, "references").is_some());
+        // Imported but not rendered
+        assert!(edge(app, &file("format.ts").id, "references").is_none());
+    }
+
+    #[test]
+    fn test_shell_scripts() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("scripts/lib")).unwrap();
+
+        fs::write(dir.path().join("scripts/lib/log.sh"), "log() { echo \"[deploy] $*\"; }\n").unwrap();
+        fs::write(dir.path().join("scripts/build.sh"), "#!/bin/sh\nmake all\n").unwrap();
+        // Extensionless, found by its shebang
+        fs::write(dir.path().join("scripts/deploy"), r#"#!/usr/bin/env bash
+source "$(dirname "$0")/lib/log.sh"
+
+main() {
+  log "building"
+  ./scripts/build.sh
+}
+main "$@"
+"#).unwrap();
+        fs::write(dir.path().join("LICENSE"), "MIT License\n").unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+        let file = |name: &str| result.files.iter().find(|f| f.name == name).unwrap();
+        let has_edge = |source: &str, target: &str, kind: &str| {
+            result.relationships.iter().any(|r| r.source_id == source && r.target_id == target && r.kind == kind)
+        };
+
+        assert_eq!(file("deploy").language, "shell");
+        assert!(result.files.iter().all(|f| f.name != "LICENSE"));
+        assert!(has_edge(&file("deploy").id, &file("log.sh").id, "imports"));
+        assert!(has_edge(&file("deploy").id, &file("build.sh").id, "imports"));
+        assert!(has_edge(&symbol("main").id, &symbol("log").id, "calls"));
+    }
+
+    #[test]
+    fn test_html_and_css_references() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/styles")).unwrap();
+        fs::create_dir_all(dir.path().join("public")).unwrap();
+
+        fs::write(dir.path().join("index.html"), r#"<!doctype html>
+<html>
+  <head>
+    <link rel="icon" href="/favicon.svg">
+    <link rel="stylesheet" href="/src/styles/app.css">
+  </head>
+  <body><script type="module" src="/src/main.ts"></script></body>
+</html>
+"#).unwrap();
+        fs::write(dir.path().join("src/main.ts"), "console.log('hi')\n").unwrap();
+        fs::write(dir.path().join("src/styles/app.css"), "@import './theme.css';\nbody { background: url(../../public/bg.png); }\n").unwrap();
+        fs::write(dir.path().join("src/styles/theme.css"), ":root { --accent: red; }\n").unwrap();
+        fs::write(dir.path().join("public/favicon.svg"), "<svg xmlns=\"http://www.w3.org/2000/svg\"/>\n").unwrap();
+        fs::write(dir.path().join("public/bg.png"), [0x89, b'P', b'N', b'G', 0xff, 0x00]).unwrap();
+        fs::write(dir.path().join("public/unused.png"), [0x89, b'P', b'N', b'G']).unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let file = |name: &str| result.files.iter().find(|f| f.name == name).unwrap();
+        let has_edge = |source: &str, target: &str| {
+            result.relationships.iter().any(|r| r.source_id == source && r.target_id == target && r.kind == "imports")
+        };
+
+        let index = &file("index.html").id;
+        assert!(has_edge(index, &file("main.ts").id));
+        assert!(has_edge(index, &file("app.css").id));
+        assert!(has_edge(index, &file("favicon.svg").id));
+        assert!(has_edge(&file("app.css").id, &file("theme.css").id));
+        assert!(has_edge(&file("app.css").id, &file("bg.png").id));
+        assert_eq!(file("bg.png").language, "image");
+        assert!(file("bg.png").content_hash.is_some());
+        // Images nothing links to are left out
+        assert!(result.files.iter().all(|f| f.name != "unused.png"));
+        assert!(result.unresolved_imports.is_empty());
+    }
+
+    #[test]
+    fn test_markdown_links() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("docs/img")).unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+
+        fs::write(dir.path().join("README.md"), r#"# App
+
+Read the [guide](docs/guide.md#setup) and the [API notes](docs/api.md).
+Licensed under [MIT](LICENSE), sources in [src](src/).
+
+```sh
+python src/main.py --config src/settings.yaml
+```
+"#).unwrap();
+        fs::write(dir.path().join("docs/guide.md"), "![Flow](img/flow.png)\nSee [main](../src/main.py) and [main again](/src/main.py).\n![Gone](img/gone.png)\n").unwrap();
+        fs::write(dir.path().join("docs/img/flow.png"), [0x89, b'P', b'N', b'G']).unwrap();
+        fs::write(dir.path().join("src/main.py"), "def main():\n    pass\n").unwrap();
+        fs::write(dir.path().join("src/util.py"), "def helper():\n    pass\n").unwrap();
+        fs::write(dir.path().join("LICENSE"), "MIT").unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let path = |id: &str| result.files.iter().find(|f| f.id == id).unwrap().path.as_str();
+        let mut references: Vec<_> = result
+            .relationships
+            .iter()
+            .filter(|r| r.kind == "references")
+            .map(|r| (path(&r.source_id), path(&r.target_id), r.metadata.as_deref().unwrap()))
+            .collect();
+        references.sort();
+        assert_eq!(
+            references,
+            vec![
+                ("README.md", "docs/guide.md", r#"{"lines":[3]}"#),
+                ("README.md", "src/main.py", r#"{"lines":[7]}"#),
+                ("docs/guide.md", "docs/img/flow.png", r#"{"lines":[1]}"#),
+                ("docs/guide.md", "src/main.py", r#"{"lines":[2,2]}"#),
+            ]
+        );
+
+        // The LICENSE and src/ exist, and code blocks may name files that do not
+        let broken: Vec<_> = result
+            .broken_links
+            .iter()
+            .map(|l| (l.file_path.as_str(), l.target.as_str(), l.line, l.kind.as_str()))
+            .collect();
+        assert_eq!(
+            broken,
+            vec![("README.md", "docs/api.md", 3, "link"), ("docs/guide.md", "img/gone.png", 3, "image")]
+        );
+        assert_eq!(AnalysisProgress::completed(&result).statistics.broken_links, 2);
+    }
+
+    #[test]
+    fn test_config_references() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::create_dir_all(dir.path().join("scripts")).unwrap();
+        fs::create_dir_all(dir.path().join(".github/workflows")).unwrap();
+        fs::create_dir_all(dir.path().join(".github/actions/setup")).unwrap();
+
+        fs::write(dir.path().join("package.json"), r#"{
+  "main": "src/index.ts",
+  "exports": { ".": "./src/index.ts" },
+  "scripts": { "build": "node scripts/build.mjs" }
+}"#).unwrap();
+        fs::write(dir.path().join("tsconfig.json"), r#"{ "include": ["src"] }"#).unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n\n[[bin]]\nname = \"tool\"\npath = \"src/tool.rs\"\n").unwrap();
+        fs::write(dir.path().join("src/index.ts"), "export const x = 1;\n").unwrap();
+        fs::write(dir.path().join("src/tool.rs"), "fn main() {}\n").unwrap();
+        fs::write(dir.path().join("scripts/build.mjs"), "console.log('build');\n").unwrap();
+        fs::write(dir.path().join(".github/workflows/ci.yml"), "jobs:\n  build:\n    steps:\n      - uses: ./.github/actions/setup\n").unwrap();
+        fs::write(dir.path().join(".github/actions/setup/action.yml"), "runs:\n  using: composite\n").unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let file = |path: &str| result.files.iter().find(|f| f.path == path).unwrap();
+        let path = |id: &str| result.files.iter().find(|f| f.id == id).unwrap().path.as_str();
+        assert_eq!(file("Cargo.toml").language, "toml");
+
+        let mut references: Vec<_> = result
+            .relationships
+            .iter()
+            .filter(|r| r.kind == "references")
+            .map(|r| (path(&r.source_id), path(&r.target_id), r.metadata.as_deref().unwrap()))
+            .collect();
+        references.sort();
+        assert_eq!(
+            references,
+            vec![
+                (".github/workflows/ci.yml", ".github/actions/setup/action.yml", r#"{"keys":["uses"]}"#),
+                ("Cargo.toml", "src/tool.rs", r#"{"keys":["bin.tool"]}"#),
+                ("package.json", "scripts/build.mjs", r#"{"keys":["scripts.build"]}"#),
+                ("package.json", "src/index.ts", r#"{"keys":["main","exports"]}"#),
+                ("tsconfig.json", "src/index.ts", r#"{"keys":["include"]}"#),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_references() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+
+        fs::write(dir.path().join("src/lib.rs"), "mod model;\nmod engine;\n").unwrap();
+        fs::write(dir.path().join("src/model.rs"), r#"
+            pub struct Project {}
+            pub struct Report {
+                pub project: Project,
+                pub children: Vec<Report>,
+            }
+        "#).unwrap();
+        fs::write(dir.path().join("src/engine.rs"), r#"
+            use crate::model::{Project, Report};
+
+            pub fn analyze<T: Into<String>>(project: &Project, name: T) -> Option<Report> {
+                None
+            }
+        "#).unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap().id.clone();
+        let references = |name: &str| {
+            let mut targets: Vec<_> = result
+                .relationships
+                .iter()
+                .filter(|r| r.kind == "references" && r.source_id == symbol(name))
+                .map(|r| result.symbols.iter().find(|s| s.id == r.target_id).unwrap().name.as_str())
+                .collect();
+            targets.sort();
+            targets
+        };
+
+        assert_eq!(references("analyze"), vec!["Project", "Report"]);
+        // The struct and its field both depend on the field's type; self-references are dropped
+        assert_eq!(references("Report"), vec!["Project"]);
+        assert_eq!(references("project"), vec!["Project"]);
+    }
+
+    #[test]
+    fn test_link_members_and_contains() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+
+        fs::write(dir.path().join("src/lib.rs"), "mod model;\nmod store;\n").unwrap();
+        fs::write(dir.path().join("src/model.rs"), "pub struct User {\n    pub name: String,\n}\n").unwrap();
+        fs::write(dir.path().join("src/store.rs"), r#"
+            use crate::model::User;
+
+            impl User {
+                pub fn rename(&mut self) {}
+            }
+        "#).unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let model = result.files.iter().find(|f| f.name == "model.rs").unwrap();
+        let store = result.files.iter().find(|f| f.name == "store.rs").unwrap();
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+        let has_contains = |source: &str, target: &str| {
+            result.relationships.iter().any(|r| r.kind == "contains" && r.source_id == source && r.target_id == target)
+        };
+
+        assert_eq!(symbol("rename").parent_id.as_deref(), Some(symbol("User").id.as_str()));
+        assert!(has_contains(&model.id, &symbol("User").id));
+        assert!(has_contains(&symbol("User").id, &symbol("name").id));
+        assert!(!has_contains(&model.id, &symbol("name").id));
+        // The method is a member of User but still lives in store.rs
+        assert!(has_contains(&symbol("User").id, &symbol("rename").id));
+        assert!(has_contains(&store.id, &symbol("rename").id));
+    }
+
+    #[test]
+    fn test_resolve_imported_names() {
+        let dir = tempdir().unwrap();
+
+        fs::write(dir.path().join("utils.ts"), r#"
+            export function helper() {}
+            export interface Options {}
+            function main() {}
+            export default main;
+        "#).unwrap();
+        fs::write(dir.path().join("app.ts"), r#"
+            import run, { helper, Options, missing } from './utils';
+        "#).unwrap();
+        fs::write(dir.path().join("settings.py"), "DEBUG = True\n").unwrap();
+        fs::write(dir.path().join("main.py"), "from settings import DEBUG\n").unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let file = |name: &str| result.files.iter().find(|f| f.name == name).unwrap().id.clone();
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap().id.clone();
+        let import = |source: String, target: String| {
+            result.relationships.iter().find(|r| r.kind == "imports" && r.source_id == source && r.target_id == target)
+        };
+
+        for name in ["helper", "Options", "main"] {
+            assert!(import(file("app.ts"), symbol(name)).is_some(), "{} not linked", name);
+        }
+        assert!(import(file("main.py"), symbol("DEBUG")).is_some());
+
+        let edge = import(file("app.ts"), file("utils.ts")).unwrap();
+        assert_eq!(edge.metadata.as_deref(), Some(r#"{"brokenNames":["missing"]}"#));
+        assert_eq!(import(file("main.py"), file("settings.py")).unwrap().metadata, None);
+    }
+
+    #[test]
+    fn test_missing_imported_names() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+
+        fs::write(dir.path().join("settings.py"), "DEBUG = True\n").unwrap();
+        fs::write(dir.path().join("main.py"), "from settings import DEBUG, VERBOSE\n").unwrap();
+        fs::write(dir.path().join("src/model.rs"), "pub struct User;\n").unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "mod model;\nuse crate::model::{User, Ghost};\n").unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let file = |name: &str| result.files.iter().find(|f| f.name == name).unwrap().id.clone();
+        let import = |source: String, target: String| {
+            result
+                .relationships
+                .iter()
+                .find(|r| r.kind == "imports" && r.source_id == source && r.target_id == target)
+                .unwrap()
+        };
+
+        let edge = import(file("main.py"), file("settings.py"));
+        assert_eq!(edge.metadata.as_deref(), Some(r#"{"brokenNames":["VERBOSE"]}"#));
+        let edge = import(file("lib.rs"), file("model.rs"));
+        assert_eq!(edge.metadata.as_deref(), Some(r#"{"brokenNames":["Ghost"]}"#));
+    }
+
+    #[test]
+    fn test_follow_re_exports() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("shapes")).unwrap();
+
+        fs::write(dir.path().join("shapes/circle.ts"), "export class Circle {}\n").unwrap();
+        fs::write(dir.path().join("shapes/square.ts"), "export class Square {}\n").unwrap();
+        fs::write(dir.path().join("shapes/round.ts"), "export * from './circle';\n").unwrap();
+        fs::write(dir.path().join("shapes/index.ts"), r#"
+            export * from './round';
+            export { Square as Box } from './square';
+        "#).unwrap();
+        fs::write(dir.path().join("app.ts"), r#"
+            import { Circle, Box } from './shapes';
+
+            function draw() {
+                new Circle();
+            }
+        "#).unwrap();
+
+        let analyze = |engine: AnalysisEngine| engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+        let result = analyze(AnalysisEngine::new());
+
+        let file = |name: &str| result.files.iter().find(|f| f.name == name).unwrap().id.clone();
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap().id.clone();
+        let edge = |source: String, target: String, kind: &str| {
+            result.relationships.iter().find(|r| r.kind == kind && r.source_id == source && r.target_id == target)
+        };
+
+        let re_export = edge(file("index.ts"), file("square.ts"), "exports").unwrap();
+        assert_eq!(re_export.metadata.as_deref(), Some(r#"{"names":["Square as Box"]}"#));
+        assert!(edge(file("index.ts"), symbol("Square"), "exports").is_some());
+
+        // The consumer is linked to the defining files and symbols behind the barrel
+        assert!(edge(file("app.ts"), symbol("Circle"), "imports").is_some());
+        assert!(edge(file("app.ts"), symbol("Square"), "imports").is_some());
+        let via = edge(file("app.ts"), file("circle.ts"), "imports").unwrap();
+        assert_eq!(via.metadata.as_deref(), Some(r#"{"via":"shapes/index.ts"}"#));
+        assert!(edge(symbol("draw"), symbol("Circle"), "calls").is_some());
+        assert_eq!(edge(file("app.ts"), file("index.ts"), "imports").unwrap().metadata, None);
+
+        let result = analyze(AnalysisEngine::new().with_follow_re_exports(false));
+        let file = |name: &str| result.files.iter().find(|f| f.name == name).unwrap().id.clone();
+        assert!(result.relationships.iter().any(|r| r.kind == "exports" && r.source_id == file("index.ts")));
+        assert!(!result.relationships.iter().any(|r| r.source_id == file("app.ts") && r.target_id == file("circle.ts")));
+    }
+
+    #[test]
+    fn test_external_packages() {
+        let dir = tempdir().unwrap();
+
+        fs::write(dir.path().join("app.tsx"), r#"
+            import React from 'react';
+            import { createRoot } from 'react-dom/client';
+            import { invoke } from '@tauri-apps/api/core';
+            import { Panel } from './panel';
+            import fs from 'node:fs';
+        "#).unwrap();
+        fs::write(dir.path().join("panel.tsx"), r#"
+            import { useState } from 'react';
+            export function Panel() {}
+        "#).unwrap();
+        fs::write(dir.path().join("main.py"), "import os\nimport numpy.linalg\nfrom numpy import array\n").unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let mut packages: Vec<_> = result
+            .packages
+            .iter()
+            .map(|p| format!("{}:{}", p.ecosystem, p.name))
+            .collect();
+        packages.sort();
+        assert_eq!(packages, vec!["npm:@tauri-apps/api", "npm:react", "npm:react-dom", "pypi:numpy"]);
+
+        let package_id = |name: &str| result.packages.iter().find(|p| p.name == name).unwrap().id.clone();
+        let users = |name: &str| result.relationships.iter().filter(|r| r.target_id == package_id(name)).count();
+        assert_eq!(users("react"), 2);
+        // Two imports of numpy from the same file make one edge
+        assert_eq!(users("numpy"), 1);
+    }
+
+    #[test]
+    fn test_unresolved_imports() {
+        let dir = tempdir().unwrap();
+
+        fs::write(dir.path().join("app.ts"), r#"
+            import React from 'react';
+            import { readFile } from 'node:fs';
+            import { Panel } from './panel';
+            import { gone } from './gone';
+            import { Button } from '@/components/Button';
+        "#).unwrap();
+        fs::write(dir.path().join("panel.ts"), "export function Panel() {}").unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let unresolved: Vec<_> = result
+            .unresolved_imports
+            .iter()
+            .map(|i| (i.line, i.source.as_str(), i.reason.as_str()))
+            .collect();
+        assert_eq!(
+            unresolved,
+            vec![
+                (2, "react", "external"),
+                (3, "node:fs", "builtin"),
+                (5, "./gone", "missing-file"),
+                (6, "@/components/Button", "unknown"),
+            ]
+        );
+
+        let expected = ImportCoverage {
+            language: "typescript".to_string(),
+            total: 5,
+            resolved: 1,
+            external: 2,
+            missing: 2,
+        };
+        assert_eq!(result.import_coverage, vec![expected]);
+
+        let statistics = AnalysisProgress::completed(&result).statistics;
+        assert_eq!((statistics.total_imports, statistics.unresolved_imports), (5, 4));
+    }
+
+    #[test]
+    fn test_import_usage_metadata() {
+        let dir = tempdir().unwrap();
+
+        fs::write(dir.path().join("app.ts"), r#"
+            import type { Props } from './props';
+            import type { Theme } from './theme';
+            import { applyTheme } from './theme';
+        "#).unwrap();
+        fs::write(dir.path().join("props.ts"), "export interface Props {}").unwrap();
+        fs::write(dir.path().join("theme.ts"), "export type Theme = {}; export function applyTheme() {}").unwrap();
+        fs::write(
+            dir.path().join("service.py"),
+            "from typing import TYPE_CHECKING\nif TYPE_CHECKING:\n    from models import User\n",
+        ).unwrap();
+        fs::write(dir.path().join("models.py"), "class User:\n    pass\n").unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let file_id = |name: &str| result.files.iter().find(|f| f.name == name).unwrap().id.clone();
+        let metadata = |source: &str, target: &str| {
+            result
+                .relationships
+                .iter()
+                .find(|r| r.source_id == file_id(source) && r.target_id == file_id(target))
+                .map(|r| r.metadata.clone())
+                .unwrap()
+        };
+
+        assert_eq!(metadata("app.ts", "props.ts").as_deref(), Some(r#"{"usage":"type-only"}"#));
+        assert_eq!(metadata("service.py", "models.py").as_deref(), Some(r#"{"usage":"type-only"}"#));
+        // One runtime import keeps the dependency at runtime
+        assert_eq!(metadata("app.ts", "theme.ts"), None);
+    }
+
+    #[test]
+    fn test_dynamic_import_styles() {
+        let dir = tempdir().unwrap();
+
+        fs::write(dir.path().join("main.js"), r#"
+            import { format } from './format';
+            const config = require('./config');
+            const lodash = require('lodash');
+
+            async function open() {
+                const { Page } = await import('./page');
+                const more = await import('./format');
+            }
+        "#).unwrap();
+        fs::write(dir.path().join("format.js"), "export function format() {}").unwrap();
+        fs::write(dir.path().join("config.js"), "module.exports = {};").unwrap();
+        fs::write(dir.path().join("page.js"), "export class Page {}").unwrap();
+
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze("project-1", dir.path(), |_| {}).unwrap();
+
+        let file_id = |name: &str| result.files.iter().find(|f| f.name == name).unwrap().id.clone();
+        let lodash_id = result.packages.iter().find(|p| p.name == "lodash").unwrap().id.clone();
+        let metadata = |target_id: String| {
+            result
+                .relationships
+                .iter()
+                .find(|r| r.source_id == file_id("main.js") && r.target_id == target_id)
+                .map(|r| r.metadata.clone())
+                .unwrap()
+        };
+
+        assert_eq!(metadata(file_id("config.js")).as_deref(), Some(r#"{"style":"commonjs"}"#));
+        assert_eq!(metadata(file_id("page.js")).as_deref(), Some(r#"{"style":"dynamic"}"#));
+        assert_eq!(metadata(lodash_id).as_deref(), Some(r#"{"style":"commonjs"}"#));
+        // Also imported statically, so the dependency is not dynamic
+        assert_eq!(metadata(file_id("format.js")), None);
+
+        // Destructured names still link to the symbols they load
+        let page = result.symbols.iter().find(|s| s.name == "Page").unwrap();
+        assert!(result
+            .relationships
+            .iter()
+            .any(|r| r.source_id == file_id("main.js") && r.target_id == page.id));
     }
 
     #[test]
     fn test_calculate_hash() {
-        let hash1 = calculate_hash("hello world");
-        let hash2 = calculate_hash("hello world");
-        let hash3 = calculate_hash("different");
+        let hash1 = calculate_hash(b"hello world");
+        let hash2 = calculate_hash(b"hello world");
+        let hash3 = calculate_hash(b"different");
 
         assert_eq!(hash1, hash2);
         assert_ne!(hash1, hash3);
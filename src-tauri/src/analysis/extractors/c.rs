@@ -1,6 +1,6 @@
 use tree_sitter::Node;
 
-use super::{create_symbol, find_child, node_text};
+use super::{add_type_refs, create_symbol, extract_calls, find_child, node_text};
 use crate::analysis::parser::{ImportInfo, ParseResult};
 
 /// Extract symbols and relationships from C AST
@@ -26,10 +26,9 @@ pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResu
     }
 }
 
-fn extract_include(node: &Node, source: &[u8], result: &mut ParseResult) {
+pub(super) fn extract_include(node: &Node, source: &[u8], result: &mut ParseResult) {
     // Get the include path
-    let path_node = find_child(node, "string_literal")
-        .or_else(|| find_child(node, "system_lib_string"));
+    let path_node = node.child_by_field_name("path");
 
     if let Some(path) = path_node {
         let path_text = node_text(&path, source);
@@ -41,7 +40,9 @@ fn extract_include(node: &Node, source: &[u8], result: &mut ParseResult) {
             source: cleaned.to_string(),
             imported_names: vec![],
             is_default: true,
+            is_system: path.kind() == "system_lib_string",
             line: node.start_position().row as i32 + 1,
+            ..Default::default()
         });
     }
 }
@@ -76,7 +77,7 @@ fn extract_function(file_id: &str, node: &Node, source: &[u8], result: &mut Pars
     // In C, functions not marked static are exported
     let is_static = node_text(node, source).starts_with("static");
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         name,
         "function",
@@ -85,7 +86,14 @@ fn extract_function(file_id: &str, node: &Node, source: &[u8], result: &mut Pars
         None,
         !is_static,
         None,
-    ));
+    );
+
+    add_type_refs(&symbol.id, node, &["type", "declarator"], source, result);
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_calls(&symbol.id, &body, source, result);
+    }
+
+    result.symbols.push(symbol);
 }
 
 fn extract_declaration(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) {
@@ -99,7 +107,7 @@ fn extract_declaration(file_id: &str, node: &Node, source: &[u8], result: &mut P
                 .map(|n| node_text(&n, source))
                 .unwrap_or("anonymous");
 
-            result.symbols.push(create_symbol(
+            let symbol = create_symbol(
                 file_id,
                 name,
                 "function",
@@ -108,7 +116,9 @@ fn extract_declaration(file_id: &str, node: &Node, source: &[u8], result: &mut P
                 None,
                 true,
                 None,
-            ));
+            );
+            add_type_refs(&symbol.id, node, &["type", "declarator"], source, result);
+            result.symbols.push(symbol);
             return;
         }
     }
@@ -130,7 +140,7 @@ fn extract_declaration(file_id: &str, node: &Node, source: &[u8], result: &mut P
 
                 let kind = if is_const { "constant" } else { "variable" };
 
-                result.symbols.push(create_symbol(
+                let symbol = create_symbol(
                     file_id,
                     name,
                     kind,
@@ -139,7 +149,9 @@ fn extract_declaration(file_id: &str, node: &Node, source: &[u8], result: &mut P
                     None,
                     !is_static,
                     None,
-                ));
+                );
+                add_type_refs(&symbol.id, node, &["type"], source, result);
+                result.symbols.push(symbol);
             }
         }
     }
@@ -160,7 +172,7 @@ fn extract_type_def(file_id: &str, node: &Node, source: &[u8], result: &mut Pars
 
     let signature = format!("{} {}", kind, name);
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         name,
         kind,
@@ -169,7 +181,10 @@ fn extract_type_def(file_id: &str, node: &Node, source: &[u8], result: &mut Pars
         None,
         true,
         None,
-    ));
+    );
+    // Field types: `struct list { struct node *head; }`
+    add_type_refs(&symbol.id, node, &["body"], source, result);
+    result.symbols.push(symbol);
 }
 
 fn extract_typedef(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) {
@@ -183,7 +198,7 @@ fn extract_typedef(file_id: &str, node: &Node, source: &[u8], result: &mut Parse
     if let Some(name_node) = identifiers.last() {
         let name = node_text(name_node, source);
 
-        result.symbols.push(create_symbol(
+        let symbol = create_symbol(
             file_id,
             name,
             "type",
@@ -192,7 +207,9 @@ fn extract_typedef(file_id: &str, node: &Node, source: &[u8], result: &mut Parse
             None,
             true,
             None,
-        ));
+        );
+        add_type_refs(&symbol.id, node, &["type"], source, result);
+        result.symbols.push(symbol);
     }
 }
 
@@ -233,6 +250,21 @@ struct User {
         assert!(result.symbols.iter().any(|s| s.name == "User" && s.kind == "struct"));
     }
 
+    #[test]
+    fn test_extract_calls() {
+        let parser = Parser::new();
+        let source = r#"
+int main(void) {
+    init();
+    return run(parse(1));
+}
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::C, source).unwrap();
+        let callees: Vec<_> = result.calls.iter().map(|c| c.callee.as_str()).collect();
+        assert_eq!(callees, vec!["init", "run", "parse"]);
+    }
+
     #[test]
     fn test_extract_includes() {
         let parser = Parser::new();
@@ -243,7 +275,7 @@ struct User {
 
         let result = parser.parse_file("test", SupportedLanguage::C, source).unwrap();
         assert!(result.imports.len() >= 2);
-        assert!(result.imports.iter().any(|i| i.source == "stdio.h"));
-        assert!(result.imports.iter().any(|i| i.source == "myheader.h"));
+        assert!(result.imports.iter().any(|i| i.source == "stdio.h" && i.is_system));
+        assert!(result.imports.iter().any(|i| i.source == "myheader.h" && !i.is_system));
     }
 }
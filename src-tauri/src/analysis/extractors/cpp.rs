@@ -0,0 +1,425 @@
+use tree_sitter::Node;
+
+use super::c::extract_include;
+use super::{add_inheritance, add_type_refs, create_symbol, extract_calls, find_child, node_text};
+use crate::analysis::parser::{MemberInfo, ParseResult};
+
+/// Where a declaration sits: the enclosing namespace or class, and whether
+/// it is visible outside the file
+#[derive(Clone, Default)]
+struct Scope {
+    parent_id: Option<String>,
+    /// Inside a class body, as opposed to a namespace or the file
+    in_class: bool,
+    /// Public access in a class; outside an anonymous namespace otherwise
+    is_exported: bool,
+    /// `template <...>` prefix of the declaration being extracted
+    template: Option<String>,
+}
+
+/// Extract symbols and relationships from C++ AST
+pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResult) {
+    let scope = Scope {
+        is_exported: true,
+        ..Default::default()
+    };
+    extract_declarations(file_id, root, source, result, &scope);
+}
+
+/// Declarations directly below a translation unit, namespace or linkage block
+fn extract_declarations(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, scope: &Scope) {
+    let mut cursor = node.walk();
+
+    for child in node.children(&mut cursor) {
+        extract_declaration(file_id, &child, source, result, scope);
+    }
+}
+
+fn extract_declaration(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, scope: &Scope) {
+    match node.kind() {
+        "preproc_include" => extract_include(node, source, result),
+        "namespace_definition" => extract_namespace(file_id, node, source, result, scope),
+        "template_declaration" => extract_template(file_id, node, source, result, scope),
+        "class_specifier" | "struct_specifier" | "union_specifier" | "enum_specifier" => {
+            extract_type(file_id, node, source, result, scope);
+        }
+        "function_definition" => extract_function(file_id, node, source, result, scope),
+        "declaration" | "field_declaration" => extract_variables_or_prototypes(file_id, node, source, result, scope),
+        "alias_declaration" | "type_definition" => extract_alias(file_id, node, source, result, scope),
+        // `extern "C" { ... }`, `#ifdef` blocks
+        "linkage_specification" | "declaration_list" | "preproc_if" | "preproc_ifdef" | "preproc_else"
+        | "preproc_elif" => extract_declarations(file_id, node, source, result, scope),
+        _ => {}
+    }
+}
+
+fn extract_namespace(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, scope: &Scope) {
+    let Some(body) = node.child_by_field_name("body") else { return };
+
+    // Anonymous namespaces have internal linkage and no symbol of their own
+    let Some(name) = node.child_by_field_name("name") else {
+        let scope = Scope {
+            is_exported: false,
+            ..scope.clone()
+        };
+        extract_declarations(file_id, &body, source, result, &scope);
+        return;
+    };
+    let name = node_text(&name, source);
+
+    let symbol = create_symbol(
+        file_id,
+        name,
+        "namespace",
+        node,
+        Some(format!("namespace {}", name)),
+        None,
+        scope.is_exported,
+        scope.parent_id.clone(),
+    );
+
+    let scope = Scope {
+        parent_id: Some(symbol.id.clone()),
+        template: None,
+        ..scope.clone()
+    };
+    result.symbols.push(symbol);
+    extract_declarations(file_id, &body, source, result, &scope);
+}
+
+fn extract_template(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, scope: &Scope) {
+    let parameters = node
+        .child_by_field_name("parameters")
+        .map(|p| node_text(&p, source))
+        .unwrap_or("<>");
+    let scope = Scope {
+        template: Some(format!("template {}", parameters)),
+        ..scope.clone()
+    };
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        extract_declaration(file_id, &child, source, result, &scope);
+    }
+}
+
+/// A class, struct, union or enum definition, with its base classes and members
+fn extract_type(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, scope: &Scope) {
+    // `struct Point p;` only names the type
+    let Some(body) = node.child_by_field_name("body") else { return };
+    let Some(name) = node.child_by_field_name("name") else { return };
+    let name = super::type_name(&name, source).unwrap_or_else(|| node_text(&name, source));
+
+    let (kind, keyword) = match node.kind() {
+        "class_specifier" => ("class", "class"),
+        "union_specifier" => ("union", "union"),
+        "enum_specifier" => ("enum", "enum"),
+        _ => ("struct", "struct"),
+    };
+    let signature = match &scope.template {
+        Some(template) => format!("{} {} {}", template, keyword, name),
+        None => format!("{} {}", keyword, name),
+    };
+
+    let symbol = create_symbol(
+        file_id,
+        name,
+        kind,
+        node,
+        Some(signature),
+        None,
+        scope.is_exported,
+        scope.parent_id.clone(),
+    );
+
+    // `class Circle : public Shape, private Named<T>`
+    if let Some(bases) = find_child(node, "base_class_clause") {
+        let mut cursor = bases.walk();
+        for base in bases.named_children(&mut cursor) {
+            if base.kind() != "access_specifier" {
+                add_inheritance(Some(&symbol.id), name, &base, "extends", source, result);
+            }
+        }
+    }
+
+    let symbol_id = symbol.id.clone();
+    result.symbols.push(symbol);
+
+    if kind == "enum" {
+        return;
+    }
+
+    // Members are private in a class and public in a struct until an access specifier says otherwise
+    let mut member_scope = Scope {
+        parent_id: Some(symbol_id.clone()),
+        in_class: true,
+        is_exported: scope.is_exported && kind != "class",
+        template: None,
+    };
+    let mut cursor = body.walk();
+    for member in body.children(&mut cursor) {
+        match member.kind() {
+            "access_specifier" => {
+                member_scope.is_exported = scope.is_exported && node_text(&member, source) == "public";
+            }
+            // Data members: their types are referenced by the class
+            "field_declaration" if find_function_declarator(&member).is_none() => {
+                add_type_refs(&symbol_id, &member, &["type"], source, result);
+                if let Some(nested) = member.child_by_field_name("type") {
+                    extract_declaration(file_id, &nested, source, result, &member_scope);
+                }
+            }
+            _ => extract_declaration(file_id, &member, source, result, &member_scope),
+        }
+    }
+}
+
+/// A function or method with a body. `Type::method` defined outside its class
+/// is linked to the class by name once all files are parsed.
+fn extract_function(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, scope: &Scope) {
+    let Some(declarator) = find_function_declarator(node) else { return };
+    let Some(name_node) = declarator.child_by_field_name("declarator") else { return };
+    let (name, owner) = declarator_name(&name_node, source);
+
+    let kind = if scope.in_class || owner.is_some() { "method" } else { "function" };
+    let is_static = node_text(node, source).starts_with("static");
+    let symbol = create_symbol(
+        file_id,
+        name,
+        kind,
+        node,
+        Some(function_signature(node, &declarator, scope, source)),
+        None,
+        scope.is_exported && !is_static,
+        scope.parent_id.clone().filter(|_| owner.is_none() || scope.in_class),
+    );
+
+    if let Some(owner) = owner.filter(|_| !scope.in_class) {
+        result.members.push(MemberInfo {
+            symbol_id: symbol.id.clone(),
+            owner: owner.to_string(),
+        });
+    }
+
+    add_type_refs(&symbol.id, node, &["type", "declarator"], source, result);
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_calls(&symbol.id, &body, source, result);
+    }
+
+    result.symbols.push(symbol);
+}
+
+/// Function prototypes, member function declarations and variables
+fn extract_variables_or_prototypes(
+    file_id: &str,
+    node: &Node,
+    source: &[u8],
+    result: &mut ParseResult,
+    scope: &Scope,
+) {
+    // `class Foo {...} foo;` and `struct Bar {...};` define a type on the way
+    if let Some(ty) = node.child_by_field_name("type") {
+        extract_declaration(file_id, &ty, source, result, scope);
+    }
+
+    if let Some(declarator) = find_function_declarator(node) {
+        let Some(name_node) = declarator.child_by_field_name("declarator") else { return };
+        let (name, owner) = declarator_name(&name_node, source);
+        // `friend` declarations and redeclarations of another scope's function
+        if owner.is_some() || find_child(node, "friend").is_some() {
+            return;
+        }
+
+        let symbol = create_symbol(
+            file_id,
+            name,
+            if scope.in_class { "method" } else { "function" },
+            node,
+            Some(function_signature(node, &declarator, scope, source)),
+            None,
+            scope.is_exported,
+            scope.parent_id.clone(),
+        );
+        add_type_refs(&symbol.id, node, &["type", "declarator"], source, result);
+        result.symbols.push(symbol);
+        return;
+    }
+
+    // Variables at namespace scope; data members are not symbols of their own
+    if scope.in_class {
+        return;
+    }
+    let text = node_text(node, source);
+    let is_static = text.starts_with("static");
+    let is_const = text.contains("const");
+
+    let mut cursor = node.walk();
+    for declarator in node.children_by_field_name("declarator", &mut cursor) {
+        let name_node = declarator.child_by_field_name("declarator").unwrap_or(declarator);
+        if !matches!(name_node.kind(), "identifier" | "field_identifier") {
+            continue;
+        }
+
+        let symbol = create_symbol(
+            file_id,
+            node_text(&name_node, source),
+            if is_const { "constant" } else { "variable" },
+            node,
+            None,
+            None,
+            scope.is_exported && !is_static,
+            scope.parent_id.clone(),
+        );
+        add_type_refs(&symbol.id, node, &["type"], source, result);
+        result.symbols.push(symbol);
+    }
+}
+
+/// `using Name = ...;` and `typedef ... Name;`
+fn extract_alias(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, scope: &Scope) {
+    let name = match node.kind() {
+        "alias_declaration" => node.child_by_field_name("name"),
+        _ => node.child_by_field_name("declarator"),
+    };
+    let Some(name) = name.filter(|n| n.kind() == "type_identifier") else { return };
+    let name = node_text(&name, source);
+
+    let symbol = create_symbol(
+        file_id,
+        name,
+        "type",
+        node,
+        Some(format!("using {}", name)),
+        None,
+        scope.is_exported,
+        scope.parent_id.clone(),
+    );
+    add_type_refs(&symbol.id, node, &["type"], source, result);
+    result.symbols.push(symbol);
+}
+
+// Helper functions
+
+/// The function declarator of a definition or declaration, below any pointer
+/// or reference declarators of its return type
+fn find_function_declarator<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+    let mut declarator = node.child_by_field_name("declarator")?;
+    loop {
+        match declarator.kind() {
+            // Not `int (*callback)(int)`, a variable of function pointer type
+            "function_declarator" => {
+                let name = declarator.child_by_field_name("declarator")?;
+                return (name.kind() != "parenthesized_declarator").then_some(declarator);
+            }
+            "pointer_declarator" | "reference_declarator" | "init_declarator" => {
+                declarator = declarator
+                    .child_by_field_name("declarator")
+                    .or_else(|| declarator.named_child(declarator.named_child_count().saturating_sub(1)))?;
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Name of a declarator and the scope qualifying it: `geo::Circle::area` ->
+/// (`area`, `Circle`), `~Circle` -> (`~Circle`, None)
+fn declarator_name<'a>(node: &Node, source: &'a [u8]) -> (&'a str, Option<&'a str>) {
+    let mut name = *node;
+    let mut owner = None;
+    while name.kind() == "qualified_identifier" {
+        owner = name
+            .child_by_field_name("scope")
+            .map(|scope| super::type_name(&scope, source).unwrap_or_else(|| node_text(&scope, source)));
+        match name.child_by_field_name("name") {
+            Some(inner) => name = inner,
+            None => break,
+        }
+    }
+    (node_text(&name, source), owner)
+}
+
+fn function_signature(node: &Node, declarator: &Node, scope: &Scope, source: &[u8]) -> String {
+    let return_type = node
+        .child_by_field_name("type")
+        .map(|t| format!("{} ", node_text(&t, source)))
+        .unwrap_or_default();
+    let signature = format!("{}{}", return_type, node_text(declarator, source));
+    match &scope.template {
+        Some(template) => format!("{} {}", template, signature),
+        None => signature,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::parser::{Parser, SupportedLanguage};
+
+    #[test]
+    fn test_extract_namespaces_classes_and_templates() {
+        let parser = Parser::new();
+        let source = r#"
+#include <vector>
+#include "shape.hpp"
+
+namespace geo {
+template <typename T>
+class Circle : public Shape, private Named<T> {
+public:
+    Circle(double r);
+    double area() const override;
+    T value() { return compute(); }
+private:
+    std::vector<Point> points_;
+    void reset();
+};
+
+template <typename T> T largest(T a, T b) { return a > b ? a : b; }
+using Points = std::vector<Point>;
+}
+
+namespace {
+int helper() { return 1; }
+}
+
+double geo::Circle::area() const { return helper(); }
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Cpp, source).unwrap();
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+
+        let geo = symbol("geo");
+        assert_eq!(geo.kind, "namespace");
+        let circle = symbol("Circle");
+        assert_eq!(circle.kind, "class");
+        assert_eq!(circle.parent_id.as_deref(), Some(geo.id.as_str()));
+        assert_eq!(circle.signature.as_deref(), Some("template <typename T> class Circle"));
+
+        let methods: Vec<_> = result
+            .symbols
+            .iter()
+            .filter(|s| s.kind == "method" && s.parent_id.as_deref() == Some(circle.id.as_str()))
+            .map(|s| (s.name.as_str(), s.is_exported))
+            .collect();
+        assert_eq!(methods, vec![("Circle", true), ("area", true), ("value", true), ("reset", false)]);
+
+        assert_eq!(symbol("largest").kind, "function");
+        assert_eq!(symbol("Points").kind, "type");
+        assert!(!symbol("helper").is_exported);
+
+        // Out-of-class definitions are linked to their class by name
+        let area_definition = result.symbols.iter().rfind(|s| s.name == "area").unwrap();
+        assert!(area_definition.parent_id.is_none());
+        assert!(result.members.iter().any(|m| m.symbol_id == area_definition.id && m.owner == "Circle"));
+
+        let bases: Vec<_> = result.inheritance.iter().map(|i| (i.base.as_str(), i.kind.as_str())).collect();
+        assert_eq!(bases, vec![("Shape", "extends"), ("Named", "extends")]);
+
+        let refs: Vec<_> = result.type_refs.iter().map(|r| r.name.as_str()).collect();
+        assert!(refs.contains(&"Point"));
+        assert!(!refs.contains(&"T"));
+
+        assert!(result.imports.iter().any(|i| i.source == "vector" && i.is_system));
+        assert!(result.imports.iter().any(|i| i.source == "shape.hpp" && !i.is_system));
+        assert!(result.calls.iter().any(|c| c.callee == "compute"));
+    }
+}
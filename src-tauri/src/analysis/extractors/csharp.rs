@@ -0,0 +1,494 @@
+use tree_sitter::Node;
+
+use super::{
+    add_inheritance, add_type_names, create_symbol, extract_calls, find_child, find_children, node_text, type_name,
+};
+use crate::analysis::parser::{ImportInfo, ParseResult};
+
+/// Extract symbols and relationships from C# AST
+pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResult) {
+    extract_declarations(file_id, root, source, result, &Scope::default());
+}
+
+/// The namespace declarations are in
+#[derive(Debug, Clone, Default)]
+struct Scope {
+    /// Fully qualified: `Acme.Shop` for `namespace Shop` inside `namespace Acme`
+    namespace: Option<String>,
+    /// The namespace symbol
+    parent_id: Option<String>,
+}
+
+fn extract_declarations(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, scope: &Scope) {
+    let mut scope = scope.clone();
+    let mut cursor = node.walk();
+
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "using_directive" => extract_using(&child, source, result),
+            "namespace_declaration" => {
+                let inner = extract_namespace(file_id, &child, source, result, &scope);
+                if let Some(body) = child.child_by_field_name("body") {
+                    extract_declarations(file_id, &body, source, result, &inner);
+                }
+            }
+            // `namespace A.B;` applies to the rest of the file
+            "file_scoped_namespace_declaration" => scope = extract_namespace(file_id, &child, source, result, &scope),
+            _ => extract_type(file_id, &child, source, result, &scope, None),
+        }
+    }
+}
+
+/// `using A.B;` imports every type of namespace `A.B`, `using static A.B.C;`
+/// the static members of type `C` and `using X = A.B;` binds an alias to a
+/// namespace or type
+fn extract_using(node: &Node, source: &[u8], result: &mut ParseResult) {
+    let mut cursor = node.walk();
+    let Some(target) = node
+        .named_children(&mut cursor)
+        .filter(|c| matches!(c.kind(), "identifier" | "qualified_name" | "generic_name"))
+        .last()
+    else {
+        return;
+    };
+
+    let imported_names = if let Some(alias) = node.child_by_field_name("name") {
+        vec![format!("* as {}", node_text(&alias, source))]
+    } else if find_child(node, "static").is_some() {
+        vec!["*".to_string()]
+    } else {
+        Vec::new()
+    };
+
+    result.imports.push(ImportInfo {
+        source: node_text(&target, source).to_string(),
+        imported_names,
+        line: node.start_position().row as i32 + 1,
+        ..Default::default()
+    });
+}
+
+/// Record a namespace declaration and return the scope of its members
+fn extract_namespace(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, scope: &Scope) -> Scope {
+    let Some(name) = node.child_by_field_name("name") else { return scope.clone() };
+    let name = node_text(&name, source);
+    let namespace = match &scope.namespace {
+        Some(outer) => format!("{}.{}", outer, name),
+        None => name.to_string(),
+    };
+
+    let symbol = create_symbol(
+        file_id,
+        name,
+        "namespace",
+        node,
+        Some(format!("namespace {}", namespace)),
+        None,
+        true,
+        scope.parent_id.clone(),
+    );
+
+    if !result.namespaces.contains(&namespace) {
+        result.namespaces.push(namespace.clone());
+    }
+    let inner = Scope {
+        namespace: Some(namespace),
+        parent_id: Some(symbol.id.clone()),
+    };
+    result.symbols.push(symbol);
+    inner
+}
+
+/// A class, struct, record, interface, enum or delegate, with its members.
+/// `parent` is the enclosing type of a nested declaration.
+fn extract_type(
+    file_id: &str,
+    node: &Node,
+    source: &[u8],
+    result: &mut ParseResult,
+    scope: &Scope,
+    parent: Option<&Parent>,
+) {
+    let kind = match node.kind() {
+        "record_declaration" if find_child(node, "struct").is_some() => "struct",
+        "class_declaration" | "record_declaration" => "class",
+        "struct_declaration" => "struct",
+        "interface_declaration" => "interface",
+        "enum_declaration" => "enum",
+        "delegate_declaration" => "type",
+        _ => return,
+    };
+    let Some(name) = node.child_by_field_name("name") else { return };
+    let name = node_text(&name, source);
+
+    let symbol = create_symbol(
+        file_id,
+        name,
+        kind,
+        node,
+        Some(signature(node, source)),
+        None,
+        is_public(node, source, parent),
+        parent.map(|p| p.id.clone()).or_else(|| scope.parent_id.clone()),
+    );
+
+    // An enum's base is its underlying integral type
+    if let Some(bases) = find_child(node, "base_list").filter(|_| kind != "enum") {
+        let mut cursor = bases.walk();
+        for (i, base) in bases.named_children(&mut cursor).enumerate() {
+            // `record Point(int X) : Shape(X)` passes arguments to the base constructor
+            let base = match base.kind() {
+                "primary_constructor_base_type" => match base.child_by_field_name("type") {
+                    Some(ty) => ty,
+                    None => continue,
+                },
+                _ => base,
+            };
+            // Only the first base of a class can be a class, and by convention
+            // interface names are `I` followed by a capital letter
+            let relation = match kind {
+                "interface" => "extends",
+                "class" if i == 0 && !is_interface_name(type_name(&base, source).unwrap_or_default()) => {
+                    "extends"
+                }
+                _ => "implements",
+            };
+            add_inheritance(Some(&symbol.id), name, &base, relation, source, result);
+        }
+    }
+
+    // Type parameter constraints, record and delegate parameters, delegate return type
+    let mut types = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "type_parameter_constraints_clause" => types.push(child),
+            "parameter_list" => types.extend(parameter_types(&child)),
+            _ => {}
+        }
+    }
+    types.extend(node.child_by_field_name("type"));
+    types.extend(node.child_by_field_name("parameters").iter().flat_map(parameter_types));
+    add_type_refs(&symbol.id, node, &types, source, result);
+
+    let this = Parent {
+        id: symbol.id.clone(),
+        is_interface: kind == "interface",
+    };
+    result.symbols.push(symbol);
+
+    if let Some(body) = node.child_by_field_name("body").filter(|_| kind != "enum") {
+        extract_members(file_id, &body, source, result, scope, &this);
+    }
+}
+
+/// The type a member is declared in
+struct Parent {
+    id: String,
+    /// Interface members are public without saying so
+    is_interface: bool,
+}
+
+fn extract_members(file_id: &str, body: &Node, source: &[u8], result: &mut ParseResult, scope: &Scope, parent: &Parent) {
+    let mut cursor = body.walk();
+
+    for member in body.children(&mut cursor) {
+        match member.kind() {
+            "method_declaration" | "constructor_declaration" => {
+                extract_method(file_id, &member, source, result, parent);
+            }
+            "property_declaration" => extract_property(file_id, &member, source, result, parent),
+            "field_declaration" => extract_field(file_id, &member, source, result, parent),
+            _ => extract_type(file_id, &member, source, result, scope, Some(parent)),
+        }
+    }
+}
+
+fn extract_method(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, parent: &Parent) {
+    let Some(name) = node.child_by_field_name("name") else { return };
+
+    let symbol = create_symbol(
+        file_id,
+        node_text(&name, source),
+        "method",
+        node,
+        Some(signature(node, source)),
+        None,
+        is_public(node, source, Some(parent)),
+        Some(parent.id.clone()),
+    );
+
+    let mut types: Vec<Node> = node.child_by_field_name("returns").into_iter().collect();
+    types.extend(node.child_by_field_name("parameters").iter().flat_map(parameter_types));
+    types.extend(find_children(node, "type_parameter_constraints_clause"));
+    add_type_refs(&symbol.id, node, &types, source, result);
+
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_calls(&symbol.id, &body, source, result);
+    }
+
+    result.symbols.push(symbol);
+}
+
+/// Auto-properties and properties with accessor bodies or an expression body
+fn extract_property(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, parent: &Parent) {
+    let Some(name) = node.child_by_field_name("name") else { return };
+
+    let symbol = create_symbol(
+        file_id,
+        node_text(&name, source),
+        "property",
+        node,
+        Some(signature(node, source)),
+        None,
+        is_public(node, source, Some(parent)),
+        Some(parent.id.clone()),
+    );
+
+    let types: Vec<Node> = node.child_by_field_name("type").into_iter().collect();
+    add_type_refs(&symbol.id, node, &types, source, result);
+    // Calls in accessor bodies, an expression body or the initializer
+    extract_calls(&symbol.id, node, source, result);
+
+    result.symbols.push(symbol);
+}
+
+/// One symbol per declared variable: `int a, b;` declares two fields
+fn extract_field(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, parent: &Parent) {
+    let Some(declaration) = find_child(node, "variable_declaration") else { return };
+    let modifiers = modifiers(node, source);
+    let is_constant = modifiers.contains(&"const") || (modifiers.contains(&"static") && modifiers.contains(&"readonly"));
+    let types: Vec<Node> = declaration.child_by_field_name("type").into_iter().collect();
+    let ty = types.first().map(|t| node_text(t, source)).unwrap_or_default();
+
+    let mut cursor = declaration.walk();
+    for declarator in declaration.children(&mut cursor).filter(|c| c.kind() == "variable_declarator") {
+        let Some(name) = declarator.child_by_field_name("name") else { continue };
+        let name = node_text(&name, source);
+
+        let symbol = create_symbol(
+            file_id,
+            name,
+            if is_constant { "constant" } else { "property" },
+            &declarator,
+            Some(format!("{} {}", ty, name)),
+            None,
+            is_public(node, source, Some(parent)),
+            Some(parent.id.clone()),
+        );
+        add_type_refs(&symbol.id, node, &types, source, result);
+        // Calls in the initializer
+        extract_calls(&symbol.id, &declarator, source, result);
+        result.symbols.push(symbol);
+    }
+}
+
+// Helper functions
+
+/// Record the types named below `types` as referenced by `symbol_id`. C# names
+/// types with plain identifiers, so the whole subtree of a type node is a type
+/// except for namespace qualifiers (`Acme.Models.Order` -> `Order`).
+fn add_type_refs(symbol_id: &str, node: &Node, types: &[Node], source: &[u8], result: &mut ParseResult) {
+    fn collect<'a>(node: &Node<'a>, names: &mut Vec<Node<'a>>) {
+        match node.kind() {
+            "identifier" => names.push(*node),
+            "qualified_name" | "alias_qualified_name" => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    collect(&name, names);
+                }
+            }
+            // Constraints name the constrained parameter before the bounds
+            "type_parameter_constraints_clause" => {
+                let mut cursor = node.walk();
+                for constraint in node.children(&mut cursor).filter(|c| c.kind() == "type_parameter_constraint") {
+                    collect(&constraint, names);
+                }
+            }
+            _ => {
+                let mut cursor = node.walk();
+                for child in node.named_children(&mut cursor) {
+                    collect(&child, names);
+                }
+            }
+        }
+    }
+
+    let mut names = Vec::new();
+    for ty in types {
+        collect(ty, &mut names);
+    }
+    add_type_names(symbol_id, node, &names, source, result);
+}
+
+/// The declared types of a parameter list's parameters
+fn parameter_types<'a>(parameters: &Node<'a>) -> Vec<Node<'a>> {
+    let mut cursor = parameters.walk();
+    parameters
+        .named_children(&mut cursor)
+        .filter_map(|param| param.child_by_field_name("type"))
+        .collect()
+}
+
+/// `IService`, `IOrderRepository`, but not `Item` or `Index`
+fn is_interface_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next() == Some('I') && chars.next().is_some_and(|c| c.is_ascii_uppercase())
+}
+
+/// Keywords among a declaration's modifiers
+fn modifiers<'a>(node: &Node, source: &'a [u8]) -> Vec<&'a str> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|c| c.kind() == "modifier")
+        .map(|m| node_text(&m, source))
+        .collect()
+}
+
+/// Visible outside the assembly: declared `public`, or a member of an interface
+fn is_public(node: &Node, source: &[u8], parent: Option<&Parent>) -> bool {
+    let modifiers = modifiers(node, source);
+    modifiers.contains(&"public") || (parent.is_some_and(|p| p.is_interface) && !modifiers.contains(&"private"))
+}
+
+/// The declaration up to its body, without attributes: `public Order Find(string id)`
+fn signature(node: &Node, source: &[u8]) -> String {
+    let mut cursor = node.walk();
+    let start = node
+        .children(&mut cursor)
+        .find(|c| c.kind() != "attribute_list")
+        .map_or(node.start_byte(), |c| c.start_byte());
+    let end = node
+        .child_by_field_name("body")
+        .or_else(|| node.child_by_field_name("accessors"))
+        .or_else(|| node.child_by_field_name("value"))
+        .map_or(node.end_byte(), |body| body.start_byte());
+
+    let text = std::str::from_utf8(&source[start..end.max(start)]).unwrap_or_default();
+    text.trim().trim_end_matches(';').split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::parser::{Parser, SupportedLanguage};
+
+    #[test]
+    fn test_extract_csharp_symbols() {
+        let parser = Parser::new();
+        let source = r#"
+using System.Collections.Generic;
+using static Acme.Util.Strings;
+using Models = Acme.Shop.Models;
+
+namespace Acme.Shop.Services;
+
+[Service]
+public class OrderService<T> : BaseService<T>, IOrderService where T : Item
+{
+    public const int Limit = 10;
+    private readonly List<Order> _orders = new List<Order>();
+
+    public string Name { get; set; } = Defaults.Name();
+
+    public OrderService(IRepo repo) : base(repo) { }
+
+    public Order? Find(string id) => _repo.Query<Order>(id);
+
+    void Run() { Join(_orders); this.Find("x"); }
+}
+
+public record Point(int X, int Y) : Shape(X), IShape;
+
+internal interface IOrderService : IService
+{
+    Order Find(string id);
+}
+"#;
+
+        let result = parser.parse_file("test", SupportedLanguage::CSharp, source).unwrap();
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+
+        let imports: Vec<_> = result
+            .imports
+            .iter()
+            .map(|i| (i.source.as_str(), i.imported_names.clone()))
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("System.Collections.Generic", vec![]),
+                ("Acme.Util.Strings", vec!["*".to_string()]),
+                ("Acme.Shop.Models", vec!["* as Models".to_string()]),
+            ]
+        );
+        assert_eq!(result.namespaces, vec!["Acme.Shop.Services"]);
+
+        let namespace = symbol("Acme.Shop.Services");
+        assert_eq!(namespace.kind, "namespace");
+        let service = symbol("OrderService");
+        assert_eq!(service.kind, "class");
+        assert!(service.is_exported);
+        assert_eq!(service.parent_id.as_deref(), Some(namespace.id.as_str()));
+        assert_eq!(
+            service.signature.as_deref(),
+            Some("public class OrderService<T> : BaseService<T>, IOrderService where T : Item")
+        );
+
+        let members: Vec<_> = result
+            .symbols
+            .iter()
+            .filter(|s| s.parent_id.as_deref() == Some(service.id.as_str()))
+            .map(|s| (s.name.as_str(), s.kind.as_str(), s.is_exported))
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                ("Limit", "constant", true),
+                ("_orders", "property", false),
+                ("Name", "property", true),
+                ("OrderService", "method", true),
+                ("Find", "method", true),
+                ("Run", "method", false),
+            ]
+        );
+        assert_eq!(symbol("Name").signature.as_deref(), Some("public string Name"));
+        assert_eq!(symbol("Point").kind, "class");
+        assert_eq!(symbol("IOrderService").kind, "interface");
+        assert!(!symbol("IOrderService").is_exported);
+
+        let bases: Vec<_> = result
+            .inheritance
+            .iter()
+            .map(|i| (i.symbol_name.as_str(), i.base.as_str(), i.kind.as_str()))
+            .collect();
+        assert_eq!(
+            bases,
+            vec![
+                ("OrderService", "BaseService", "extends"),
+                ("OrderService", "IOrderService", "implements"),
+                ("Point", "Shape", "extends"),
+                ("Point", "IShape", "implements"),
+                ("IOrderService", "IService", "extends"),
+            ]
+        );
+
+        let calls: Vec<_> = result
+            .calls
+            .iter()
+            .map(|c| (c.callee.as_str(), c.qualifier.as_deref()))
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                ("List", None),
+                ("Name", Some("Defaults")),
+                ("Query", Some("_repo")),
+                ("Join", None),
+                ("Find", Some("this")),
+            ]
+        );
+
+        let refs: Vec<_> = result.type_refs.iter().map(|r| r.name.as_str()).collect();
+        assert!(refs.contains(&"Item") && refs.contains(&"Order") && refs.contains(&"IRepo"));
+        assert!(!refs.contains(&"T") && !refs.contains(&"id"));
+    }
+}
@@ -0,0 +1,106 @@
+use tree_sitter::Node;
+
+use super::{is_analyzed_file, local_reference, node_text};
+use crate::analysis::parser::{ImportInfo, ParseResult};
+
+/// Record the stylesheets a stylesheet `@import`s and the images its `url()`s
+/// load. Files of types that are not analysed (fonts) are left out.
+pub fn extract(root: &Node, source: &[u8], result: &mut ParseResult) {
+    let mut cursor = root.walk();
+
+    for child in root.named_children(&mut cursor) {
+        match child.kind() {
+            // `@import "a.css"`, `@import url(a.css) screen`
+            "import_statement" => {
+                let reference = child.named_child(0).and_then(|target| match target.kind() {
+                    "call_expression" => url_argument(&target, source),
+                    _ => string_value(&target, source),
+                });
+                push_reference(reference, child.start_position().row, result);
+            }
+            // The grammar fails on unquoted `url(../a.png)`, so declarations
+            // are scanned as text
+            "declaration" => {
+                let text = node_text(&child, source);
+                let mut rest = text;
+                while let Some(offset) = rest.find("url(") {
+                    let before = &text[..text.len() - rest.len() + offset];
+                    rest = &rest[offset + 4..];
+                    if before.ends_with(|c: char| c.is_ascii_alphanumeric() || c == '-') {
+                        continue;
+                    }
+                    let Some(end) = rest.find(')') else { break };
+                    let argument = rest[..end].trim().trim_matches(|c| c == '"' || c == '\'');
+                    let row = child.start_position().row + before.matches('\n').count();
+                    push_reference(Some(argument), row, result);
+                }
+            }
+            _ => extract(&child, source, result),
+        }
+    }
+}
+
+fn push_reference(reference: Option<&str>, row: usize, result: &mut ParseResult) {
+    if let Some(path) = reference.and_then(local_reference).filter(|path| is_analyzed_file(path)) {
+        result.imports.push(ImportInfo {
+            source: path.to_string(),
+            line: row as i32 + 1,
+            ..Default::default()
+        });
+    }
+}
+
+/// The argument of `url("a.png")` or `url(a.png)`
+fn url_argument<'a>(node: &Node, source: &'a [u8]) -> Option<&'a str> {
+    let name = super::find_child(node, "function_name")?;
+    if node_text(&name, source) != "url" {
+        return None;
+    }
+    let arguments = super::find_child(node, "arguments")?;
+    let argument = arguments.named_child(0)?;
+    match argument.kind() {
+        "plain_value" => Some(node_text(&argument, source)),
+        _ => string_value(&argument, source),
+    }
+}
+
+fn string_value<'a>(node: &Node, source: &'a [u8]) -> Option<&'a str> {
+    if node.kind() != "string_value" {
+        return None;
+    }
+    super::find_child(node, "string_content").map(|content| node_text(&content, source))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::parser::{Parser, SupportedLanguage};
+
+    #[test]
+    fn test_extract_css_references() {
+        let parser = Parser::new();
+        let source = r#"@import "base.css";
+@import url('./components/button.css') screen;
+@import url(https://fonts.googleapis.com/css?family=Inter);
+
+.logo {
+  background: url("../img/logo.svg#icon") no-repeat, url(data:image/png;base64,AAA);
+  mask-image: url(../../icons/mask.svg);
+}
+
+@font-face { src: url(/fonts/inter.woff2) format("woff2"); }
+"#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Css, source).unwrap();
+
+        let imports: Vec<_> = result.imports.iter().map(|i| (i.source.as_str(), i.line)).collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("base.css", 1),
+                ("./components/button.css", 2),
+                ("../img/logo.svg", 6),
+                ("../../icons/mask.svg", 7),
+            ]
+        );
+    }
+}
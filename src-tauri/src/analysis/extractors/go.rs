@@ -1,7 +1,7 @@
 use tree_sitter::Node;
 
-use super::{create_symbol, find_child, node_text};
-use crate::analysis::parser::{ImportInfo, ParseResult};
+use super::{add_type_refs, create_symbol, extract_calls, find_child, node_text, type_name};
+use crate::analysis::parser::{ImportInfo, MemberInfo, ParseResult};
 
 /// Extract symbols and relationships from Go AST
 pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResult) {
@@ -59,6 +59,7 @@ fn extract_single_import(node: &Node, source: &[u8], result: &mut ParseResult) {
             imported_names: vec![],
             is_default: true,
             line: node.start_position().row as i32 + 1,
+            ..Default::default()
         });
     }
 }
@@ -83,7 +84,7 @@ fn extract_function(file_id: &str, node: &Node, source: &[u8], result: &mut Pars
 
     let signature = format!("func {}{}{}", name, params, return_type.unwrap_or_default());
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         name,
         "function",
@@ -92,7 +93,14 @@ fn extract_function(file_id: &str, node: &Node, source: &[u8], result: &mut Pars
         None,
         is_exported,
         None,
-    ));
+    );
+
+    add_type_refs(&symbol.id, node, &["type_parameters", "parameters", "result"], source, result);
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_calls(&symbol.id, &body, source, result);
+    }
+
+    result.symbols.push(symbol);
 }
 
 fn extract_method(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) {
@@ -125,7 +133,7 @@ fn extract_method(file_id: &str, node: &Node, source: &[u8], result: &mut ParseR
         format!("func {}", name)
     };
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         name,
         "method",
@@ -134,7 +142,34 @@ fn extract_method(file_id: &str, node: &Node, source: &[u8], result: &mut ParseR
         None,
         is_exported,
         None,
-    ));
+    );
+
+    // The receiver is the owner, not a reference
+    add_type_refs(&symbol.id, node, &["parameters", "result"], source, result);
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_calls(&symbol.id, &body, source, result);
+    }
+
+    // The receiver type may be declared in another file of the package
+    if let Some(owner) = receiver_type_name(node, source) {
+        result.members.push(MemberInfo {
+            symbol_id: symbol.id.clone(),
+            owner: owner.to_string(),
+        });
+    }
+
+    result.symbols.push(symbol);
+}
+
+/// Name of a method's receiver type: `(s *Server[T])` -> `Server`
+fn receiver_type_name<'a>(node: &Node, source: &'a [u8]) -> Option<&'a str> {
+    let receiver = node.child_by_field_name("receiver")?;
+    let param = find_child(&receiver, "parameter_declaration")?;
+    let mut receiver_type = param.child_by_field_name("type")?;
+    if receiver_type.kind() == "pointer_type" {
+        receiver_type = receiver_type.named_child(0)?;
+    }
+    type_name(&receiver_type, source)
 }
 
 fn extract_type(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) {
@@ -157,7 +192,7 @@ fn extract_type(file_id: &str, node: &Node, source: &[u8], result: &mut ParseRes
 
             let signature = format!("type {} {}", name, type_def);
 
-            result.symbols.push(create_symbol(
+            let symbol = create_symbol(
                 file_id,
                 name,
                 type_def,
@@ -166,7 +201,9 @@ fn extract_type(file_id: &str, node: &Node, source: &[u8], result: &mut ParseRes
                 None,
                 is_exported,
                 None,
-            ));
+            );
+            add_type_refs(&symbol.id, &child, &["type_parameters", "type"], source, result);
+            result.symbols.push(symbol);
         }
     }
 }
@@ -186,7 +223,7 @@ fn extract_var_const(file_id: &str, node: &Node, source: &[u8], result: &mut Par
                 let name = node_text(&name_node, source);
                 let is_exported = name.chars().next().map(|c| c.is_uppercase()).unwrap_or(false);
 
-                result.symbols.push(create_symbol(
+                let symbol = create_symbol(
                     file_id,
                     name,
                     kind,
@@ -195,7 +232,9 @@ fn extract_var_const(file_id: &str, node: &Node, source: &[u8], result: &mut Par
                     None,
                     is_exported,
                     None,
-                ));
+                );
+                add_type_refs(&symbol.id, &child, &["type"], source, result);
+                result.symbols.push(symbol);
             }
         }
     }
@@ -242,6 +281,42 @@ type User struct {
         assert!(result.symbols.iter().any(|s| s.name == "User" && s.kind == "struct"));
     }
 
+    #[test]
+    fn test_extract_calls() {
+        let parser = Parser::new();
+        let source = r#"
+package main
+
+func (s *Server) Run() {
+    fmt.Println(s.addr)
+    start()
+}
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Go, source).unwrap();
+        assert_eq!(result.calls.len(), 2);
+
+        let println = result.calls.iter().find(|c| c.callee == "Println").unwrap();
+        assert_eq!(println.qualifier.as_deref(), Some("fmt"));
+        assert!(result.calls.iter().any(|c| c.callee == "start"));
+    }
+
+    #[test]
+    fn test_extract_method_receivers() {
+        let parser = Parser::new();
+        let source = r#"
+package main
+
+func (s *Server[T]) Run() {}
+func (Config) Validate() {}
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Go, source).unwrap();
+        let owners: Vec<_> = result.members.iter().map(|m| m.owner.as_str()).collect();
+        assert_eq!(owners, vec!["Server", "Config"]);
+        assert_eq!(result.members[0].symbol_id, result.symbols[0].id);
+    }
+
     #[test]
     fn test_extract_imports() {
         let parser = Parser::new();
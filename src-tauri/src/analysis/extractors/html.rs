@@ -0,0 +1,88 @@
+use tree_sitter::Node;
+
+use super::{is_analyzed_file, local_reference, node_text};
+use crate::analysis::parser::{ImportInfo, ParseResult};
+
+/// Tags that load a file, and the attribute naming it
+const RESOURCE_ATTRIBUTES: &[(&str, &str)] = &[("script", "src"), ("link", "href"), ("img", "src")];
+
+/// Record the scripts, stylesheets, icons and images an HTML page loads.
+/// Files of types that are not analysed (fonts, video) are left out.
+pub fn extract(root: &Node, source: &[u8], result: &mut ParseResult) {
+    let mut cursor = root.walk();
+
+    for child in root.named_children(&mut cursor) {
+        if matches!(child.kind(), "start_tag" | "self_closing_tag") {
+            extract_tag(&child, source, result);
+        } else {
+            extract(&child, source, result);
+        }
+    }
+}
+
+fn extract_tag(node: &Node, source: &[u8], result: &mut ParseResult) {
+    let Some(tag) = super::find_child(node, "tag_name") else { return };
+    let tag = node_text(&tag, source).to_ascii_lowercase();
+    let Some((_, wanted)) = RESOURCE_ATTRIBUTES.iter().find(|(name, _)| *name == tag) else { return };
+
+    let mut cursor = node.walk();
+    for attribute in node.named_children(&mut cursor).filter(|c| c.kind() == "attribute") {
+        let Some(name) = super::find_child(&attribute, "attribute_name") else { continue };
+        if !node_text(&name, source).eq_ignore_ascii_case(wanted) {
+            continue;
+        }
+        // `href="x"` and `href=x`
+        let value = super::find_child(&attribute, "quoted_attribute_value")
+            .and_then(|quoted| quoted.named_child(0))
+            .or_else(|| super::find_child(&attribute, "attribute_value"));
+        let path = value.and_then(|v| local_reference(node_text(&v, source)));
+        if let Some(path) = path.filter(|path| is_analyzed_file(path)) {
+            result.imports.push(ImportInfo {
+                source: path.to_string(),
+                line: attribute.start_position().row as i32 + 1,
+                ..Default::default()
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::parser::{Parser, SupportedLanguage};
+
+    #[test]
+    fn test_extract_html_resources() {
+        let parser = Parser::new();
+        let source = r#"<!DOCTYPE html>
+<html>
+<head>
+  <link rel="stylesheet" href="/styles/main.css?v=3">
+  <link rel=icon href=favicon.svg>
+  <link rel="preconnect" href="https://fonts.gstatic.com">
+  <script type="module" src="/src/main.tsx"></script>
+  <script>console.log("<img src='inline.png'>")</script>
+</head>
+<body>
+  <IMG SRC="assets/logo.png" alt="logo" />
+  <!-- <img src="old.png"> -->
+  <img src="data:image/png;base64,AAAA">
+  <a href="docs/index.html">Docs</a>
+</body>
+</html>
+"#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Html, source).unwrap();
+
+        let imports: Vec<_> = result.imports.iter().map(|i| (i.source.as_str(), i.line)).collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("/styles/main.css", 4),
+                ("favicon.svg", 5),
+                ("/src/main.tsx", 7),
+                ("assets/logo.png", 11),
+            ]
+        );
+        assert!(result.symbols.is_empty());
+    }
+}
@@ -0,0 +1,311 @@
+use tree_sitter::Node;
+
+use super::{add_inheritance, add_type_refs, create_symbol, extract_calls, find_child, node_text};
+use crate::analysis::parser::{ImportInfo, ParseResult};
+
+/// Extract symbols and relationships from Java AST
+pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResult) {
+    let mut cursor = root.walk();
+
+    for child in root.children(&mut cursor) {
+        match child.kind() {
+            "import_declaration" => extract_import(&child, source, result),
+            _ => extract_type(file_id, &child, source, result, None),
+        }
+    }
+}
+
+/// `import a.b.C;` imports `C` from package `a.b`, `import static a.b.C.m;`
+/// imports member `m` of type `a.b.C`, and `import a.b.*;` the whole package
+fn extract_import(node: &Node, source: &[u8], result: &mut ParseResult) {
+    let Some(path) = find_child(node, "scoped_identifier").or_else(|| find_child(node, "identifier")) else {
+        return;
+    };
+    let path = node_text(&path, source);
+
+    let (source_path, name) = if find_child(node, "asterisk").is_some() {
+        (path, "*")
+    } else {
+        match path.rsplit_once('.') {
+            Some(split) => split,
+            None => return,
+        }
+    };
+
+    result.imports.push(ImportInfo {
+        source: source_path.to_string(),
+        imported_names: vec![name.to_string()],
+        line: node.start_position().row as i32 + 1,
+        ..Default::default()
+    });
+}
+
+/// A class, interface, enum, record or annotation type, with its members.
+/// `parent` is the enclosing type of a nested declaration.
+fn extract_type(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, parent: Option<&Parent>) {
+    let kind = match node.kind() {
+        "class_declaration" | "record_declaration" => "class",
+        "interface_declaration" | "annotation_type_declaration" => "interface",
+        "enum_declaration" => "enum",
+        _ => return,
+    };
+    let Some(name) = node.child_by_field_name("name") else { return };
+    let name = node_text(&name, source);
+
+    let symbol = create_symbol(
+        file_id,
+        name,
+        kind,
+        node,
+        Some(signature(node, source)),
+        None,
+        is_public(node, source, parent),
+        parent.map(|p| p.id.clone()),
+    );
+
+    if let Some(superclass) = node.child_by_field_name("superclass") {
+        if let Some(base) = superclass.named_child(0) {
+            add_inheritance(Some(&symbol.id), name, &base, "extends", source, result);
+        }
+    }
+    // `implements` for classes, enums and records; `extends` between interfaces
+    let interfaces = node
+        .child_by_field_name("interfaces")
+        .map(|list| (list, "implements"))
+        .or_else(|| find_child(node, "extends_interfaces").map(|list| (list, "extends")));
+    if let Some((list, relation)) = interfaces {
+        if let Some(types) = find_child(&list, "type_list") {
+            let mut cursor = types.walk();
+            for base in types.named_children(&mut cursor) {
+                add_inheritance(Some(&symbol.id), name, &base, relation, source, result);
+            }
+        }
+    }
+
+    // Type parameter bounds and record components
+    add_type_refs(&symbol.id, node, &["type_parameters", "parameters"], source, result);
+
+    let this = Parent {
+        id: symbol.id.clone(),
+        is_interface: kind == "interface",
+    };
+    result.symbols.push(symbol);
+
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_members(file_id, &body, source, result, &this);
+    }
+}
+
+/// The type a member is declared in
+struct Parent {
+    id: String,
+    /// Interface members are public without saying so
+    is_interface: bool,
+}
+
+fn extract_members(file_id: &str, body: &Node, source: &[u8], result: &mut ParseResult, parent: &Parent) {
+    let mut cursor = body.walk();
+
+    for member in body.children(&mut cursor) {
+        match member.kind() {
+            "method_declaration" | "constructor_declaration" | "compact_constructor_declaration" => {
+                extract_method(file_id, &member, source, result, parent);
+            }
+            "field_declaration" | "constant_declaration" => extract_field(file_id, &member, source, result, parent),
+            // Methods and fields after an enum's constants
+            "enum_body_declarations" => extract_members(file_id, &member, source, result, parent),
+            _ => extract_type(file_id, &member, source, result, Some(parent)),
+        }
+    }
+}
+
+fn extract_method(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, parent: &Parent) {
+    let Some(name) = node.child_by_field_name("name") else { return };
+
+    let symbol = create_symbol(
+        file_id,
+        node_text(&name, source),
+        "method",
+        node,
+        Some(signature(node, source)),
+        None,
+        is_public(node, source, Some(parent)),
+        Some(parent.id.clone()),
+    );
+
+    add_type_refs(&symbol.id, node, &["type_parameters", "type", "parameters"], source, result);
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_calls(&symbol.id, &body, source, result);
+    }
+
+    result.symbols.push(symbol);
+}
+
+/// One symbol per declared variable: `int a, b;` declares two fields
+fn extract_field(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, parent: &Parent) {
+    let modifiers = modifiers(node, source);
+    // Interface fields are implicitly static and final
+    let is_constant =
+        parent.is_interface || (modifiers.contains(&"static") && modifiers.contains(&"final"));
+    let ty = node
+        .child_by_field_name("type")
+        .map(|t| node_text(&t, source))
+        .unwrap_or_default();
+
+    let mut cursor = node.walk();
+    for declarator in node.children_by_field_name("declarator", &mut cursor) {
+        let Some(name) = declarator.child_by_field_name("name") else { continue };
+        let name = node_text(&name, source);
+
+        let symbol = create_symbol(
+            file_id,
+            name,
+            if is_constant { "constant" } else { "property" },
+            &declarator,
+            Some(format!("{} {}", ty, name)),
+            None,
+            is_public(node, source, Some(parent)),
+            Some(parent.id.clone()),
+        );
+        add_type_refs(&symbol.id, node, &["type"], source, result);
+        // Calls in the initializer
+        extract_calls(&symbol.id, &declarator, source, result);
+        result.symbols.push(symbol);
+    }
+}
+
+// Helper functions
+
+/// Keywords among a declaration's modifiers, without annotations
+fn modifiers<'a>(node: &Node, source: &'a [u8]) -> Vec<&'a str> {
+    let Some(modifiers) = find_child(node, "modifiers") else { return Vec::new() };
+    let mut cursor = modifiers.walk();
+    modifiers
+        .children(&mut cursor)
+        .filter(|m| !m.kind().ends_with("annotation"))
+        .map(|m| node_text(&m, source))
+        .collect()
+}
+
+/// Visible outside the package: declared `public`, or a member of an interface
+fn is_public(node: &Node, source: &[u8], parent: Option<&Parent>) -> bool {
+    let modifiers = modifiers(node, source);
+    modifiers.contains(&"public") || (parent.is_some_and(|p| p.is_interface) && !modifiers.contains(&"private"))
+}
+
+/// The declaration up to its body, without annotations: `public List<Order> find(String id)`
+fn signature(node: &Node, source: &[u8]) -> String {
+    let start = find_child(node, "modifiers")
+        .map(|modifiers| {
+            let mut cursor = modifiers.walk();
+            let first_keyword = modifiers
+                .children(&mut cursor)
+                .find(|m| !m.kind().ends_with("annotation"));
+            first_keyword.map_or(modifiers.end_byte(), |m| m.start_byte())
+        })
+        .unwrap_or(node.start_byte());
+    let end = node.child_by_field_name("body").map_or(node.end_byte(), |body| body.start_byte());
+
+    let text = std::str::from_utf8(&source[start..end.max(start)]).unwrap_or_default();
+    text.trim().trim_end_matches(';').split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::parser::{Parser, SupportedLanguage};
+
+    #[test]
+    fn test_extract_java_symbols() {
+        let parser = Parser::new();
+        let source = r#"
+package com.acme.shop;
+
+import java.util.List;
+import static com.acme.util.Strings.join;
+import com.acme.model.*;
+
+@Service
+public class OrderService<T extends Item> extends Base<T> implements Runnable, Comparable<OrderService<T>> {
+    public static final int LIMIT = 10;
+    private final List<Order> orders = new ArrayList<>();
+
+    public OrderService(Repo repo) { super(repo); }
+
+    public List<Order> find(String id) { return repo.query(id); }
+
+    void run() { join(orders); this.find("x"); }
+
+    interface Listener extends EventListener { void changed(Order order); }
+}
+"#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Java, source).unwrap();
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+
+        let imports: Vec<_> = result
+            .imports
+            .iter()
+            .map(|i| (i.source.as_str(), i.imported_names[0].as_str()))
+            .collect();
+        assert_eq!(
+            imports,
+            vec![("java.util", "List"), ("com.acme.util.Strings", "join"), ("com.acme.model", "*")]
+        );
+
+        let service = symbol("OrderService");
+        assert_eq!(service.kind, "class");
+        assert!(service.is_exported);
+        assert_eq!(
+            service.signature.as_deref(),
+            Some("public class OrderService<T extends Item> extends Base<T> implements Runnable, Comparable<OrderService<T>>")
+        );
+
+        let members: Vec<_> = result
+            .symbols
+            .iter()
+            .filter(|s| s.parent_id.as_deref() == Some(service.id.as_str()))
+            .map(|s| (s.name.as_str(), s.kind.as_str(), s.is_exported))
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                ("LIMIT", "constant", true),
+                ("orders", "property", false),
+                ("OrderService", "method", true),
+                ("find", "method", true),
+                ("run", "method", false),
+                ("Listener", "interface", false),
+            ]
+        );
+        assert!(symbol("changed").is_exported);
+
+        let bases: Vec<_> = result
+            .inheritance
+            .iter()
+            .map(|i| (i.symbol_name.as_str(), i.base.as_str(), i.kind.as_str()))
+            .collect();
+        assert_eq!(
+            bases,
+            vec![
+                ("OrderService", "Base", "extends"),
+                ("OrderService", "Runnable", "implements"),
+                ("OrderService", "Comparable", "implements"),
+                ("Listener", "EventListener", "extends"),
+            ]
+        );
+
+        let calls: Vec<_> = result
+            .calls
+            .iter()
+            .map(|c| (c.callee.as_str(), c.qualifier.as_deref()))
+            .collect();
+        assert_eq!(
+            calls,
+            vec![("ArrayList", None), ("query", Some("repo")), ("join", None), ("find", Some("this"))]
+        );
+
+        let refs: Vec<_> = result.type_refs.iter().map(|r| r.name.as_str()).collect();
+        assert!(refs.contains(&"Item") && refs.contains(&"Order") && refs.contains(&"Repo"));
+        assert!(!refs.contains(&"T"));
+    }
+}
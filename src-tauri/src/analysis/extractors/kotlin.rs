@@ -0,0 +1,1362 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::analysis::parser::{CallInfo, ImportInfo, InheritanceInfo, MemberInfo, ParseResult, TypeRefInfo};
+use crate::storage::SymbolRecord;
+
+/// Words that may directly precede `(` or `{` without being a call
+const NOT_CALLS: &[&str] = &[
+    "if", "for", "while", "when", "catch", "return", "throw", "fun", "super", "this", "object",
+    "constructor", "init", "in", "is", "as", "else", "try", "finally", "do", "val", "var", "get",
+    "set",
+];
+
+/// Modifiers that can precede a declaration keyword
+const MODIFIERS: &[&str] = &[
+    "public", "private", "protected", "internal", "open", "abstract", "final", "sealed", "data",
+    "enum", "annotation", "inner", "value", "companion", "override", "const", "lateinit",
+    "inline", "suspend", "operator", "infix", "tailrec", "external", "expect", "actual",
+];
+
+/// Extract symbols and relationships from Kotlin source.
+///
+/// There is no Kotlin grammar for our tree-sitter version, so declarations are
+/// found by scanning the source with comments and string literals blanked
+/// out: packages, imports, classes, interfaces and objects with their
+/// supertypes, functions, properties and type aliases, plus the calls made in
+/// function bodies, property initializers and `init` blocks.
+pub fn extract(file_id: &str, source: &str, result: &mut ParseResult) {
+    let scanner = Scanner::new(file_id, source);
+    scanner.scan_declarations(0, scanner.code.len(), None, result);
+}
+
+/// The type whose body is being scanned
+struct Owner {
+    id: String,
+    /// Type parameters in scope, which are not type references
+    type_params: Vec<String>,
+}
+
+/// How a declaration header ends
+enum HeaderEnd {
+    /// At the `{` opening its body
+    Body(usize),
+    /// At the `=` of an expression body or initializer
+    Expression(usize),
+    /// Without a body, just before this offset
+    None(usize),
+}
+
+/// A parameter of a function or primary constructor
+struct Param {
+    name: (usize, usize),
+    ty: Option<(usize, usize)>,
+    /// `val`/`var` in a primary constructor also declares a property
+    is_property: bool,
+    start: usize,
+}
+
+struct Scanner<'a> {
+    file_id: &'a str,
+    source: &'a str,
+    /// `source` with comments and string contents replaced by spaces
+    code: Vec<u8>,
+    /// Opening bracket offset -> matching closing bracket offset
+    closing: HashMap<usize, usize>,
+    /// Closing bracket offset -> matching opening bracket offset
+    opening: HashMap<usize, usize>,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(file_id: &'a str, source: &'a str) -> Self {
+        let code = blank_comments_and_strings(source.as_bytes());
+
+        let mut closing = HashMap::new();
+        let mut opening = HashMap::new();
+        let mut open = Vec::new();
+        for (i, &c) in code.iter().enumerate() {
+            match c {
+                b'{' | b'(' | b'[' => open.push(i),
+                b'}' | b')' | b']' => {
+                    let opener = match c {
+                        b'}' => b'{',
+                        b')' => b'(',
+                        _ => b'[',
+                    };
+                    // Brackets left unclosed inside are dropped, not paired with this one
+                    if let Some(depth) = open.iter().rposition(|&start| code[start] == opener) {
+                        let start = open[depth];
+                        open.truncate(depth);
+                        closing.insert(start, i);
+                        opening.insert(i, start);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        Self {
+            file_id,
+            source,
+            code,
+            closing,
+            opening,
+            line_starts,
+        }
+    }
+
+    /// Declarations directly inside `start..end`: the file or a type body
+    fn scan_declarations(&self, start: usize, end: usize, owner: Option<&Owner>, result: &mut ParseResult) {
+        let mut i = start;
+        // Where the modifiers of the next declaration begin
+        let mut statement = start;
+
+        while i < end {
+            let c = self.code[i];
+            if matches!(c, b'{' | b'(' | b'[') {
+                // An unclosed bracket is stepped over rather than taking the rest with it
+                i = self.closing.get(&i).map_or(i + 1, |close| close + 1);
+                statement = i;
+                continue;
+            }
+            if matches!(c, b'\n' | b';' | b'}' | b')' | b']' | b'=' | b',') {
+                i += 1;
+                statement = i;
+                continue;
+            }
+            let Some(word) = self.word_at(i) else {
+                i += 1;
+                continue;
+            };
+            // Annotations and `Foo::class` are not declarations
+            if i > 0 && (self.code[i - 1] == b'@' || self.code[..i].ends_with(b"::")) {
+                i += word.len();
+                continue;
+            }
+
+            let next = match word {
+                "package" => Some(self.line_end(i)),
+                "import" if owner.is_none() => Some(self.import(i, result)),
+                "class" | "interface" | "object" => self.type_declaration(statement, i, word, end, owner, result),
+                "fun" => self.function(statement, i, end, owner, result),
+                "val" | "var" => self.property(statement, i, end, owner, result),
+                "typealias" => self.type_alias(statement, i, end, owner, result),
+                "init" => owner.and_then(|owner| self.init_block(i, end, owner, result)),
+                _ => None,
+            };
+            match next {
+                Some(next) => {
+                    i = next.max(i + 1);
+                    statement = i;
+                }
+                None => i += word.len(),
+            }
+        }
+    }
+
+    /// `import a.b.C`, `import a.b.C as D` and `import a.b.*`
+    fn import(&self, keyword: usize, result: &mut ParseResult) -> usize {
+        let end = self.line_end(keyword);
+        let text = self.text(keyword + "import".len(), end).trim().trim_end_matches(';').trim();
+        let path = text.split(" as ").next().unwrap_or_default().trim();
+
+        let split = match path.strip_suffix(".*") {
+            Some(package) => Some((package, "*")),
+            None => path.rsplit_once('.'),
+        };
+        if let Some((package, name)) = split {
+            result.imports.push(ImportInfo {
+                source: package.to_string(),
+                imported_names: vec![name.to_string()],
+                line: self.line(keyword),
+                ..Default::default()
+            });
+        }
+
+        end
+    }
+
+    /// A class, interface or object. Companion objects have no symbol of
+    /// their own: their members belong to the enclosing class.
+    fn type_declaration(
+        &self,
+        statement: usize,
+        keyword: usize,
+        word: &str,
+        end: usize,
+        owner: Option<&Owner>,
+        result: &mut ParseResult,
+    ) -> Option<usize> {
+        let (modifiers, first_modifier) = self.modifiers(statement, keyword);
+        let name_start = self.skip_whitespace(keyword + word.len(), end);
+        let name = self.word_at(name_start);
+
+        if word == "object" && (name.is_none() || modifiers.contains(&"companion")) {
+            let after_name = name.map_or(name_start, |n| name_start + n.len());
+            return match self.header_end(after_name, end) {
+                // Anonymous objects are expressions; their members are not the owner's
+                HeaderEnd::Body(open) if name.is_none() && !modifiers.contains(&"companion") => {
+                    Some(self.after_closing(open, end))
+                }
+                HeaderEnd::Body(open) => {
+                    let close = self.closing.get(&open).copied().unwrap_or(end);
+                    self.scan_declarations(open + 1, close, owner, result);
+                    Some(close + 1)
+                }
+                HeaderEnd::Expression(at) | HeaderEnd::None(at) => Some(at),
+            };
+        }
+        let name = name?;
+        let mut i = name_start + name.len();
+
+        let kind = match word {
+            "interface" => "interface",
+            _ if modifiers.contains(&"enum") => "enum",
+            _ => "class",
+        };
+
+        let mut type_params = owner.map(|o| o.type_params.clone()).unwrap_or_default();
+        if self.code.get(i) == Some(&b'<') {
+            let close = self.skip_angles(i);
+            type_params.extend(self.type_param_names(i + 1, close - 1));
+            i = close;
+        }
+
+        let header_end = self.header_end(i, end);
+        let (header_stop, body) = match header_end {
+            HeaderEnd::Body(open) => (open, Some(open)),
+            HeaderEnd::Expression(at) | HeaderEnd::None(at) => (at, None),
+        };
+        let declaration_end = body.map_or(header_stop, |open| self.after_closing(open, end));
+
+        let symbol = self.symbol(
+            name,
+            kind,
+            (first_modifier.unwrap_or(keyword), declaration_end),
+            Some(self.collapsed(first_modifier.unwrap_or(keyword), header_stop)),
+            !modifiers.contains(&"private"),
+            owner.map(|o| o.id.clone()),
+        );
+        let this = Owner {
+            id: symbol.id.clone(),
+            type_params,
+        };
+        result.symbols.push(symbol);
+
+        // Primary constructor, then supertypes after the first `:` outside brackets
+        let mut supertypes = None;
+        while i < header_stop {
+            match self.code[i] {
+                b'(' => {
+                    let close = self.closing.get(&i).copied().unwrap_or(header_stop);
+                    for param in self.params(i + 1, close) {
+                        self.add_param(&param, &this, result);
+                    }
+                    i = close + 1;
+                }
+                b':' => {
+                    supertypes = Some(i + 1);
+                    break;
+                }
+                _ => i += 1,
+            }
+        }
+        if let Some(start) = supertypes {
+            self.supertypes(start, header_stop, name, kind, &this, result);
+        }
+
+        if let Some(open) = body {
+            let close = self.closing.get(&open).copied().unwrap_or(end);
+            self.scan_declarations(open + 1, close, Some(&this), result);
+        }
+
+        Some(declaration_end)
+    }
+
+    /// `: Base(args), Interface, Other by delegate where T : Bound`
+    fn supertypes(&self, start: usize, end: usize, name: &str, kind: &str, this: &Owner, result: &mut ParseResult) {
+        let end = self.find_word("where", start, end).unwrap_or(end);
+
+        for (entry_start, entry_end) in self.split_top_level(start, end, b',') {
+            let entry = self.text(entry_start, entry_end);
+            let entry = entry.split(" by ").next().unwrap_or_default();
+            let is_constructor_call = entry.contains('(');
+            let Some(base) = base_type_name(entry) else { continue };
+
+            let relation = if kind == "interface" || is_constructor_call { "extends" } else { "implements" };
+            result.inheritance.push(InheritanceInfo {
+                symbol_id: Some(this.id.clone()),
+                symbol_name: name.to_string(),
+                base: base.to_string(),
+                kind: relation.to_string(),
+                line: self.line(self.skip_whitespace(entry_start, entry_end)),
+            });
+        }
+    }
+
+    /// `fun <T> Receiver.name(params): Type { body }` or `= expression`
+    fn function(
+        &self,
+        statement: usize,
+        keyword: usize,
+        end: usize,
+        owner: Option<&Owner>,
+        result: &mut ParseResult,
+    ) -> Option<usize> {
+        let mut i = self.skip_whitespace(keyword + "fun".len(), end);
+        if self.word_at(i) == Some("interface") {
+            return self.type_declaration(statement, i, "interface", end, owner, result);
+        }
+        let (modifiers, first_modifier) = self.modifiers(statement, keyword);
+
+        let mut type_params = owner.map(|o| o.type_params.clone()).unwrap_or_default();
+        if self.code.get(i) == Some(&b'<') {
+            let close = self.skip_angles(i);
+            type_params.extend(self.type_param_names(i + 1, close - 1));
+            i = self.skip_whitespace(close, end);
+        }
+
+        let (receiver, name, paren) = self.declared_name(i, end)?;
+        if self.code.get(paren) != Some(&b'(') {
+            return None;
+        }
+        let close = *self.closing.get(&paren)?;
+
+        let header_end = self.header_end(close + 1, end);
+        let (header_stop, declaration_end) = match header_end {
+            HeaderEnd::Body(open) => (open, self.after_closing(open, end)),
+            HeaderEnd::Expression(at) => (at, self.statement_end(at + 1, end)),
+            HeaderEnd::None(at) => (at, at),
+        };
+
+        let kind = if owner.is_some() || receiver.is_some() { "method" } else { "function" };
+        let signature_start = first_modifier.unwrap_or(keyword);
+        let symbol = self.symbol(
+            name,
+            kind,
+            (signature_start, declaration_end),
+            Some(self.collapsed(signature_start, header_stop)),
+            !modifiers.contains(&"private"),
+            owner.map(|o| o.id.clone()),
+        );
+
+        // Extension functions are members of the receiver type
+        if let (None, Some(receiver)) = (owner, receiver.and_then(base_type_name)) {
+            result.members.push(MemberInfo {
+                symbol_id: symbol.id.clone(),
+                owner: receiver.to_string(),
+            });
+        }
+
+        for param in self.params(paren + 1, close) {
+            if let Some((start, end)) = param.ty {
+                self.add_type_refs(&symbol.id, start, end, &type_params, result);
+            }
+        }
+        // Return type
+        let after_params = self.skip_whitespace(close + 1, header_stop);
+        if self.code.get(after_params) == Some(&b':') {
+            let end = self.find_word("where", after_params, header_stop).unwrap_or(header_stop);
+            self.add_type_refs(&symbol.id, after_params + 1, end, &type_params, result);
+        }
+
+        match header_end {
+            HeaderEnd::Body(open) => self.scan_calls(&symbol.id, open + 1, declaration_end, result),
+            HeaderEnd::Expression(at) => self.scan_calls(&symbol.id, at + 1, declaration_end, result),
+            HeaderEnd::None(_) => {}
+        }
+
+        result.symbols.push(symbol);
+        Some(declaration_end)
+    }
+
+    /// `val name: Type = ...`, `var Receiver.name: Type get() = ...`. Only the
+    /// declaration is consumed; calls in the initializer or delegate are the
+    /// property's.
+    fn property(
+        &self,
+        statement: usize,
+        keyword: usize,
+        end: usize,
+        owner: Option<&Owner>,
+        result: &mut ParseResult,
+    ) -> Option<usize> {
+        let (modifiers, first_modifier) = self.modifiers(statement, keyword);
+        let mut i = self.skip_whitespace(keyword + 3, end);
+
+        let mut type_params = owner.map(|o| o.type_params.clone()).unwrap_or_default();
+        if self.code.get(i) == Some(&b'<') {
+            let close = self.skip_angles(i);
+            type_params.extend(self.type_param_names(i + 1, close - 1));
+            i = self.skip_whitespace(close, end);
+        }
+
+        // `val (a, b) = pair` declares no single name
+        let (receiver, name, after_name) = self.declared_name(i, end)?;
+        let mut declaration_end = after_name;
+        let mut ty = None;
+        let colon = self.skip_inline_whitespace(after_name, end);
+        if self.code.get(colon) == Some(&b':') {
+            let type_end = self.type_end(colon + 1, end);
+            ty = Some((colon + 1, type_end));
+            declaration_end = type_end;
+        }
+
+        let kind = match (owner, self.word_at(keyword)) {
+            (Some(_), _) => "property",
+            (None, Some("val")) => "constant",
+            _ => "variable",
+        };
+        let signature_start = first_modifier.unwrap_or(keyword);
+        let symbol = self.symbol(
+            name,
+            kind,
+            (signature_start, declaration_end),
+            Some(self.collapsed(signature_start, declaration_end)),
+            !modifiers.contains(&"private"),
+            owner.map(|o| o.id.clone()),
+        );
+
+        if let (None, Some(receiver)) = (owner, receiver.and_then(base_type_name)) {
+            result.members.push(MemberInfo {
+                symbol_id: symbol.id.clone(),
+                owner: receiver.to_string(),
+            });
+        }
+        if let Some((start, end)) = ty {
+            self.add_type_refs(&symbol.id, start, end, &type_params, result);
+        }
+
+        // `= initializer` or `by delegate`
+        let after = self.skip_inline_whitespace(declaration_end, end);
+        let initializer = match self.word_at(after) {
+            Some("by") => Some(after + "by".len()),
+            _ => (self.code.get(after) == Some(&b'=')).then_some(after + 1),
+        };
+        if let Some(start) = initializer {
+            self.scan_calls(&symbol.id, start, self.statement_end(start, end), result);
+        }
+
+        result.symbols.push(symbol);
+        Some(declaration_end)
+    }
+
+    /// `init { ... }`: the calls made while constructing the owner are its own
+    fn init_block(&self, keyword: usize, end: usize, owner: &Owner, result: &mut ParseResult) -> Option<usize> {
+        let open = self.skip_whitespace(keyword + "init".len(), end);
+        if self.code.get(open) != Some(&b'{') {
+            return None;
+        }
+        let close = self.after_closing(open, end);
+        self.scan_calls(&owner.id, open + 1, close, result);
+        Some(close)
+    }
+
+    /// `typealias Name<T> = Type`
+    fn type_alias(
+        &self,
+        statement: usize,
+        keyword: usize,
+        end: usize,
+        owner: Option<&Owner>,
+        result: &mut ParseResult,
+    ) -> Option<usize> {
+        let (modifiers, first_modifier) = self.modifiers(statement, keyword);
+        let name_start = self.skip_whitespace(keyword + "typealias".len(), end);
+        let name = self.word_at(name_start)?;
+        let declaration_end = self.statement_end(name_start, end);
+
+        let mut type_params = owner.map(|o| o.type_params.clone()).unwrap_or_default();
+        let after_name = name_start + name.len();
+        if self.code.get(after_name) == Some(&b'<') {
+            let close = self.skip_angles(after_name);
+            type_params.extend(self.type_param_names(after_name + 1, close - 1));
+        }
+
+        let signature_start = first_modifier.unwrap_or(keyword);
+        let symbol = self.symbol(
+            name,
+            "type",
+            (signature_start, declaration_end),
+            Some(self.collapsed(signature_start, declaration_end)),
+            !modifiers.contains(&"private"),
+            owner.map(|o| o.id.clone()),
+        );
+        if let Some(eq) = self.text(after_name, declaration_end).find('=') {
+            self.add_type_refs(&symbol.id, after_name + eq + 1, declaration_end, &type_params, result);
+        }
+
+        result.symbols.push(symbol);
+        Some(declaration_end)
+    }
+
+    /// Primary constructor parameters: their types are referenced by the
+    /// class, and `val`/`var` ones are properties of it
+    fn add_param(&self, param: &Param, this: &Owner, result: &mut ParseResult) {
+        if let Some((start, end)) = param.ty {
+            self.add_type_refs(&this.id, start, end, &this.type_params, result);
+        }
+        if !param.is_property {
+            return;
+        }
+
+        let (modifiers, _) = self.modifiers(param.start, param.name.0);
+        let end = param.ty.map_or(param.name.1, |(_, end)| end);
+        let symbol = self.symbol(
+            self.text(param.name.0, param.name.1),
+            "property",
+            (param.start, end),
+            Some(self.collapsed(param.start, end)),
+            !modifiers.contains(&"private"),
+            Some(this.id.clone()),
+        );
+        result.symbols.push(symbol);
+    }
+
+    /// Every `name(` call and `name { ... }` call with a trailing lambda in
+    /// `start..end`, with the receiver in front of it
+    fn scan_calls(&self, caller_id: &str, start: usize, end: usize, result: &mut ParseResult) {
+        let mut i = start;
+
+        while i < end {
+            let Some(word) = self.word_at(i) else {
+                i += 1;
+                continue;
+            };
+            let after = i + word.len();
+            let next = self.code.get(self.skip_inline_whitespace(after, end));
+            // `Listener {` in `object : Listener {` is a supertype, not a call
+            let is_lambda_call = next == Some(&b'{')
+                && word.starts_with(|c: char| c.is_ascii_lowercase())
+                && self.code[..i].iter().rev().find(|c| !c.is_ascii_whitespace()) != Some(&b':');
+            // `listOf<String>()`: type arguments between the name and the arguments
+            let arguments = match self.code.get(after) {
+                Some(b'<') => {
+                    let close = self.skip_angles(after);
+                    if self.code[close - 1] == b'>' { close } else { after }
+                }
+                _ => after,
+            };
+            let is_call = (self.code.get(arguments) == Some(&b'(') || is_lambda_call)
+                && !NOT_CALLS.contains(&word)
+                // Local declarations: `fun helper(`, `class Local {`
+                && !self.previous_word(i).is_some_and(|w| matches!(w, "fun" | "class" | "interface" | "object"))
+                && !self.code[..i].ends_with(b"::");
+
+            if is_call {
+                let qualifier = self.receiver(i);
+                result.calls.push(CallInfo {
+                    caller_id: caller_id.to_string(),
+                    callee: word.to_string(),
+                    qualifier,
+                    line: self.line(i),
+                });
+            }
+            i = after;
+        }
+    }
+
+    /// Record capitalised names in a type as references, skipping qualifiers
+    /// (`a.b.Type`) and type parameters
+    fn add_type_refs(&self, symbol_id: &str, start: usize, end: usize, declared: &[String], result: &mut ParseResult) {
+        let mut i = start;
+
+        while i < end {
+            let Some(word) = self.word_at(i) else {
+                i += 1;
+                continue;
+            };
+            let after = i + word.len();
+            let is_qualifier = self.code.get(after) == Some(&b'.');
+            let is_type = word.starts_with(|c: char| c.is_ascii_uppercase());
+
+            if is_type && !is_qualifier && !declared.iter().any(|d| d == word) {
+                result.type_refs.push(TypeRefInfo {
+                    symbol_id: symbol_id.to_string(),
+                    name: word.to_string(),
+                    line: self.line(i),
+                });
+            }
+            i = after;
+        }
+    }
+
+    // Scanning helpers
+
+    /// The identifier starting at `i`, if one starts there
+    fn word_at(&self, i: usize) -> Option<&'a str> {
+        let first = *self.code.get(i)?;
+        if !is_identifier_start(first) || (i > 0 && is_identifier_byte(self.code[i - 1])) {
+            return None;
+        }
+        let len = self.code[i..].iter().take_while(|&&c| is_identifier_byte(c)).count();
+        Some(&self.source[i..i + len])
+    }
+
+    /// The identifier ending just before `i`, skipping whitespace
+    fn previous_word(&self, i: usize) -> Option<&'a str> {
+        let end = self.code[..i].iter().rposition(|c| !c.is_ascii_whitespace())? + 1;
+        let len = self.code[..end].iter().rev().take_while(|&&c| is_identifier_byte(c)).count();
+        (len > 0).then(|| &self.source[end - len..end])
+    }
+
+    /// `a.b` in `a.b.call()`, `list` in `list?.map()`, `foo()` in `foo().bar()`
+    fn receiver(&self, name: usize) -> Option<String> {
+        let mut dot = name.checked_sub(1)?;
+        if self.code[dot] != b'.' {
+            return None;
+        }
+        if dot > 0 && self.code[dot - 1] == b'?' {
+            dot -= 1;
+        }
+
+        let mut start = dot;
+        loop {
+            // Step over call arguments, an index or a trailing lambda, then the name in front of it
+            if start > 0 && matches!(self.code[start - 1], b')' | b']' | b'}') {
+                match self.opening.get(&(start - 1)) {
+                    Some(&open) => {
+                        let spaces = self.code[..open].iter().rev().take_while(|&&c| c == b' ' || c == b'\t').count();
+                        start = open - spaces;
+                    }
+                    None => break,
+                }
+            }
+            let len = self.code[..start].iter().rev().take_while(|&&c| is_identifier_byte(c)).count();
+            start -= len;
+            if start > 0 && self.code[start - 1] == b'.' {
+                start -= 1;
+                if start > 0 && self.code[start - 1] == b'?' {
+                    start -= 1;
+                }
+                continue;
+            }
+            break;
+        }
+
+        let receiver = self.text(start, dot).trim();
+        (!receiver.is_empty()).then(|| receiver.to_string())
+    }
+
+    /// `Receiver.name` or `name` before `(`, `:` or `=`: (receiver, name, offset after the name)
+    fn declared_name(&self, start: usize, end: usize) -> Option<(Option<&'a str>, &'a str, usize)> {
+        let mut i = start;
+        let mut last_dot = None;
+
+        while i < end {
+            match self.code[i] {
+                b'<' => i = self.skip_angles(i),
+                b'.' => {
+                    last_dot = Some(i);
+                    i += 1;
+                }
+                b'?' => i += 1,
+                b'`' => {
+                    let close = self.code[i + 1..end].iter().position(|&c| c == b'`')? + i + 1;
+                    i = close + 1;
+                }
+                c if is_identifier_byte(c) => i += 1,
+                _ => break,
+            }
+        }
+
+        let name_start = last_dot.map_or(start, |dot| dot + 1);
+        let name = self.text(name_start, i).trim_matches('`');
+        if name.is_empty() {
+            return None;
+        }
+        let receiver = last_dot.map(|dot| self.text(start, dot));
+        Some((receiver, name, self.skip_inline_whitespace(i, end)))
+    }
+
+    /// Parameters between a pair of parentheses
+    fn params(&self, start: usize, end: usize) -> Vec<Param> {
+        self.split_top_level(start, end, b',')
+            .into_iter()
+            .filter_map(|(param_start, param_end)| {
+                let param_start = self.skip_whitespace(param_start, param_end);
+                let colon = self.find_top_level(param_start, param_end, b':')?;
+
+                // The name is the last word before the colon, after modifiers and annotations
+                let name_end = self.code[..colon].iter().rposition(|c| !c.is_ascii_whitespace())? + 1;
+                let name_len = self.code[..name_end].iter().rev().take_while(|&&c| is_identifier_byte(c)).count();
+                let name_start = name_end - name_len;
+                let is_property = self
+                    .previous_word(name_start)
+                    .is_some_and(|w| w == "val" || w == "var");
+
+                let type_end = self.find_top_level(colon + 1, param_end, b'=').unwrap_or(param_end);
+                Some(Param {
+                    name: (name_start, name_end),
+                    ty: Some((colon + 1, type_end)),
+                    is_property,
+                    start: param_start,
+                })
+            })
+            .collect()
+    }
+
+    /// Modifier keywords between `statement` and `keyword`, and where the first one starts
+    fn modifiers(&self, statement: usize, keyword: usize) -> (Vec<&'a str>, Option<usize>) {
+        let mut modifiers = Vec::new();
+        let mut first = None;
+
+        let mut i = statement;
+        while i < keyword {
+            match self.word_at(i) {
+                Some(word) => {
+                    let is_annotation = i > 0 && self.code[i - 1] == b'@';
+                    if !is_annotation && MODIFIERS.contains(&word) {
+                        first.get_or_insert(i);
+                        modifiers.push(word);
+                    }
+                    i += word.len();
+                }
+                None => i += 1,
+            }
+        }
+
+        (modifiers, first)
+    }
+
+    /// Where a class or function header stops: at its body, at an `=`, or at
+    /// the end of the line when nothing continues it on the next one
+    fn header_end(&self, start: usize, end: usize) -> HeaderEnd {
+        let mut i = start;
+
+        while i < end {
+            match self.code[i] {
+                b'(' | b'[' => i = self.after_closing(i, end),
+                b'<' => i = self.skip_angles(i),
+                b'{' => return HeaderEnd::Body(i),
+                b'=' => return HeaderEnd::Expression(i),
+                b'}' | b';' => return HeaderEnd::None(i),
+                b'\n' if !self.continues_after(i, end) => return HeaderEnd::None(i),
+                _ => i += 1,
+            }
+        }
+
+        HeaderEnd::None(end)
+    }
+
+    /// Whether the code around the line break at `newline` belongs to one
+    /// declaration: a trailing `,` or `:`, or a next line starting with `:`,
+    /// `,`, `{`, `.`, an operator or `where`
+    fn continues_after(&self, newline: usize, end: usize) -> bool {
+        let previous = self.code[..newline].iter().rev().find(|c| !c.is_ascii_whitespace());
+        if matches!(previous, Some(b',' | b':' | b'=' | b'.' | b'(' | b'+' | b'-' | b'*' | b'/' | b'&' | b'|')) {
+            return true;
+        }
+
+        let next = self.skip_whitespace(newline, end);
+        match self.code.get(next) {
+            Some(b':' | b',' | b'{' | b'.' | b'?' | b'+' | b'-' | b'*' | b'/' | b'&' | b'|' | b'=') => true,
+            _ => self.word_at(next) == Some("where"),
+        }
+    }
+
+    /// End of an expression statement starting at `start`
+    fn statement_end(&self, start: usize, end: usize) -> usize {
+        let mut i = start;
+
+        while i < end {
+            match self.code[i] {
+                b'(' | b'[' | b'{' => i = self.after_closing(i, end),
+                b'}' | b';' => return i,
+                b'\n' if i > start && !self.continues_after(i, end) => return i,
+                _ => i += 1,
+            }
+        }
+
+        end
+    }
+
+    /// End of a property type: before `=`, `by`, a getter or the line end
+    fn type_end(&self, start: usize, end: usize) -> usize {
+        let mut i = start;
+
+        while i < end {
+            match self.code[i] {
+                b'(' | b'[' => i = self.after_closing(i, end),
+                b'<' => i = self.skip_angles(i),
+                b'=' | b'{' | b'}' | b';' | b'\n' => break,
+                _ if matches!(self.word_at(i), Some("by" | "get" | "set")) => break,
+                _ => i += 1,
+            }
+        }
+
+        let trimmed = self.text(start, i).trim_end().len();
+        start + trimmed
+    }
+
+    /// The offset after the `>` closing the `<` at `open`
+    fn skip_angles(&self, open: usize) -> usize {
+        let mut depth = 0;
+        let mut i = open;
+
+        while i < self.code.len() {
+            match self.code[i] {
+                b'<' => depth += 1,
+                // `->` in function types is not a closing angle
+                b'>' if self.code[i - 1] != b'-' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return i + 1;
+                    }
+                }
+                b'(' | b'[' => {
+                    i = self.after_closing(i, self.code.len());
+                    continue;
+                }
+                b'{' | b'}' | b';' | b'=' => return i,
+                _ => {}
+            }
+            i += 1;
+        }
+
+        i
+    }
+
+    /// Declared names in `<in T : Bound, reified R>`
+    fn type_param_names(&self, start: usize, end: usize) -> Vec<String> {
+        self.split_top_level(start, end, b',')
+            .into_iter()
+            .filter_map(|(start, end)| {
+                self.text(start, end)
+                    .split(|c: char| !c.is_alphanumeric() && c != '_')
+                    .find(|word| !word.is_empty() && !matches!(*word, "in" | "out" | "reified"))
+                    .map(str::to_string)
+            })
+            .collect()
+    }
+
+    /// Ranges between `separator`s outside brackets
+    fn split_top_level(&self, start: usize, end: usize, separator: u8) -> Vec<(usize, usize)> {
+        let mut parts = Vec::new();
+        let mut part_start = start;
+        let mut i = start;
+
+        while i < end {
+            match self.code[i] {
+                b'(' | b'[' | b'{' => i = self.after_closing(i, end),
+                b'<' => i = self.skip_angles(i).min(end),
+                c if c == separator => {
+                    parts.push((part_start, i));
+                    i += 1;
+                    part_start = i;
+                }
+                _ => i += 1,
+            }
+        }
+        if !self.text(part_start, end).trim().is_empty() {
+            parts.push((part_start, end));
+        }
+
+        parts
+    }
+
+    fn find_top_level(&self, start: usize, end: usize, target: u8) -> Option<usize> {
+        let mut i = start;
+
+        while i < end {
+            match self.code[i] {
+                b'(' | b'[' | b'{' => i = self.after_closing(i, end),
+                b'<' if target != b'<' => i = self.skip_angles(i).min(end),
+                c if c == target => return Some(i),
+                _ => i += 1,
+            }
+        }
+
+        None
+    }
+
+    fn find_word(&self, word: &str, start: usize, end: usize) -> Option<usize> {
+        (start..end).find(|&i| self.word_at(i) == Some(word))
+    }
+
+    fn after_closing(&self, open: usize, end: usize) -> usize {
+        self.closing.get(&open).map_or(end, |close| close + 1)
+    }
+
+    fn skip_whitespace(&self, start: usize, end: usize) -> usize {
+        let len = self.code[start.min(end)..end].iter().take_while(|c| c.is_ascii_whitespace()).count();
+        start + len
+    }
+
+    fn skip_inline_whitespace(&self, start: usize, end: usize) -> usize {
+        let len = self.code[start.min(end)..end]
+            .iter()
+            .take_while(|&&c| c == b' ' || c == b'\t')
+            .count();
+        start + len
+    }
+
+    fn line_end(&self, start: usize) -> usize {
+        self.code[start..]
+            .iter()
+            .position(|&c| c == b'\n')
+            .map_or(self.code.len(), |len| start + len)
+    }
+
+    fn text(&self, start: usize, end: usize) -> &'a str {
+        self.source.get(start..end.max(start)).unwrap_or_default()
+    }
+
+    /// Source text with its whitespace runs collapsed, for signatures
+    fn collapsed(&self, start: usize, end: usize) -> String {
+        self.text(start, end).split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// 1-based line and column of a byte offset
+    fn position(&self, offset: usize) -> (i32, i32) {
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        (line as i32 + 1, (offset - self.line_starts[line]) as i32 + 1)
+    }
+
+    fn line(&self, offset: usize) -> i32 {
+        self.position(offset).0
+    }
+
+    fn symbol(
+        &self,
+        name: &str,
+        kind: &str,
+        (start, end): (usize, usize),
+        signature: Option<String>,
+        is_exported: bool,
+        parent_id: Option<String>,
+    ) -> SymbolRecord {
+        let (line, column) = self.position(start);
+        let (end_line, end_column) = self.position(end);
+
+        SymbolRecord {
+            id: Uuid::new_v4().to_string(),
+            file_id: self.file_id.to_string(),
+            name: name.to_string(),
+            kind: kind.to_string(),
+            line,
+            column,
+            end_line: Some(end_line),
+            end_column: Some(end_column),
+            signature,
+            documentation: None,
+            is_exported,
+            parent_id,
+        }
+    }
+}
+
+/// Name of a supertype or receiver: `a.b.Base<T>(args)` -> `Base`
+fn base_type_name(text: &str) -> Option<&str> {
+    let text = text.trim();
+    let end = text.find(['<', '(', '?', ' ']).unwrap_or(text.len());
+    let name = text[..end].rsplit('.').next()?.trim();
+    (!name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')).then_some(name)
+}
+
+fn is_identifier_start(c: u8) -> bool {
+    c.is_ascii_alphabetic() || c == b'_' || c >= 0x80
+}
+
+fn is_identifier_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c >= 0x80
+}
+
+/// Replace comments and the contents of string and character literals,
+/// templates included, with spaces, keeping line breaks and byte offsets
+fn blank_comments_and_strings(source: &[u8]) -> Vec<u8> {
+    let mut code = source.to_vec();
+    let blank = |code: &mut Vec<u8>, start: usize, end: usize| {
+        for c in &mut code[start..end] {
+            if *c != b'\n' {
+                *c = b' ';
+            }
+        }
+    };
+
+    let mut i = 0;
+    while i < source.len() {
+        let rest = &source[i..];
+        if rest.starts_with(b"//") {
+            let end = rest.iter().position(|&c| c == b'\n').map_or(source.len(), |len| i + len);
+            blank(&mut code, i, end);
+            i = end;
+        } else if rest.starts_with(b"/*") {
+            // Kotlin block comments nest
+            let mut depth = 0;
+            let mut j = i;
+            while j < source.len() {
+                if source[j..].starts_with(b"/*") {
+                    depth += 1;
+                    j += 2;
+                } else if source[j..].starts_with(b"*/") {
+                    depth -= 1;
+                    j += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    j += 1;
+                }
+            }
+            blank(&mut code, i, j);
+            i = j;
+        } else if matches!(rest[0], b'"' | b'\'') {
+            let end = literal_end(source, i);
+            // The quotes stay, so the code still shows a value there
+            let quotes = if rest.starts_with(b"\"\"\"") { 3 } else { 1 };
+            let closed = end >= i + 2 * quotes && source[end - quotes..end].iter().all(|&c| c == rest[0]);
+            blank(&mut code, i + quotes, if closed { end - quotes } else { end });
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+
+    code
+}
+
+/// Offset just past the string or character literal opening at `start`. A
+/// `${...}` template may hold literals of its own, quotes and all. Unterminated
+/// literals end at the line break, raw strings at the end of the source.
+fn literal_end(source: &[u8], start: usize) -> usize {
+    let quote = source[start];
+    let raw = source[start..].starts_with(b"\"\"\"");
+    let mut i = start + if raw { 3 } else { 1 };
+
+    while i < source.len() {
+        match source[i] {
+            b'\\' if !raw => i += 2,
+            b'\n' if !raw => return i,
+            // `""""` closes a raw string with its last three quotes
+            b'"' if raw && source[i..].starts_with(b"\"\"\"") => {
+                return i + source[i..].iter().take_while(|&&c| c == b'"').count();
+            }
+            c if c == quote && !raw => return i + 1,
+            b'$' if quote == b'"' && source.get(i + 1) == Some(&b'{') => i = template_end(source, i + 2),
+            _ => i += 1,
+        }
+    }
+
+    source.len()
+}
+
+/// Offset just past the `}` closing a `${` template whose expression starts at `start`
+fn template_end(source: &[u8], start: usize) -> usize {
+    let mut depth = 1;
+    let mut i = start;
+
+    while i < source.len() {
+        match source[i] {
+            b'"' | b'\'' => {
+                i = literal_end(source, i);
+                continue;
+            }
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    source.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_kotlin_symbols() {
+        let source = r#"
+package com.acme.shop
+
+import com.acme.model.Order
+import com.acme.util.*
+import com.acme.format.price as formatPrice
+
+/* Orders { not a body */
+@Service
+class OrderService<T : Item>(
+    private val repo: Repository,
+    val limit: Int = 10,
+) : BaseService(repo), Listener, Comparable<OrderService<T>> {
+    private val cache = mutableMapOf<String, Order>()
+
+    fun find(id: String): List<Order> {
+        val query = "select { from orders"
+        return repo.query(id).map { toOrder(it) }.also { log(it) }
+    }
+
+    private fun toOrder(row: Row): Order = Order(row.id)
+
+    companion object {
+        const val NAME = "orders"
+        fun create(): OrderService<Item> = OrderService(Repository())
+    }
+}
+
+interface Listener : EventListener {
+    fun changed(order: Order)
+}
+
+enum class Status { OPEN, CLOSED }
+
+object Registry
+
+fun Order.total(): Money = items.sumOf { it.price }
+
+typealias Orders = List<Order>
+"#;
+
+        let mut result = ParseResult::default();
+        extract("test", source, &mut result);
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+
+        let imports: Vec<_> = result
+            .imports
+            .iter()
+            .map(|i| (i.source.as_str(), i.imported_names[0].as_str()))
+            .collect();
+        assert_eq!(
+            imports,
+            vec![("com.acme.model", "Order"), ("com.acme.util", "*"), ("com.acme.format", "price")]
+        );
+
+        let service = symbol("OrderService");
+        assert_eq!(service.kind, "class");
+        assert_eq!((service.line, service.end_line), (10, Some(27)));
+        assert_eq!(service.signature.as_deref().map(|s| s.starts_with("class OrderService<T : Item>(")), Some(true));
+
+        let members: Vec<_> = result
+            .symbols
+            .iter()
+            .filter(|s| s.parent_id.as_deref() == Some(service.id.as_str()))
+            .map(|s| (s.name.as_str(), s.kind.as_str(), s.is_exported))
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                ("repo", "property", false),
+                ("limit", "property", true),
+                ("cache", "property", false),
+                ("find", "method", true),
+                ("toOrder", "method", false),
+                ("NAME", "property", true),
+                ("create", "method", true),
+            ]
+        );
+
+        assert_eq!(symbol("Listener").kind, "interface");
+        assert_eq!(symbol("changed").kind, "method");
+        assert_eq!(symbol("Status").kind, "enum");
+        assert_eq!(symbol("Registry").kind, "class");
+        assert_eq!(symbol("Orders").kind, "type");
+
+        let total = symbol("total");
+        assert_eq!(total.kind, "method");
+        assert!(result.members.iter().any(|m| m.symbol_id == total.id && m.owner == "Order"));
+
+        let bases: Vec<_> = result
+            .inheritance
+            .iter()
+            .map(|i| (i.symbol_name.as_str(), i.base.as_str(), i.kind.as_str()))
+            .collect();
+        assert_eq!(
+            bases,
+            vec![
+                ("OrderService", "BaseService", "extends"),
+                ("OrderService", "Listener", "implements"),
+                ("OrderService", "Comparable", "implements"),
+                ("Listener", "EventListener", "extends"),
+            ]
+        );
+
+        let find = symbol("find");
+        let calls: Vec<_> = result
+            .calls
+            .iter()
+            .filter(|c| c.caller_id == find.id)
+            .map(|c| (c.callee.as_str(), c.qualifier.as_deref()))
+            .collect();
+        assert_eq!(calls, vec![
+                ("query", Some("repo")),
+                ("map", Some("repo.query(id)")),
+                ("toOrder", None),
+                ("also", Some("repo.query(id).map { toOrder(it) }")),
+                ("log", None),
+            ]);
+
+        let refs: Vec<_> = result.type_refs.iter().map(|r| r.name.as_str()).collect();
+        assert!(refs.contains(&"Repository") && refs.contains(&"Row") && refs.contains(&"Money"));
+        assert!(!refs.contains(&"T"));
+    }
+
+    #[test]
+    fn test_string_templates() {
+        let source = r#"
+class Report(private val rows: List<Row>) {
+    fun title(map: Map<String, String>) = "Report ${map["name"]} { ${"}"} for ${map.getValue("year")}"
+
+    fun render(): String {
+        val quote = '"'
+        return """
+            <h1>${title(mapOf("name" to "}\""))}</h1>
+            ${rows.joinToString { "<tr>${it["cell"]}</tr>" }}
+        """
+    }
+}
+
+fun after(): Int = 1
+"#;
+
+        let mut result = ParseResult::default();
+        extract("test", source, &mut result);
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+
+        let report = symbol("Report");
+        assert_eq!((report.line, report.end_line), (2, Some(12)));
+        for method in ["title", "render"] {
+            assert_eq!(symbol(method).parent_id.as_deref(), Some(report.id.as_str()));
+        }
+        assert_eq!((symbol("render").line, symbol("render").end_line), (5, Some(11)));
+
+        let after = symbol("after");
+        assert_eq!((after.kind.as_str(), after.parent_id.as_deref(), after.line), ("function", None, 14));
+
+        // Template contents are string text, not calls
+        let title = symbol("title");
+        assert!(result.calls.iter().all(|c| c.caller_id != title.id));
+    }
+
+    #[test]
+    fn test_nested_classes() {
+        let source = r#"
+class Outer {
+    class Nested {
+        fun inner() {}
+    }
+
+    inner class Inner : Base() {
+        fun value() = 1
+    }
+
+    sealed interface State {
+        object Idle : State
+        data class Loading(val progress: Int) : State
+    }
+}
+"#;
+
+        let mut result = ParseResult::default();
+        extract("test", source, &mut result);
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+        let parent = |name: &str| {
+            let parent_id = symbol(name).parent_id.clone();
+            parent_id.map(|id| result.symbols.iter().find(|s| s.id == id).unwrap().name.as_str())
+        };
+
+        assert_eq!(parent("Outer"), None);
+        assert_eq!(parent("Nested"), Some("Outer"));
+        assert_eq!(parent("inner"), Some("Nested"));
+        assert_eq!(parent("Inner"), Some("Outer"));
+        assert_eq!(parent("value"), Some("Inner"));
+        assert_eq!(parent("State"), Some("Outer"));
+        assert_eq!(parent("Idle"), Some("State"));
+        assert_eq!(parent("Loading"), Some("State"));
+        assert_eq!(parent("progress"), Some("Loading"));
+
+        let bases: Vec<_> = result
+            .inheritance
+            .iter()
+            .map(|i| (i.symbol_name.as_str(), i.base.as_str(), i.kind.as_str()))
+            .collect();
+        assert_eq!(
+            bases,
+            vec![("Inner", "Base", "extends"), ("Idle", "State", "implements"), ("Loading", "State", "implements")]
+        );
+    }
+
+    #[test]
+    fn test_initializer_calls() {
+        let source = r#"
+class Cache(private val loader: Loader) {
+    private val entries = mutableMapOf<String, Entry>()
+    val size: Int
+        get() = entries.size
+    val config by lazy { loadConfig(loader) }
+
+    init {
+        loader.warmUp()
+        register(this)
+    }
+}
+
+val DEFAULT = Cache(
+    Loader.create(),
+)
+"#;
+
+        let mut result = ParseResult::default();
+        extract("test", source, &mut result);
+        let calls = |name: &str| -> Vec<(&str, Option<&str>)> {
+            let id = &result.symbols.iter().find(|s| s.name == name).unwrap().id;
+            result
+                .calls
+                .iter()
+                .filter(|c| &c.caller_id == id)
+                .map(|c| (c.callee.as_str(), c.qualifier.as_deref()))
+                .collect()
+        };
+
+        assert_eq!(calls("entries"), vec![("mutableMapOf", None)]);
+        assert!(calls("size").is_empty());
+        assert_eq!(calls("config"), vec![("lazy", None), ("loadConfig", None)]);
+        assert_eq!(calls("Cache"), vec![("warmUp", Some("loader")), ("register", None)]);
+        assert_eq!(calls("DEFAULT"), vec![("Cache", None), ("create", Some("Loader"))]);
+    }
+
+    #[test]
+    fn test_malformed_source() {
+        let source = r#"
+class Broken {
+    fun missingParen(a: Int {
+        start()
+    }
+    val text = "unterminated
+    fun stillFound() = finish()
+}
+
+fun survivor() = 1
+"#;
+
+        let mut result = ParseResult::default();
+        extract("test", source, &mut result);
+        let names: Vec<_> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert!(names.contains(&"Broken") && names.contains(&"stillFound") && names.contains(&"survivor"));
+        let survivor = result.symbols.iter().find(|s| s.name == "survivor").unwrap();
+        assert_eq!((survivor.parent_id.as_deref(), survivor.line), (None, 10));
+
+        // Source cut off anywhere, as while it is being edited, never panics
+        for end in (0..source.len()).filter(|&end| source.is_char_boundary(end)) {
+            extract("test", &source[..end], &mut ParseResult::default());
+        }
+        let sample = include_str!("kotlin.rs");
+        let start = sample.find("class OrderService").unwrap();
+        let sample = &sample[start..start + 1200];
+        for end in (0..sample.len()).filter(|&end| sample.is_char_boundary(end)) {
+            extract("test", &sample[..end], &mut ParseResult::default());
+        }
+    }
+}
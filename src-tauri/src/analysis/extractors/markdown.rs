@@ -0,0 +1,244 @@
+use super::{is_analyzed_file, local_reference};
+use crate::analysis::parser::{DocLinkInfo, DocLinkKind, ParseResult};
+
+/// Characters that end a file path mentioned in a code block: `src/main.rs:12`,
+/// `"./lib.sh"`, `(config.yaml)`
+const MENTION_DELIMITERS: &[char] = &['"', '\'', '`', '(', ')', '[', ']', '{', '}', '<', '>', ',', ';', ':', '=', '|'];
+
+/// Record the files a Markdown document links to: inline and reference-style
+/// links and images, `<a href>` and `<img src>` tags, and paths of analysed
+/// files named in fenced code blocks
+pub fn extract(source: &str, result: &mut ParseResult) {
+    let mut fence: Option<(char, usize)> = None;
+
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index as i32 + 1;
+        let trimmed = line.trim_start();
+
+        // ``` and ~~~ fences; a fence closes with at least as many of the same character
+        if let Some(marker) = trimmed.chars().next().filter(|&c| c == '`' || c == '~') {
+            let length = trimmed.chars().take_while(|&c| c == marker).count();
+            if length >= 3 && line.len() - trimmed.len() <= 3 {
+                match fence {
+                    None => {
+                        fence = Some((marker, length));
+                        continue;
+                    }
+                    Some((open, open_length)) if open == marker && length >= open_length => {
+                        fence = None;
+                        continue;
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        if fence.is_some() {
+            extract_mentions(line, line_number, result);
+        } else {
+            extract_links(&strip_code_spans(line), line_number, result);
+        }
+    }
+}
+
+/// Inline links and images, reference definitions and HTML tags on one line
+fn extract_links(line: &str, line_number: i32, result: &mut ParseResult) {
+    // `[id]: path "title"`
+    let trimmed = line.trim_start();
+    if let Some(definition) = trimmed.strip_prefix('[').and_then(|rest| rest.split_once("]:")) {
+        push_link(destination(definition.1.trim_start()), DocLinkKind::Link, line_number, result);
+        return;
+    }
+
+    // `[text](path)` and `![alt](path)`, text possibly holding an image badge
+    let bytes = line.as_bytes();
+    let mut opened: Vec<usize> = Vec::new();
+    for (i, &c) in bytes.iter().enumerate() {
+        match c {
+            b'[' => opened.push(i),
+            b']' => {
+                let Some(start) = opened.pop() else { continue };
+                let Some(rest) = line[i + 1..].strip_prefix('(') else { continue };
+                let kind = if start > 0 && bytes[start - 1] == b'!' {
+                    DocLinkKind::Image
+                } else {
+                    DocLinkKind::Link
+                };
+                push_link(destination(rest.trim_start()), kind, line_number, result);
+            }
+            _ => {}
+        }
+    }
+
+    for (tag, attribute, kind) in [("<a ", "href", DocLinkKind::Link), ("<img ", "src", DocLinkKind::Image)] {
+        let lower = line.to_ascii_lowercase();
+        let mut rest = lower.as_str();
+        while let Some(offset) = rest.find(tag) {
+            let start = line.len() - rest.len() + offset + tag.len();
+            let end = line[start..].find('>').map_or(line.len(), |end| start + end);
+            push_link(attribute_value(&line[start..end], attribute), kind, line_number, result);
+            rest = &lower[end..];
+        }
+    }
+}
+
+/// Paths of analysed files named in a line of a fenced code block. Only those
+/// that resolve become edges, so words that merely look like file names are
+/// harmless.
+fn extract_mentions(line: &str, line_number: i32, result: &mut ParseResult) {
+    for word in line.split(|c: char| c.is_whitespace() || MENTION_DELIMITERS.contains(&c)) {
+        let word = word.trim_end_matches('.');
+        if word.starts_with('-') || word.contains('$') || !is_analyzed_file(word) {
+            continue;
+        }
+        let Some(path) = local_reference(word) else { continue };
+        if !result.links.iter().any(|l| l.target == path && l.kind == DocLinkKind::Mention) {
+            result.links.push(DocLinkInfo {
+                target: path.to_string(),
+                line: line_number,
+                kind: DocLinkKind::Mention,
+            });
+        }
+    }
+}
+
+fn push_link(target: Option<&str>, kind: DocLinkKind, line: i32, result: &mut ParseResult) {
+    let Some(path) = target.and_then(local_reference) else { return };
+    result.links.push(DocLinkInfo {
+        target: percent_decode(path),
+        line,
+        kind,
+    });
+}
+
+/// The destination of a link: `<path with spaces>` or up to the first space
+/// or unbalanced `)`
+fn destination(text: &str) -> Option<&str> {
+    if let Some(rest) = text.strip_prefix('<') {
+        return rest.split_once('>').map(|(path, _)| path);
+    }
+
+    let mut depth = 0;
+    let end = text
+        .char_indices()
+        .find(|&(_, c)| match c {
+            '(' => {
+                depth += 1;
+                false
+            }
+            ')' if depth == 0 => true,
+            ')' => {
+                depth -= 1;
+                false
+            }
+            _ => c.is_whitespace(),
+        })
+        .map_or(text.len(), |(i, _)| i);
+    (end > 0).then(|| &text[..end])
+}
+
+/// The value of `name="value"` or `name='value'` in the attributes of a tag
+fn attribute_value<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let lower = attributes.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(offset) = lower[from..].find(name) {
+        let start = from + offset;
+        from = start + name.len();
+        if lower[..start].ends_with(|c: char| !c.is_ascii_whitespace()) {
+            continue;
+        }
+        let Some(value) = attributes[from..].trim_start().strip_prefix('=').map(str::trim_start) else { continue };
+        return match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next(),
+            _ => value.split(|c: char| c.is_ascii_whitespace() || c == '/').next(),
+        };
+    }
+    None
+}
+
+/// The line with `code spans` blanked, so that links shown as code are skipped
+fn strip_code_spans(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find('`') {
+        let ticks = rest[start..].chars().take_while(|&c| c == '`').count();
+        let fence = &rest[start..start + ticks];
+        let Some(end) = rest[start + ticks..].find(fence) else { break };
+        stripped.push_str(&rest[..start]);
+        stripped.push_str(&" ".repeat(ticks * 2 + end));
+        rest = &rest[start + ticks * 2 + end..];
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+/// `docs/Getting%20Started.md` -> `docs/Getting Started.md`
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+        match hex.filter(|_| bytes[i] == b'%').and_then(|h| u8::from_str_radix(h, 16).ok()) {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| path.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::parser::{DocLinkKind, Parser, SupportedLanguage};
+
+    #[test]
+    fn test_extract_markdown_links() {
+        let parser = Parser::new();
+        let source = r#"# Project
+
+[![CI](https://github.com/acme/app/actions/workflows/ci.yml/badge.svg)](.github/workflows/ci.yml)
+See the [architecture notes](docs/Architecture%20Notes.md#storage) and [the parser](<src/analysis/parser.rs>).
+![Diagram](./docs/diagram.png "Overview") and [home](https://example.com), [top](#project).
+Use `[not a link](src/fake.rs)` in examples.
+
+<p align="center"><img src="assets/logo.svg" width="120"></p>
+
+[license]: LICENSE
+
+```sh
+cargo run --bin server -- --config config/dev.yaml
+./scripts/deploy.sh "$TARGET"
+```
+
+~~~rust
+// see src/lib.rs:12 and [ignored](src/ignored.rs)
+~~~
+"#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Markdown, source).unwrap();
+
+        let links: Vec<_> = result.links.iter().map(|l| (l.target.as_str(), l.line, l.kind)).collect();
+        assert_eq!(
+            links,
+            vec![
+                (".github/workflows/ci.yml", 3, DocLinkKind::Link),
+                ("docs/Architecture Notes.md", 4, DocLinkKind::Link),
+                ("src/analysis/parser.rs", 4, DocLinkKind::Link),
+                ("./docs/diagram.png", 5, DocLinkKind::Image),
+                ("assets/logo.svg", 8, DocLinkKind::Image),
+                ("LICENSE", 10, DocLinkKind::Link),
+                ("config/dev.yaml", 13, DocLinkKind::Mention),
+                ("./scripts/deploy.sh", 14, DocLinkKind::Mention),
+                ("src/lib.rs", 18, DocLinkKind::Mention),
+                ("src/ignored.rs", 18, DocLinkKind::Mention),
+            ]
+        );
+        assert!(result.symbols.is_empty());
+    }
+}
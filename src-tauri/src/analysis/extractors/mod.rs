@@ -3,11 +3,23 @@ pub mod python;
 pub mod go;
 pub mod rust;
 pub mod c;
+pub mod cpp;
+pub mod java;
+pub mod csharp;
+pub mod ruby;
+pub mod php;
+pub mod shell;
+pub mod kotlin;
+pub mod markdown;
+pub mod html;
+pub mod css;
+pub mod sfc;
 pub mod swift;
 
 use tree_sitter::Node;
 use uuid::Uuid;
 
+use crate::analysis::parser::{CallInfo, InheritanceInfo, ParseResult, SupportedLanguage, TypeRefInfo};
 use crate::storage::SymbolRecord;
 
 /// Helper to get text from a node
@@ -61,6 +73,169 @@ pub fn find_children<'a>(node: &'a Node, kind: &str) -> Vec<Node<'a>> {
         .collect()
 }
 
+/// Name of a referenced type without module path or type arguments:
+/// `React.Component<P>`, `fmt::Display`, `models.Base`, `Generic[T]` -> last segment
+pub fn type_name<'a>(node: &Node, source: &'a [u8]) -> Option<&'a str> {
+    match node.kind() {
+        "identifier" | "type_identifier" | "property_identifier" | "simple_identifier" | "constant" | "name" => {
+            Some(node_text(node, source))
+        }
+        "member_expression" => type_name(&node.child_by_field_name("property")?, source),
+        "attribute" => type_name(&node.child_by_field_name("attribute")?, source),
+        "subscript" => type_name(&node.child_by_field_name("value")?, source),
+        // Java `a.b.Base` has no name field, its last child is the name
+        "nested_type_identifier" | "scoped_type_identifier" | "scoped_identifier" | "qualified_identifier"
+        | "qualified_name" | "scope_resolution" => node
+            .child_by_field_name("name")
+            .or_else(|| node.named_child(node.named_child_count().checked_sub(1)?))
+            .and_then(|n| type_name(&n, source)),
+        // TS and C++ use `name`, Rust `type`, Java and C# neither
+        "generic_type" | "template_type" | "generic_name" => node
+            .child_by_field_name("name")
+            .or_else(|| node.child_by_field_name("type"))
+            .or_else(|| node.named_child(0))
+            .and_then(|n| type_name(&n, source)),
+        // Swift `Outer.Inner<T>`
+        "user_type" => {
+            let mut cursor = node.walk();
+            let last = node
+                .named_children(&mut cursor)
+                .filter(|c| c.kind() == "type_identifier")
+                .last();
+            last.map(|n| node_text(&n, source))
+        }
+        _ => None,
+    }
+}
+
+/// Record that a type extends or implements the type named by `base`
+pub fn add_inheritance(
+    symbol_id: Option<&str>,
+    symbol_name: &str,
+    base: &Node,
+    kind: &str,
+    source: &[u8],
+    result: &mut ParseResult,
+) {
+    if let Some(base_name) = type_name(base, source) {
+        result.inheritance.push(InheritanceInfo {
+            symbol_id: symbol_id.map(str::to_string),
+            symbol_name: symbol_name.to_string(),
+            base: base_name.to_string(),
+            kind: kind.to_string(),
+            line: base.start_position().row as i32 + 1,
+        });
+    }
+}
+
+/// Record the types named in the given fields of a declaration (parameters,
+/// return type, field types, type parameters...) as referenced by `symbol_id`.
+/// The type parameters of the declaration and of the declarations around it
+/// are not references.
+pub fn add_type_refs(symbol_id: &str, node: &Node, fields: &[&str], source: &[u8], result: &mut ParseResult) {
+    let mut children = Vec::new();
+    for field in fields {
+        let mut cursor = node.walk();
+        children.extend(node.children_by_field_name(field, &mut cursor));
+    }
+    add_type_refs_in(symbol_id, node, &children, source, result);
+}
+
+/// Like [`add_type_refs`], for grammars that do not put a declaration's types
+/// in dedicated fields: record the types named below the given children of `node`
+pub fn add_type_refs_in(symbol_id: &str, node: &Node, children: &[Node], source: &[u8], result: &mut ParseResult) {
+    let mut names = Vec::new();
+    for child in children {
+        collect_type_names(child, false, &mut names);
+    }
+    add_type_names(symbol_id, node, &names, source, result);
+}
+
+/// Record the given type name nodes as referenced by `symbol_id`, for grammars
+/// that need their own walk to find them (C# names types with plain identifiers).
+/// Type parameters in scope at `node` are skipped as in [`add_type_refs`].
+pub fn add_type_names(symbol_id: &str, node: &Node, names: &[Node], source: &[u8], result: &mut ParseResult) {
+    let mut cursor = node.walk();
+    let mut type_parameters: Vec<Node> = node
+        .child_by_field_name("type_parameters")
+        .or_else(|| {
+            node.children(&mut cursor)
+                .find(|c| c.kind() == "type_parameters" || c.kind() == "type_parameter_list")
+        })
+        .into_iter()
+        .collect();
+    // So do those of enclosing classes, impl blocks and C++ templates
+    let mut ancestor = node.parent();
+    while let Some(parent) = ancestor {
+        match parent.kind() {
+            "template_declaration" => type_parameters.extend(parent.child_by_field_name("parameters")),
+            _ => {
+                let mut cursor = parent.walk();
+                type_parameters.extend(
+                    parent
+                        .child_by_field_name("type_parameters")
+                        .or_else(|| parent.children(&mut cursor).find(|c| c.kind() == "type_parameter_list")),
+                );
+            }
+        }
+        ancestor = parent.parent();
+    }
+
+    let mut declared: Vec<&str> = Vec::new();
+    for params in type_parameters {
+        let mut cursor = params.walk();
+        let names = params
+            .named_children(&mut cursor)
+            .filter_map(|param| match param.kind() {
+                "type_identifier" => Some(param),
+                // Swift `T: Codable` and C++ `typename T` have no name field
+                "type_parameter" | "type_parameter_declaration" | "variadic_type_parameter_declaration"
+                    if param.named_child(0).map(|c| c.kind()) == Some("type_identifier") =>
+                {
+                    param.named_child(0)
+                }
+                _ => param.child_by_field_name("name").or_else(|| param.child_by_field_name("left")),
+            })
+            .map(|name| node_text(&name, source));
+        declared.extend(names);
+    }
+
+    for named in names {
+        let name = node_text(named, source);
+        let is_identifier = name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if is_identifier && !name.is_empty() && !declared.contains(&name) {
+            result.type_refs.push(TypeRefInfo {
+                symbol_id: symbol_id.to_string(),
+                name: name.to_string(),
+                line: named.start_position().row as i32 + 1,
+            });
+        }
+    }
+}
+
+/// Nodes naming a type below `node`: `type_identifier` in most grammars, the
+/// plain identifiers of Python annotations (`type` nodes), skipping module
+/// qualifiers (`models.User` -> `User`), and quoted forward references
+fn collect_type_names<'a>(node: &Node<'a>, in_annotation: bool, names: &mut Vec<Node<'a>>) {
+    let mut cursor = node.walk();
+    let named = match node.kind() {
+        "type_identifier" => Some(*node),
+        "identifier" if in_annotation => Some(*node),
+        "attribute" if in_annotation => node.child_by_field_name("attribute"),
+        "string" if in_annotation => node.children(&mut cursor).find(|c| c.kind() == "string_content"),
+        _ => None,
+    };
+    if let Some(named) = named {
+        names.push(named);
+        return;
+    }
+
+    let in_annotation = in_annotation || (node.is_named() && node.kind() == "type");
+    for child in node.children(&mut cursor) {
+        collect_type_names(&child, in_annotation, names);
+    }
+}
+
 /// Helper to find the first descendant with a given type
 pub fn find_descendant<'a>(node: &'a Node, kind: &str) -> Option<Node<'a>> {
     let mut cursor = node.walk();
@@ -88,3 +263,156 @@ pub fn find_descendant<'a>(node: &'a Node, kind: &str) -> Option<Node<'a>> {
         }
     }
 }
+
+/// The project path a URL in HTML, CSS or Markdown points at, without query or
+/// fragment. None for remote, `data:` and `mailto:` URLs, in-page anchors and
+/// template placeholders.
+pub fn local_reference(url: &str) -> Option<&str> {
+    let url = url.trim();
+    if url.starts_with("//") || url.starts_with('#') || url.contains("{{") || url.contains("${") {
+        return None;
+    }
+    let has_scheme = url.split_once(':').is_some_and(|(scheme, _)| {
+        !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    });
+    if has_scheme {
+        return None;
+    }
+
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    (!path.is_empty()).then_some(path)
+}
+
+/// Whether the analyzer discovers files like `path`, judging by its extension
+pub fn is_analyzed_file(path: &str) -> bool {
+    std::path::Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SupportedLanguage::from_extension(ext).is_some())
+}
+
+/// Named function and method declarations across the tree-sitter grammars we parse
+const FUNCTION_DECLARATION_KINDS: &[&str] = &[
+    "function_declaration",
+    "generator_function_declaration",
+    "method_definition",
+    "function_definition",
+    "method_declaration",
+    "function_item",
+    "local_function_statement",
+    "method",
+    "singleton_method",
+];
+
+/// Helper to record every call site below `node` as made from `caller_id`
+///
+/// Covers the call node shapes of all tree-sitter grammars we parse:
+/// `call_expression` (TS/JS, Go, Rust, C), `call` (Python), `new_expression`
+/// (TS/JS), `method_invocation` and `object_creation_expression` (Java).
+/// Nested named declarations are not descended into, their calls belong to the
+/// nested symbol; calls in anonymous callbacks and closures count for `caller_id`.
+pub fn extract_calls(caller_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if FUNCTION_DECLARATION_KINDS.contains(&child.kind()) {
+            continue;
+        }
+        let callee = match child.kind() {
+            // Ruby `receiver.method(args)`, where `Type.new` constructs `Type`
+            "call" if child.child_by_field_name("method").is_some() => {
+                let method = child.child_by_field_name("method").map(|m| node_text(&m, source));
+                match child.child_by_field_name("receiver") {
+                    Some(receiver) if method == Some("new") && matches!(receiver.kind(), "constant" | "scope_resolution") => {
+                        type_name(&receiver, source).map(|name| (name.to_string(), None))
+                    }
+                    receiver => method.map(|m| (m.to_string(), receiver.map(|r| node_text(&r, source).to_string()))),
+                }
+            }
+            "call_expression" | "call" | "new_expression" | "invocation_expression" | "function_call_expression" => child
+                .child_by_field_name("function")
+                .or_else(|| child.child_by_field_name("constructor"))
+                .and_then(|c| callee_name(&c, source)),
+            // Java and PHP put the receiver and the name side by side
+            "method_invocation" | "member_call_expression" | "nullsafe_member_call_expression"
+            | "scoped_call_expression" => child.child_by_field_name("name").map(|name| {
+                let qualifier = child
+                    .child_by_field_name("object")
+                    .or_else(|| child.child_by_field_name("scope"))
+                    .map(|o| node_text(&o, source).to_string());
+                (node_text(&name, source).to_string(), qualifier)
+            }),
+            // PHP `new Order()` names the class without a field
+            "object_creation_expression" => child
+                .child_by_field_name("type")
+                .or_else(|| {
+                    let mut cursor = child.walk();
+                    let class = child
+                        .named_children(&mut cursor)
+                        .find(|c| c.kind() == "name" || c.kind() == "qualified_name");
+                    class
+                })
+                .and_then(|t| type_name(&t, source))
+                .map(|name| (name.to_string(), None)),
+            _ => None,
+        };
+
+        if let Some((name, qualifier)) = callee {
+            result.calls.push(CallInfo {
+                caller_id: caller_id.to_string(),
+                callee: name,
+                qualifier,
+                line: child.start_position().row as i32 + 1,
+            });
+        }
+
+        extract_calls(caller_id, &child, source, result);
+    }
+}
+
+/// Split a callee expression into the called name and its qualifier
+fn callee_name(node: &Node, source: &[u8]) -> Option<(String, Option<String>)> {
+    match node.kind() {
+        "identifier" | "field_identifier" | "property_identifier" | "private_property_identifier"
+        | "type_identifier" | "name" => Some((node_text(node, source).to_string(), None)),
+        // obj.method(), self.method(), pkg.Func(), Type::new()
+        "member_expression" | "attribute" | "selector_expression" | "field_expression"
+        | "scoped_identifier" | "member_access_expression" => {
+            let name = node
+                .child_by_field_name("property")
+                .or_else(|| node.child_by_field_name("attribute"))
+                .or_else(|| node.child_by_field_name("field"))
+                .or_else(|| node.child_by_field_name("name"))?;
+            let qualifier = node
+                .child_by_field_name("object")
+                .or_else(|| node.child_by_field_name("operand"))
+                .or_else(|| node.child_by_field_name("value"))
+                .or_else(|| node.child_by_field_name("argument"))
+                .or_else(|| node.child_by_field_name("path"))
+                .or_else(|| node.child_by_field_name("expression"))
+                .map(|q| node_text(&q, source).to_string());
+            let (name, _) = callee_name(&name, source)?;
+            Some((name, qualifier))
+        }
+        // C++ ns::func(), Type::method(): the innermost name, qualified by everything before it
+        "qualified_identifier" => {
+            let mut name = node.child_by_field_name("name")?;
+            while name.kind() == "qualified_identifier" {
+                name = name.child_by_field_name("name")?;
+            }
+            let (callee, _) = callee_name(&name, source)?;
+            let qualifier = std::str::from_utf8(&source[node.start_byte()..name.start_byte()])
+                .ok()
+                .map(|q| q.trim_end_matches("::").to_string())
+                .filter(|q| !q.is_empty());
+            Some((callee, qualifier))
+        }
+        // Rust turbofish: parse::<T>(), C++ make<T>()
+        "generic_function" => callee_name(&node.child_by_field_name("function")?, source),
+        "template_function" => callee_name(&node.child_by_field_name("name")?, source),
+        // C# Create<T>()
+        "generic_name" => callee_name(&node.named_child(0)?, source),
+        // PHP \App\helper()
+        "qualified_name" => callee_name(&node.child_by_field_name("name")?, source),
+        _ => None,
+    }
+}
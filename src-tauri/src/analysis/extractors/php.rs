@@ -0,0 +1,486 @@
+use tree_sitter::Node;
+
+use super::{add_inheritance, add_type_names, create_symbol, extract_calls, find_child, find_children, node_text};
+use crate::analysis::parser::{ImportInfo, ParseResult};
+
+/// Expressions that load another file
+const INCLUDE_EXPRESSIONS: &[&str] = &[
+    "require_expression",
+    "require_once_expression",
+    "include_expression",
+    "include_once_expression",
+];
+
+/// Extract symbols and relationships from PHP AST
+pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResult) {
+    extract_includes(root, source, result);
+    extract_declarations(file_id, root, source, result, &Scope::default());
+}
+
+/// The namespace declarations are in
+#[derive(Debug, Clone, Default)]
+struct Scope {
+    /// The namespace symbol
+    parent_id: Option<String>,
+}
+
+fn extract_declarations(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, scope: &Scope) {
+    let mut scope = scope.clone();
+    let mut cursor = node.walk();
+
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "namespace_definition" => {
+                let inner = extract_namespace(file_id, &child, source, result);
+                match child.child_by_field_name("body") {
+                    Some(body) => extract_declarations(file_id, &body, source, result, &inner),
+                    // `namespace App\Models;` applies to the rest of the file
+                    None => scope = inner,
+                }
+            }
+            "namespace_use_declaration" => extract_use(&child, source, result),
+            "function_definition" => extract_function(file_id, &child, source, result, &scope),
+            "const_declaration" => extract_constants(file_id, &child, source, result, scope.parent_id.as_deref(), true),
+            _ => extract_type(file_id, &child, source, result, &scope),
+        }
+    }
+}
+
+/// `use App\Models\User;` imports `User` from its fully qualified name,
+/// `use App\Models\{User, Order as O};` both classes, `use function
+/// App\Support\helper;` a function
+fn extract_use(node: &Node, source: &[u8], result: &mut ParseResult) {
+    let prefix = find_child(node, "namespace_name").map(|n| node_text(&n, source));
+    let group = node.child_by_field_name("body").unwrap_or(*node);
+    let clauses = find_children(&group, "namespace_use_clause");
+
+    for clause in clauses {
+        let mut cursor = clause.walk();
+        let Some(name) = clause
+            .named_children(&mut cursor)
+            .find(|c| c.kind() == "name" || c.kind() == "qualified_name")
+        else {
+            continue;
+        };
+        let name = node_text(&name, source).trim_start_matches('\\');
+        let path = match prefix {
+            Some(prefix) => format!("{}\\{}", prefix.trim_start_matches('\\'), name),
+            None => name.to_string(),
+        };
+        let imported = path.rsplit('\\').next().unwrap_or_default().to_string();
+
+        result.imports.push(ImportInfo {
+            source: path,
+            imported_names: vec![imported],
+            line: clause.start_position().row as i32 + 1,
+            ..Default::default()
+        });
+    }
+}
+
+/// `require_once 'lib/db.php'` and friends, anywhere in the file.
+/// `__DIR__ . '/x.php'` is relative to this file and recorded as `./x.php`.
+fn extract_includes(node: &Node, source: &[u8], result: &mut ParseResult) {
+    let mut cursor = node.walk();
+
+    for child in node.named_children(&mut cursor) {
+        if INCLUDE_EXPRESSIONS.contains(&child.kind()) {
+            if let Some(path) = child.named_child(0).and_then(|arg| include_path(&arg, source)) {
+                result.imports.push(ImportInfo {
+                    source: path,
+                    line: child.start_position().row as i32 + 1,
+                    ..Default::default()
+                });
+            }
+            continue;
+        }
+        extract_includes(&child, source, result);
+    }
+}
+
+/// Record a namespace declaration and return the scope of its members
+fn extract_namespace(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) -> Scope {
+    // `namespace { ... }` is the global namespace
+    let Some(name) = node.child_by_field_name("name") else { return Scope::default() };
+    let name = node_text(&name, source);
+
+    let symbol = create_symbol(
+        file_id,
+        name,
+        "namespace",
+        node,
+        Some(format!("namespace {}", name)),
+        None,
+        true,
+        None,
+    );
+
+    if !result.namespaces.iter().any(|n| n == name) {
+        result.namespaces.push(name.to_string());
+    }
+    let inner = Scope {
+        parent_id: Some(symbol.id.clone()),
+    };
+    result.symbols.push(symbol);
+    inner
+}
+
+/// A class, interface, trait or enum with its members
+fn extract_type(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, scope: &Scope) {
+    let kind = match node.kind() {
+        "class_declaration" => "class",
+        "interface_declaration" => "interface",
+        "trait_declaration" => "trait",
+        "enum_declaration" => "enum",
+        _ => return,
+    };
+    let Some(name) = node.child_by_field_name("name") else { return };
+    let name = node_text(&name, source);
+
+    let symbol = create_symbol(
+        file_id,
+        name,
+        kind,
+        node,
+        Some(signature(node, source)),
+        None,
+        true,
+        scope.parent_id.clone(),
+    );
+
+    // Interfaces extend interfaces, classes extend one class
+    let bases = [("base_clause", "extends"), ("class_interface_clause", "implements")];
+    for (clause, relation) in bases {
+        if let Some(clause) = find_child(node, clause) {
+            let mut cursor = clause.walk();
+            for base in clause.named_children(&mut cursor) {
+                add_inheritance(Some(&symbol.id), name, &base, relation, source, result);
+            }
+        }
+    }
+
+    let id = symbol.id.clone();
+    result.symbols.push(symbol);
+
+    let Some(body) = node.child_by_field_name("body") else { return };
+    let mut cursor = body.walk();
+    for member in body.named_children(&mut cursor) {
+        match member.kind() {
+            "method_declaration" => extract_method(file_id, &member, source, result, &id),
+            "property_declaration" => extract_properties(file_id, &member, source, result, &id),
+            "const_declaration" => {
+                let is_public = is_public(&member, source);
+                extract_constants(file_id, &member, source, result, Some(&id), is_public);
+            }
+            // `use Loggable;` copies a trait's members into the class
+            "use_declaration" => {
+                let mut cursor = member.walk();
+                for used in member.named_children(&mut cursor) {
+                    add_inheritance(Some(&id), name, &used, "implements", source, result);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn extract_function(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, scope: &Scope) {
+    let Some(name) = node.child_by_field_name("name") else { return };
+
+    let symbol = create_symbol(
+        file_id,
+        node_text(&name, source),
+        "function",
+        node,
+        Some(signature(node, source)),
+        None,
+        true,
+        scope.parent_id.clone(),
+    );
+
+    add_type_refs(&symbol.id, node, &["parameters", "return_type"], source, result);
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_calls(&symbol.id, &body, source, result);
+    }
+
+    result.symbols.push(symbol);
+}
+
+fn extract_method(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, parent_id: &str) {
+    let Some(name) = node.child_by_field_name("name") else { return };
+
+    let symbol = create_symbol(
+        file_id,
+        node_text(&name, source),
+        "method",
+        node,
+        Some(signature(node, source)),
+        None,
+        is_public(node, source),
+        Some(parent_id.to_string()),
+    );
+
+    add_type_refs(&symbol.id, node, &["parameters", "return_type"], source, result);
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_calls(&symbol.id, &body, source, result);
+    }
+
+    result.symbols.push(symbol);
+}
+
+/// One symbol per property: `private ?Logger $logger = null, $cache;`
+fn extract_properties(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, parent_id: &str) {
+    let ty = node.child_by_field_name("type").map(|t| node_text(&t, source));
+
+    for element in find_children(node, "property_element") {
+        let Some(name) = element.child_by_field_name("name") else { continue };
+        let name = node_text(&name, source);
+
+        let symbol = create_symbol(
+            file_id,
+            name.trim_start_matches('$'),
+            "property",
+            &element,
+            Some(ty.map_or(name.to_string(), |ty| format!("{} {}", ty, name))),
+            None,
+            is_public(node, source),
+            Some(parent_id.to_string()),
+        );
+        add_type_refs(&symbol.id, node, &["type"], source, result);
+        extract_calls(&symbol.id, &element, source, result);
+        result.symbols.push(symbol);
+    }
+}
+
+/// `const LIMIT = 10, OTHER = 20;` at the top level or in a class
+fn extract_constants(
+    file_id: &str,
+    node: &Node,
+    source: &[u8],
+    result: &mut ParseResult,
+    parent_id: Option<&str>,
+    is_public: bool,
+) {
+    for element in find_children(node, "const_element") {
+        let Some(name) = find_child(&element, "name") else { continue };
+        let name = node_text(&name, source);
+
+        let symbol = create_symbol(
+            file_id,
+            name,
+            "constant",
+            &element,
+            Some(format!("const {}", name)),
+            None,
+            is_public,
+            parent_id.map(str::to_string),
+        );
+        extract_calls(&symbol.id, &element, source, result);
+        result.symbols.push(symbol);
+    }
+}
+
+// Helper functions
+
+/// Record the classes named by `named_type`s below the given fields:
+/// parameter, return and property types
+fn add_type_refs(symbol_id: &str, node: &Node, fields: &[&str], source: &[u8], result: &mut ParseResult) {
+    fn collect<'a>(node: &Node<'a>, names: &mut Vec<Node<'a>>) {
+        if node.kind() == "named_type" {
+            // `\App\Models\User` -> `User`
+            let name = node.named_child(0).and_then(|n| match n.kind() {
+                "qualified_name" => n.child_by_field_name("name"),
+                _ => Some(n),
+            });
+            names.extend(name);
+            return;
+        }
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            collect(&child, names);
+        }
+    }
+
+    let mut names = Vec::new();
+    for field in fields {
+        if let Some(child) = node.child_by_field_name(field) {
+            collect(&child, &mut names);
+        }
+    }
+    add_type_names(symbol_id, node, &names, source, result);
+}
+
+/// The file an include expression loads, when it is a literal path or a path
+/// relative to `__DIR__` / `dirname(__FILE__)`
+fn include_path(node: &Node, source: &[u8]) -> Option<String> {
+    match node.kind() {
+        "string" | "encapsed_string" => string_content(node, source).map(str::to_string),
+        "parenthesized_expression" => include_path(&node.named_child(0)?, source),
+        "binary_expression" => {
+            let left = node.child_by_field_name("left")?;
+            let right = node.child_by_field_name("right")?;
+            let left_text = node_text(&left, source);
+            let is_dir = left_text == "__DIR__" || left_text.replace(' ', "") == "dirname(__FILE__)";
+            if !is_dir {
+                return None;
+            }
+            let rest = string_content(&right, source)?;
+            Some(format!(".{}", rest))
+        }
+        _ => None,
+    }
+}
+
+/// The text of a string without interpolation
+fn string_content<'a>(node: &Node, source: &'a [u8]) -> Option<&'a str> {
+    if !matches!(node.kind(), "string" | "encapsed_string") || node.named_child_count() != 1 {
+        return None;
+    }
+    node.named_child(0)
+        .filter(|c| c.kind() == "string_content")
+        .map(|c| node_text(&c, source))
+}
+
+/// Members are public unless declared `private` or `protected`
+fn is_public(node: &Node, source: &[u8]) -> bool {
+    find_child(node, "visibility_modifier").map_or("public", |v| node_text(&v, source)) == "public"
+}
+
+/// The declaration up to its body, without attributes: `public static function create(array $data): Order`
+fn signature(node: &Node, source: &[u8]) -> String {
+    let mut cursor = node.walk();
+    let start = node
+        .children(&mut cursor)
+        .find(|c| c.kind() != "attribute_list")
+        .map_or(node.start_byte(), |c| c.start_byte());
+    let end = node.child_by_field_name("body").map_or(node.end_byte(), |body| body.start_byte());
+
+    let text = std::str::from_utf8(&source[start..end.max(start)]).unwrap_or_default();
+    text.trim().trim_end_matches(';').split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::parser::{Parser, SupportedLanguage};
+
+    #[test]
+    fn test_extract_php_symbols() {
+        let parser = Parser::new();
+        let source = r#"<?php
+declare(strict_types=1);
+
+namespace App\Services;
+
+use App\Models\{User, Order as O};
+use function App\Support\helper;
+use Psr\Log\LoggerInterface;
+
+require_once __DIR__ . '/../bootstrap.php';
+require 'config/app.php';
+
+interface Billable extends \Countable {}
+
+trait Loggable
+{
+    protected function log(string $m): void { $this->logger->info($m); }
+}
+
+#[Service]
+final class OrderService extends BaseService implements Billable, \JsonSerializable
+{
+    use Loggable;
+
+    public const LIMIT = 10;
+    private ?LoggerInterface $logger = null;
+
+    public static function create(array $data): Order
+    {
+        $o = new Order($data);
+        self::validate($o);
+        return helper(User::find(1));
+    }
+}
+
+function format_money(Money $m): string { return sprintf('%d', $m->amount); }
+"#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Php, source).unwrap();
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+
+        let imports: Vec<_> = result
+            .imports
+            .iter()
+            .map(|i| (i.source.as_str(), i.imported_names.first().map(String::as_str)))
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("./../bootstrap.php", None),
+                ("config/app.php", None),
+                ("App\\Models\\User", Some("User")),
+                ("App\\Models\\Order", Some("Order")),
+                ("App\\Support\\helper", Some("helper")),
+                ("Psr\\Log\\LoggerInterface", Some("LoggerInterface")),
+            ]
+        );
+        assert_eq!(result.namespaces, vec!["App\\Services"]);
+
+        let service = symbol("OrderService");
+        assert_eq!(service.kind, "class");
+        assert_eq!(service.parent_id.as_deref(), Some(symbol("App\\Services").id.as_str()));
+        assert_eq!(
+            service.signature.as_deref(),
+            Some("final class OrderService extends BaseService implements Billable, \\JsonSerializable")
+        );
+        assert_eq!(symbol("Loggable").kind, "trait");
+        assert_eq!(symbol("format_money").kind, "function");
+
+        let members: Vec<_> = result
+            .symbols
+            .iter()
+            .filter(|s| s.parent_id.as_deref() == Some(service.id.as_str()))
+            .map(|s| (s.name.as_str(), s.kind.as_str(), s.is_exported))
+            .collect();
+        assert_eq!(
+            members,
+            vec![("LIMIT", "constant", true), ("logger", "property", false), ("create", "method", true)]
+        );
+        assert!(!symbol("log").is_exported);
+
+        let bases: Vec<_> = result
+            .inheritance
+            .iter()
+            .map(|i| (i.symbol_name.as_str(), i.base.as_str(), i.kind.as_str()))
+            .collect();
+        assert_eq!(
+            bases,
+            vec![
+                ("Billable", "Countable", "extends"),
+                ("OrderService", "BaseService", "extends"),
+                ("OrderService", "Billable", "implements"),
+                ("OrderService", "JsonSerializable", "implements"),
+                ("OrderService", "Loggable", "implements"),
+            ]
+        );
+
+        let calls: Vec<_> = result
+            .calls
+            .iter()
+            .map(|c| (c.callee.as_str(), c.qualifier.as_deref()))
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                ("info", Some("$this->logger")),
+                ("Order", None),
+                ("validate", Some("self")),
+                ("helper", None),
+                ("find", Some("User")),
+                ("sprintf", None),
+            ]
+        );
+
+        let refs: Vec<_> = result.type_refs.iter().map(|r| r.name.as_str()).collect();
+        assert!(refs.contains(&"LoggerInterface") && refs.contains(&"Order") && refs.contains(&"Money"));
+    }
+}
@@ -1,7 +1,7 @@
 use tree_sitter::Node;
 
-use super::{create_symbol, find_child, find_children, node_text};
-use crate::analysis::parser::{ImportInfo, ParseResult};
+use super::{add_inheritance, add_type_refs, create_symbol, extract_calls, find_child, node_text};
+use crate::analysis::parser::{ImportInfo, ImportUsage, ParseResult};
 
 /// Extract symbols and relationships from Python AST
 pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResult) {
@@ -21,11 +21,53 @@ pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResu
                     extract_class(file_id, &def, source, result);
                 }
             }
+            "expression_statement" => extract_assignment(file_id, &child, source, result),
+            "if_statement" | "try_statement" => {
+                extract_guarded_imports(&child, source, result, ImportUsage::Conditional)
+            }
             _ => {}
         }
     }
 }
 
+/// Imports nested in a module-level `if` or `try`. Those in the body of
+/// `if TYPE_CHECKING:` are type-only, anything else behind a guard is conditional.
+fn extract_guarded_imports(node: &Node, source: &[u8], result: &mut ParseResult, usage: ImportUsage) {
+    let type_checked = node
+        .child_by_field_name("condition")
+        .filter(|condition| node.kind() == "if_statement" && is_type_checking(condition, source))
+        .and_then(|_| node.child_by_field_name("consequence"));
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        let usage = if Some(child) == type_checked { ImportUsage::TypeOnly } else { usage };
+        let count = result.imports.len();
+        match child.kind() {
+            "import_statement" => extract_import(&child, source, result),
+            "import_from_statement" => extract_import_from(&child, source, result),
+            // Imports inside functions and classes are not module dependencies
+            "function_definition" | "class_definition" | "decorated_definition" => continue,
+            _ => {
+                extract_guarded_imports(&child, source, result, usage);
+                continue;
+            }
+        }
+        for import in &mut result.imports[count..] {
+            import.usage = usage;
+        }
+    }
+}
+
+/// `TYPE_CHECKING` or `typing.TYPE_CHECKING`
+fn is_type_checking(condition: &Node, source: &[u8]) -> bool {
+    let name = match condition.kind() {
+        "attribute" => condition.child_by_field_name("attribute"),
+        "identifier" => Some(*condition),
+        _ => None,
+    };
+    name.is_some_and(|name| node_text(&name, source) == "TYPE_CHECKING")
+}
+
 fn extract_import(node: &Node, source: &[u8], result: &mut ParseResult) {
     // import foo, bar
     let mut cursor = node.walk();
@@ -45,6 +87,7 @@ fn extract_import(node: &Node, source: &[u8], result: &mut ParseResult) {
                     imported_names: vec![],
                     is_default: true,
                     line: node.start_position().row as i32 + 1,
+                    ..Default::default()
                 });
             }
         }
@@ -52,10 +95,10 @@ fn extract_import(node: &Node, source: &[u8], result: &mut ParseResult) {
 }
 
 fn extract_import_from(node: &Node, source: &[u8], result: &mut ParseResult) {
-    // from foo import bar, baz
-    let module_node = find_child(node, "dotted_name")
-        .or_else(|| find_child(node, "relative_import"));
-    let module_name = module_node
+    // from foo import bar, baz / from ..pkg import mod
+    // The module is kept verbatim, leading dots included, for the resolver
+    let module_name = node
+        .child_by_field_name("module_name")
         .map(|n| node_text(&n, source).to_string())
         .unwrap_or_default();
 
@@ -66,32 +109,21 @@ fn extract_import_from(node: &Node, source: &[u8], result: &mut ParseResult) {
     let mut imported_names = Vec::new();
 
     // Check for wildcard import
-    let mut cursor = node.walk();
-    for child in node.children(&mut cursor) {
-        if child.kind() == "wildcard_import" {
-            imported_names.push("*".to_string());
-        } else if child.kind() == "import_prefix" {
-            // Skip the "from" keyword handling
-        }
+    if find_child(node, "wildcard_import").is_some() {
+        imported_names.push("*".to_string());
     }
 
-    // Named imports
-    let import_list = find_children(node, "dotted_name")
-        .into_iter()
-        .skip(1) // Skip module name
-        .chain(find_children(node, "aliased_import"));
-
-    for import_node in import_list {
-        let name = if import_node.kind() == "aliased_import" {
-            find_child(&import_node, "identifier")
-                .map(|n| node_text(&n, source).to_string())
-                .unwrap_or_default()
+    // Named imports - for `bar as b` record the imported name `bar`
+    let mut cursor = node.walk();
+    for import_node in node.children_by_field_name("name", &mut cursor) {
+        let name_node = if import_node.kind() == "aliased_import" {
+            import_node.child_by_field_name("name")
         } else {
-            node_text(&import_node, source).to_string()
+            Some(import_node)
         };
 
-        if !name.is_empty() && name != module_name {
-            imported_names.push(name);
+        if let Some(name_node) = name_node {
+            imported_names.push(node_text(&name_node, source).to_string());
         }
     }
 
@@ -100,6 +132,7 @@ fn extract_import_from(node: &Node, source: &[u8], result: &mut ParseResult) {
         imported_names,
         is_default: false,
         line: node.start_position().row as i32 + 1,
+        ..Default::default()
     });
 }
 
@@ -149,7 +182,7 @@ fn extract_function(
         None
     };
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         name,
         "function",
@@ -158,7 +191,14 @@ fn extract_function(
         documentation,
         is_exported,
         parent_id,
-    ));
+    );
+
+    add_type_refs(&symbol.id, node, &["parameters", "return_type"], source, result);
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_calls(&symbol.id, &body, source, result);
+    }
+
+    result.symbols.push(symbol);
 }
 
 fn extract_class(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) {
@@ -206,11 +246,24 @@ fn extract_class(file_id: &str, node: &Node, source: &[u8], result: &mut ParseRe
         id
     };
 
-    // Extract class body methods
+    // Base classes (`metaclass=...` keyword arguments are not types we inherit from)
+    if let Some(args) = node.child_by_field_name("superclasses") {
+        let mut args_cursor = args.walk();
+        for base in args.named_children(&mut args_cursor) {
+            add_inheritance(Some(&class_id), name, &base, "extends", source, result);
+        }
+    }
+
+    // Extract class body methods; annotated attributes (`owner: User`) are type references
     if let Some(body) = find_child(node, "block") {
         let mut body_cursor = body.walk();
         for member in body.children(&mut body_cursor) {
             match member.kind() {
+                "expression_statement" => {
+                    if let Some(assignment) = find_child(&member, "assignment") {
+                        add_type_refs(&class_id, &assignment, &["type"], source, result);
+                    }
+                }
                 "function_definition" => {
                     extract_function(file_id, &member, source, result, Some(class_id.clone()));
                 }
@@ -225,6 +278,32 @@ fn extract_class(file_id: &str, node: &Node, source: &[u8], result: &mut ParseRe
     }
 }
 
+/// Module-level `NAME = value` bindings; UPPER_CASE names are treated as constants
+fn extract_assignment(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) {
+    let Some(assignment) = find_child(node, "assignment") else { return };
+    let Some(left) = assignment.child_by_field_name("left").filter(|l| l.kind() == "identifier") else {
+        return;
+    };
+
+    let name = node_text(&left, source);
+    let is_constant = name.chars().any(|c| c.is_ascii_uppercase())
+        && !name.chars().any(|c| c.is_ascii_lowercase());
+    let kind = if is_constant { "constant" } else { "variable" };
+
+    let symbol = create_symbol(
+        file_id,
+        name,
+        kind,
+        node,
+        Some(node_text(&assignment, source).lines().next().unwrap_or_default().to_string()),
+        None,
+        !name.starts_with('_'),
+        None,
+    );
+    add_type_refs(&symbol.id, &assignment, &["type"], source, result);
+    result.symbols.push(symbol);
+}
+
 #[cfg(test)]
 mod tests {
     
@@ -263,6 +342,61 @@ class User:
         assert_eq!(result.symbols[0].kind, "class");
     }
 
+    #[test]
+    fn test_extract_base_classes() {
+        let parser = Parser::new();
+        let source = r#"
+class Admin(User, mixins.Audited, Generic[T], metaclass=ABCMeta):
+    pass
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Python, source).unwrap();
+        let bases: Vec<_> = result.inheritance.iter().map(|i| i.base.as_str()).collect();
+        assert_eq!(bases, vec!["User", "Audited", "Generic"]);
+        assert!(result.inheritance.iter().all(|i| i.symbol_name == "Admin" && i.kind == "extends"));
+    }
+
+    #[test]
+    fn test_extract_module_assignments() {
+        let parser = Parser::new();
+        let source = r#"
+MAX_RETRIES = 3
+default_timeout = 30
+_cache = {}
+a, b = 1, 2
+
+def run():
+    local = 1
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Python, source).unwrap();
+        let kind = |name: &str| result.symbols.iter().find(|s| s.name == name).map(|s| s.kind.as_str());
+        assert_eq!(kind("MAX_RETRIES"), Some("constant"));
+        assert_eq!(kind("default_timeout"), Some("variable"));
+        assert!(!result.symbols.iter().find(|s| s.name == "_cache").unwrap().is_exported);
+        assert_eq!(kind("local"), None);
+        assert_eq!(result.symbols.len(), 4);
+    }
+
+    #[test]
+    fn test_extract_calls() {
+        let parser = Parser::new();
+        let source = r#"
+class User:
+    def greet(self):
+        return self.format(helper())
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Python, source).unwrap();
+        let greet = result.symbols.iter().find(|s| s.name == "greet").unwrap();
+        assert_eq!(result.calls.len(), 2);
+        assert!(result.calls.iter().all(|c| c.caller_id == greet.id));
+
+        let format = result.calls.iter().find(|c| c.callee == "format").unwrap();
+        assert_eq!(format.qualifier.as_deref(), Some("self"));
+        assert!(result.calls.iter().any(|c| c.callee == "helper" && c.qualifier.is_none()));
+    }
+
     #[test]
     fn test_extract_imports() {
         let parser = Parser::new();
@@ -275,4 +409,74 @@ from .utils import helper
         let result = parser.parse_file("test", SupportedLanguage::Python, source).unwrap();
         assert!(result.imports.len() >= 2);
     }
+
+    #[test]
+    fn test_extract_type_refs() {
+        let parser = Parser::new();
+        let source = r#"
+class Order:
+    customer: models.Customer
+    lines: list[OrderLine]
+
+def place(order: "Order", repo: Optional[Repository] = None) -> Receipt:
+    pass
+"#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Python, source).unwrap();
+        let refs: Vec<_> = result.type_refs.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(refs, vec!["Customer", "list", "OrderLine", "Order", "Optional", "Repository", "Receipt"]);
+    }
+
+    #[test]
+    fn test_extract_guarded_imports() {
+        let parser = Parser::new();
+        let source = r#"
+from typing import TYPE_CHECKING
+
+if TYPE_CHECKING:
+    from .models import User
+else:
+    User = None
+
+try:
+    import ujson as json
+except ImportError:
+    import json
+
+def load():
+    import pickle
+"#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Python, source).unwrap();
+        let imports: Vec<_> = result
+            .imports
+            .iter()
+            .map(|i| (i.source.as_str(), i.usage.as_str()))
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("typing", "runtime"),
+                (".models", "type-only"),
+                ("ujson", "conditional"),
+                ("json", "conditional"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_relative_imports() {
+        let parser = Parser::new();
+        let source = r#"
+from .utils import helper as h, other
+from .. import models
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Python, source).unwrap();
+        assert_eq!(result.imports.len(), 2);
+        assert_eq!(result.imports[0].source, ".utils");
+        assert_eq!(result.imports[0].imported_names, vec!["helper", "other"]);
+        assert_eq!(result.imports[1].source, "..");
+        assert_eq!(result.imports[1].imported_names, vec!["models"]);
+    }
 }
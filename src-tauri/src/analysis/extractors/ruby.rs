@@ -0,0 +1,317 @@
+use tree_sitter::Node;
+
+use super::{add_inheritance, create_symbol, extract_calls, node_text, type_name};
+use crate::analysis::parser::{ImportInfo, ParseResult};
+
+/// Methods that load another file
+const REQUIRE_METHODS: &[&str] = &["require", "require_relative", "load"];
+
+/// Methods that mix a module into a class or module
+const MIXIN_METHODS: &[&str] = &["include", "extend", "prepend"];
+
+/// Extract symbols and relationships from Ruby AST
+pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResult) {
+    extract_requires(root, source, result);
+    extract_body(file_id, root, source, result, None);
+}
+
+/// `require 'a/b'` loads `a/b.rb` from the load path and `require_relative
+/// 'a/b'` the file next to this one, recorded as `./a/b`. Requires may appear
+/// anywhere, often inside conditionals or `begin`/`rescue`.
+fn extract_requires(node: &Node, source: &[u8], result: &mut ParseResult) {
+    let mut cursor = node.walk();
+
+    for child in node.named_children(&mut cursor) {
+        if child.kind() == "call" && child.child_by_field_name("receiver").is_none() {
+            let method = child.child_by_field_name("method").map(|m| node_text(&m, source));
+            if let Some(method) = method.filter(|m| REQUIRE_METHODS.contains(m)) {
+                if let Some(path) = child.child_by_field_name("arguments").and_then(|a| string_argument(&a, source)) {
+                    let path = if method == "require_relative" && !path.starts_with('.') {
+                        format!("./{}", path)
+                    } else {
+                        path.to_string()
+                    };
+                    result.imports.push(ImportInfo {
+                        source: path,
+                        line: child.start_position().row as i32 + 1,
+                        ..Default::default()
+                    });
+                }
+                continue;
+            }
+        }
+        extract_requires(&child, source, result);
+    }
+}
+
+/// Classes, modules, methods and constants in a program or a class/module
+/// body. `parent` is the enclosing class or module.
+fn extract_body(file_id: &str, body: &Node, source: &[u8], result: &mut ParseResult, parent: Option<&Parent>) {
+    // `private` and `protected` on their own line apply to the methods after them
+    let mut is_public = true;
+    let mut cursor = body.walk();
+
+    for child in body.named_children(&mut cursor) {
+        match child.kind() {
+            "class" | "module" => extract_type(file_id, &child, source, result, parent),
+            "method" | "singleton_method" => extract_method(file_id, &child, source, result, parent, is_public),
+            // `class << self` holds class methods
+            "singleton_class" => {
+                if let Some(inner) = child.child_by_field_name("body") {
+                    extract_body(file_id, &inner, source, result, parent);
+                }
+            }
+            "identifier" if parent.is_some() => match node_text(&child, source) {
+                "private" | "protected" => is_public = false,
+                "public" => is_public = true,
+                _ => {}
+            },
+            "assignment" => extract_constant(file_id, &child, source, result, parent),
+            "call" => extract_class_macro(file_id, &child, source, result, parent),
+            _ => {}
+        }
+    }
+}
+
+fn extract_type(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, parent: Option<&Parent>) {
+    let Some(name) = node.child_by_field_name("name") else { return };
+    // `class Billing::Invoice` declares `Invoice` inside `Billing`
+    let Some(name) = type_name(&name, source) else { return };
+    let kind = if node.kind() == "module" { "module" } else { "class" };
+
+    let symbol = create_symbol(
+        file_id,
+        name,
+        kind,
+        node,
+        Some(signature(node, source)),
+        None,
+        true,
+        parent.map(|p| p.id.clone()),
+    );
+
+    if let Some(superclass) = node.child_by_field_name("superclass").and_then(|s| s.named_child(0)) {
+        add_inheritance(Some(&symbol.id), name, &superclass, "extends", source, result);
+    }
+
+    let this = Parent {
+        id: symbol.id.clone(),
+        name: name.to_string(),
+    };
+    result.symbols.push(symbol);
+
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_body(file_id, &body, source, result, Some(&this));
+    }
+}
+
+/// The class or module a declaration is in
+struct Parent {
+    id: String,
+    name: String,
+}
+
+/// `def name` and `def self.name`; methods outside a class are functions
+fn extract_method(
+    file_id: &str,
+    node: &Node,
+    source: &[u8],
+    result: &mut ParseResult,
+    parent: Option<&Parent>,
+    is_public: bool,
+) {
+    let Some(name) = node.child_by_field_name("name") else { return };
+
+    let symbol = create_symbol(
+        file_id,
+        node_text(&name, source),
+        if parent.is_some() { "method" } else { "function" },
+        node,
+        Some(signature(node, source)),
+        None,
+        is_public,
+        parent.map(|p| p.id.clone()),
+    );
+
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_calls(&symbol.id, &body, source, result);
+    }
+
+    result.symbols.push(symbol);
+}
+
+/// `TAX_RATE = 0.2`
+fn extract_constant(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, parent: Option<&Parent>) {
+    let Some(left) = node.child_by_field_name("left").filter(|l| l.kind() == "constant") else { return };
+    let name = node_text(&left, source);
+
+    let symbol = create_symbol(
+        file_id,
+        name,
+        "constant",
+        node,
+        Some(name.to_string()),
+        None,
+        true,
+        parent.map(|p| p.id.clone()),
+    );
+    if let Some(right) = node.child_by_field_name("right") {
+        extract_calls(&symbol.id, &right, source, result);
+    }
+    result.symbols.push(symbol);
+}
+
+/// Calls in a class body that declare something: `include Comparable` mixes a
+/// module in, `private def helper` declares a private method
+fn extract_class_macro(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, parent: Option<&Parent>) {
+    let Some(parent) = parent else { return };
+    if node.child_by_field_name("receiver").is_some() {
+        return;
+    }
+    let Some(method) = node.child_by_field_name("method") else { return };
+    let Some(arguments) = node.child_by_field_name("arguments") else { return };
+    let method = node_text(&method, source);
+
+    let mut cursor = arguments.walk();
+    for argument in arguments.named_children(&mut cursor) {
+        match argument.kind() {
+            "constant" | "scope_resolution" if MIXIN_METHODS.contains(&method) => {
+                add_inheritance(Some(&parent.id), &parent.name, &argument, "implements", source, result);
+            }
+            "method" | "singleton_method" => {
+                let is_public = !matches!(method, "private" | "protected" | "private_class_method");
+                extract_method(file_id, &argument, source, result, Some(parent), is_public);
+            }
+            _ => {}
+        }
+    }
+}
+
+// Helper functions
+
+/// The text of a plain string argument: `require 'json'`, `require("json")`
+fn string_argument<'a>(arguments: &Node, source: &'a [u8]) -> Option<&'a str> {
+    let string = arguments.named_child(0).filter(|a| a.kind() == "string")?;
+    // Interpolated strings cannot be resolved statically
+    if string.named_child_count() != 1 {
+        return None;
+    }
+    string
+        .named_child(0)
+        .filter(|c| c.kind() == "string_content")
+        .map(|c| node_text(&c, source))
+}
+
+/// The declaration's first line: `class Invoice < Base::Record`, `def self.build(data)`
+fn signature(node: &Node, source: &[u8]) -> String {
+    let text = node_text(node, source);
+    let first_line = text.lines().next().unwrap_or_default();
+    first_line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::parser::{Parser, SupportedLanguage};
+
+    #[test]
+    fn test_extract_ruby_symbols() {
+        let parser = Parser::new();
+        let source = r#"
+require 'json'
+require_relative '../lib/helper'
+require_relative 'formatter'
+begin
+  require 'oj'
+rescue LoadError
+end
+
+module Billing
+  class Invoice < Base::Record
+    include Comparable
+    extend Forwardable
+    TAX = 0.2
+
+    def initialize(items)
+      @items = items
+      Helper.log("created")
+    end
+
+    def self.build(data)
+      new(data.map { |d| Item.new(d) })
+    end
+
+    private
+
+    def compute
+      total_for(@items)
+    end
+
+    public def total = compute
+  end
+end
+
+def top_level(x) = x * 2
+"#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Ruby, source).unwrap();
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+
+        let imports: Vec<_> = result.imports.iter().map(|i| i.source.as_str()).collect();
+        assert_eq!(imports, vec!["json", "../lib/helper", "./formatter", "oj"]);
+
+        let billing = symbol("Billing");
+        assert_eq!(billing.kind, "module");
+        let invoice = symbol("Invoice");
+        assert_eq!(invoice.kind, "class");
+        assert_eq!(invoice.parent_id.as_deref(), Some(billing.id.as_str()));
+        assert_eq!(invoice.signature.as_deref(), Some("class Invoice < Base::Record"));
+
+        let members: Vec<_> = result
+            .symbols
+            .iter()
+            .filter(|s| s.parent_id.as_deref() == Some(invoice.id.as_str()))
+            .map(|s| (s.name.as_str(), s.kind.as_str(), s.is_exported))
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                ("TAX", "constant", true),
+                ("initialize", "method", true),
+                ("build", "method", true),
+                ("compute", "method", false),
+                ("total", "method", true),
+            ]
+        );
+        assert_eq!(symbol("top_level").kind, "function");
+
+        let bases: Vec<_> = result
+            .inheritance
+            .iter()
+            .map(|i| (i.symbol_name.as_str(), i.base.as_str(), i.kind.as_str()))
+            .collect();
+        assert_eq!(
+            bases,
+            vec![
+                ("Invoice", "Record", "extends"),
+                ("Invoice", "Comparable", "implements"),
+                ("Invoice", "Forwardable", "implements"),
+            ]
+        );
+
+        let calls: Vec<_> = result
+            .calls
+            .iter()
+            .map(|c| (c.callee.as_str(), c.qualifier.as_deref()))
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                ("log", Some("Helper")),
+                ("new", None),
+                ("map", Some("data")),
+                ("Item", None),
+                ("total_for", None),
+            ]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use tree_sitter::Node;
 
-use super::{create_symbol, find_child, find_children, node_text};
-use crate::analysis::parser::{ImportInfo, ParseResult};
+use super::{add_inheritance, add_type_refs, create_symbol, extract_calls, find_child, node_text, type_name};
+use crate::analysis::parser::{ImportInfo, ImportUsage, MemberInfo, ModuleDeclInfo, ParseResult};
 
 /// Extract symbols and relationships from Rust AST
 pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResult) {
@@ -9,8 +9,17 @@ pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResu
 
     for child in root.children(&mut cursor) {
         match child.kind() {
-            "use_declaration" => extract_use(&child, source, result),
-            "function_item" => extract_function(file_id, &child, source, result, None),
+            "use_declaration" => {
+                let count = result.imports.len();
+                extract_use(&child, source, result);
+                let usage = cfg_usage(&child, source, ImportUsage::Runtime);
+                for import in &mut result.imports[count..] {
+                    import.usage = usage;
+                }
+            }
+            "function_item" => {
+                extract_function(file_id, &child, source, result, None, "function");
+            }
             "struct_item" => extract_struct(file_id, &child, source, result),
             "enum_item" => extract_enum(file_id, &child, source, result),
             "trait_item" => extract_trait(file_id, &child, source, result),
@@ -34,55 +43,89 @@ fn is_pub(node: &Node) -> bool {
 }
 
 fn extract_use(node: &Node, source: &[u8], result: &mut ParseResult) {
-    // Extract use path from argument field
-    fn extract_path(node: &Node, source: &[u8]) -> Option<String> {
+    // Flatten the use tree into (module path, imported name) pairs:
+    // `use crate::storage::{Repository, FileRecord as F}` -> ("crate::storage", "Repository"), ...
+    fn collect_leaves(node: &Node, prefix: &str, source: &[u8], leaves: &mut Vec<(String, String)>) {
+        let join = |path: &str| {
+            if prefix.is_empty() {
+                path.to_string()
+            } else {
+                format!("{}::{}", prefix, path)
+            }
+        };
+
         match node.kind() {
-            "scoped_identifier" | "use_wildcard" | "identifier" | "crate" | "self" | "super" => {
-                Some(node_text(node, source).to_string())
+            "scoped_identifier" => {
+                let path = node.child_by_field_name("path").map(|p| node_text(&p, source));
+                let name = node.child_by_field_name("name").map(|n| node_text(&n, source));
+                if let Some(name) = name {
+                    leaves.push((path.map(join).unwrap_or_else(|| prefix.to_string()), name.to_string()));
+                }
+            }
+            "identifier" | "crate" | "self" | "super" => {
+                leaves.push((prefix.to_string(), node_text(node, source).to_string()));
             }
             "use_as_clause" => {
-                find_child(node, "scoped_identifier")
-                    .or_else(|| find_child(node, "identifier"))
-                    .map(|n| node_text(&n, source).to_string())
+                // `Foo as Bar` imports `Foo`
+                if let Some(path) = node.child_by_field_name("path") {
+                    collect_leaves(&path, prefix, source, leaves);
+                }
+            }
+            "use_wildcard" => {
+                if let Some(path) = find_child(node, "scoped_identifier").or_else(|| find_child(node, "identifier")) {
+                    leaves.push((join(node_text(&path, source)), "*".to_string()));
+                }
             }
             "use_list" => {
-                // Handle { foo, bar }
                 let mut cursor = node.walk();
-                let parts: Vec<_> = node.children(&mut cursor)
-                    .filter_map(|c| extract_path(&c, source))
-                    .collect();
-                if parts.is_empty() {
-                    None
-                } else {
-                    Some(format!("{{{}}}", parts.join(", ")))
+                for child in node.named_children(&mut cursor) {
+                    collect_leaves(&child, prefix, source, leaves);
                 }
             }
             "scoped_use_list" => {
-                // Handle foo::{bar, baz}
-                let path = find_child(node, "scoped_identifier")
-                    .or_else(|| find_child(node, "identifier"))
-                    .map(|n| node_text(&n, source).to_string())
-                    .unwrap_or_default();
-                let list = find_child(node, "use_list")
-                    .and_then(|l| extract_path(&l, source))
-                    .unwrap_or_default();
-                Some(format!("{}::{}", path, list))
+                let path = node
+                    .child_by_field_name("path")
+                    .map(|p| join(node_text(&p, source)))
+                    .unwrap_or_else(|| prefix.to_string());
+                if let Some(list) = node.child_by_field_name("list") {
+                    collect_leaves(&list, &path, source, leaves);
+                }
             }
-            _ => None,
+            _ => {}
         }
     }
 
     // tree-sitter-rust uses "argument" field for the use path
+    let mut leaves = Vec::new();
     if let Some(arg_node) = node.child_by_field_name("argument") {
-        if let Some(path) = extract_path(&arg_node, source) {
-            result.imports.push(ImportInfo {
-                source: path,
-                imported_names: vec![],
-                is_default: false,
-                line: node.start_position().row as i32 + 1,
-            });
+        collect_leaves(&arg_node, "", source, &mut leaves);
+    }
+
+    // One import per module path, carrying every name imported from it
+    let mut grouped: Vec<(String, Vec<String>)> = Vec::new();
+    for (path, name) in leaves {
+        match grouped.iter_mut().find(|(p, _)| *p == path) {
+            Some((_, names)) => names.push(name),
+            None => grouped.push((path, vec![name])),
         }
     }
+
+    for (path, names) in grouped {
+        // `use serde;` names a crate or module with nothing imported from it
+        let (import_source, imported_names) = if path.is_empty() {
+            (names.join("::"), vec![])
+        } else {
+            (path, names)
+        };
+
+        result.imports.push(ImportInfo {
+            source: import_source,
+            imported_names,
+            is_default: false,
+            line: node.start_position().row as i32 + 1,
+            ..Default::default()
+        });
+    }
 }
 
 fn extract_function(
@@ -91,7 +134,8 @@ fn extract_function(
     source: &[u8],
     result: &mut ParseResult,
     parent_id: Option<String>,
-) {
+    kind: &str,
+) -> String {
     let name_node = find_child(node, "identifier");
     let name = name_node
         .map(|n| node_text(&n, source))
@@ -112,16 +156,25 @@ fn extract_function(
 
     let signature = format!("{}fn {}{}{}", async_keyword, name, params, return_type.unwrap_or_default());
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         name,
-        "function",
+        kind,
         node,
         Some(signature),
         None,
         is_exported,
         parent_id,
-    ));
+    );
+
+    add_type_refs(&symbol.id, node, &["type_parameters", "parameters", "return_type"], source, result);
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_calls(&symbol.id, &body, source, result);
+    }
+
+    let id = symbol.id.clone();
+    result.symbols.push(symbol);
+    id
 }
 
 fn extract_struct(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) {
@@ -144,6 +197,7 @@ fn extract_struct(file_id: &str, node: &Node, source: &[u8], result: &mut ParseR
             is_exported,
             None,
         );
+        add_type_refs(&symbol.id, node, &["type_parameters", "body"], source, result);
         let id = symbol.id.clone();
         result.symbols.push(symbol);
         id
@@ -155,7 +209,7 @@ fn extract_struct(file_id: &str, node: &Node, source: &[u8], result: &mut ParseR
         for field in fields.children(&mut cursor) {
             if field.kind() == "field_declaration" {
                 if let Some(field_name) = find_child(&field, "field_identifier") {
-                    result.symbols.push(create_symbol(
+                    let symbol = create_symbol(
                         file_id,
                         node_text(&field_name, source),
                         "field",
@@ -164,7 +218,9 @@ fn extract_struct(file_id: &str, node: &Node, source: &[u8], result: &mut ParseR
                         None,
                         is_pub(&field),
                         Some(struct_id.clone()),
-                    ));
+                    );
+                    add_type_refs(&symbol.id, &field, &["type"], source, result);
+                    result.symbols.push(symbol);
                 }
             }
         }
@@ -180,7 +236,7 @@ fn extract_enum(file_id: &str, node: &Node, source: &[u8], result: &mut ParseRes
     let is_exported = is_pub(node);
     let signature = format!("enum {}", name);
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         name,
         "enum",
@@ -189,7 +245,10 @@ fn extract_enum(file_id: &str, node: &Node, source: &[u8], result: &mut ParseRes
         None,
         is_exported,
         None,
-    ));
+    );
+    // Types carried by variants: `Loaded(Project)`, `Failed { error: NexusError }`
+    add_type_refs(&symbol.id, node, &["type_parameters", "body"], source, result);
+    result.symbols.push(symbol);
 }
 
 fn extract_trait(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) {
@@ -201,7 +260,7 @@ fn extract_trait(file_id: &str, node: &Node, source: &[u8], result: &mut ParseRe
     let is_exported = is_pub(node);
     let signature = format!("trait {}", name);
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         name,
         "trait",
@@ -210,30 +269,54 @@ fn extract_trait(file_id: &str, node: &Node, source: &[u8], result: &mut ParseRe
         None,
         is_exported,
         None,
-    ));
+    );
+
+    add_type_refs(&symbol.id, node, &["type_parameters"], source, result);
+
+    // Supertraits: `trait A: B + fmt::Debug`
+    if let Some(bounds) = node.child_by_field_name("bounds") {
+        let mut cursor = bounds.walk();
+        for bound in bounds.named_children(&mut cursor) {
+            add_inheritance(Some(&symbol.id), name, &bound, "extends", source, result);
+        }
+    }
+
+    let trait_id = symbol.id.clone();
+    result.symbols.push(symbol);
+
+    // Required and provided methods
+    if let Some(body) = node.child_by_field_name("body") {
+        let mut cursor = body.walk();
+        for item in body.children(&mut cursor) {
+            if matches!(item.kind(), "function_item" | "function_signature_item") {
+                extract_function(file_id, &item, source, result, Some(trait_id.clone()), "method");
+            }
+        }
+    }
 }
 
 fn extract_impl(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) {
     // Get the type being implemented
-    let type_node = find_child(node, "type_identifier")
-        .or_else(|| find_child(node, "generic_type"));
+    let type_node = node.child_by_field_name("type");
     let type_name = type_node
-        .map(|n| node_text(&n, source))
+        .and_then(|n| type_name(&n, source))
         .unwrap_or("anonymous");
 
-    // Check if implementing a trait (may be used in future for relationship tracking)
-    let _trait_name = find_children(node, "type_identifier")
-        .get(0)
-        .map(|n| node_text(n, source).to_string());
+    // `impl Trait for Type`: the type lives elsewhere, so it is resolved by name
+    if let Some(trait_node) = node.child_by_field_name("trait") {
+        add_inheritance(None, type_name, &trait_node, "implements", source, result);
+    }
 
-    // Extract methods from declaration_list
+    // Extract methods from declaration_list; the type may be declared in another file
     if let Some(decl_list) = find_child(node, "declaration_list") {
         let mut cursor = decl_list.walk();
         for item in decl_list.children(&mut cursor) {
             if item.kind() == "function_item" {
-                // Create parent_id from impl type
-                let parent_id = format!("{}_{}", file_id, type_name);
-                extract_function(file_id, &item, source, result, Some(parent_id));
+                let symbol_id = extract_function(file_id, &item, source, result, None, "method");
+                result.members.push(MemberInfo {
+                    symbol_id,
+                    owner: type_name.to_string(),
+                });
             }
         }
     }
@@ -248,7 +331,7 @@ fn extract_const_static(file_id: &str, node: &Node, source: &[u8], result: &mut
     let is_exported = is_pub(node);
     let kind = if node.kind() == "const_item" { "constant" } else { "variable" };
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         name,
         kind,
@@ -257,7 +340,9 @@ fn extract_const_static(file_id: &str, node: &Node, source: &[u8], result: &mut
         None,
         is_exported,
         None,
-    ));
+    );
+    add_type_refs(&symbol.id, node, &["type"], source, result);
+    result.symbols.push(symbol);
 }
 
 fn extract_type_alias(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) {
@@ -268,7 +353,7 @@ fn extract_type_alias(file_id: &str, node: &Node, source: &[u8], result: &mut Pa
 
     let is_exported = is_pub(node);
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         name,
         "type",
@@ -277,7 +362,9 @@ fn extract_type_alias(file_id: &str, node: &Node, source: &[u8], result: &mut Pa
         None,
         is_exported,
         None,
-    ));
+    );
+    add_type_refs(&symbol.id, node, &["type_parameters", "type"], source, result);
+    result.symbols.push(symbol);
 }
 
 fn extract_mod(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) {
@@ -298,6 +385,136 @@ fn extract_mod(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResu
         is_exported,
         None,
     ));
+
+    extract_mod_declarations(node, &[], source, result, ImportUsage::Runtime);
+}
+
+/// Record `mod foo;` declarations that load other files, including those
+/// nested in inline modules (`mod net { mod socket; }`), and the `use`
+/// declarations of inline modules (`mod tests { use tempfile::tempdir; }`)
+fn extract_mod_declarations(
+    node: &Node,
+    inline_path: &[String],
+    source: &[u8],
+    result: &mut ParseResult,
+    usage: ImportUsage,
+) {
+    let name = match node.child_by_field_name("name") {
+        Some(name) => node_text(&name, source).to_string(),
+        None => return,
+    };
+    let usage = cfg_usage(node, source, usage);
+
+    match node.child_by_field_name("body") {
+        Some(body) => {
+            let mut path = inline_path.to_vec();
+            path.push(name);
+
+            let mut cursor = body.walk();
+            for child in body.children(&mut cursor) {
+                match child.kind() {
+                    "mod_item" => extract_mod_declarations(&child, &path, source, result, usage),
+                    "use_declaration" => {
+                        let mut scoped = ParseResult::default();
+                        extract_use(&child, source, &mut scoped);
+                        for mut import in scoped.imports {
+                            let Some(rebased) = rebase_use_path(&import.source, path.len()) else { continue };
+                            import.source = rebased;
+                            import.usage = cfg_usage(&child, source, usage);
+                            result.imports.push(import);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        None => {
+            let line = node.start_position().row as i32 + 1;
+            let mut path = inline_path.to_vec();
+            path.push(name.clone());
+
+            result.module_decls.push(ModuleDeclInfo {
+                path,
+                path_attribute: path_attribute(node, source),
+                line,
+            });
+
+            // The declaring file depends on the module file: `self::foo`
+            let parent = std::iter::once("self")
+                .chain(inline_path.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join("::");
+            result.imports.push(ImportInfo {
+                source: parent,
+                imported_names: vec![name],
+                is_default: false,
+                usage,
+                line,
+                ..Default::default()
+            });
+        }
+    }
+}
+
+/// A `use` path written inside an inline module `depth` levels deep, made
+/// relative to the file: `super::x` in `mod tests` is `self::x`. None for
+/// paths into the inline module itself or to the file as a whole.
+fn rebase_use_path(path: &str, depth: usize) -> Option<String> {
+    let segments: Vec<&str> = path.split("::").collect();
+    let supers = segments.iter().take_while(|s| **s == "super").count();
+    match segments[0] {
+        "self" => None,
+        _ if supers == 0 => Some(path.to_string()),
+        _ if supers > depth => Some(segments[depth..].join("::")),
+        _ if supers == segments.len() => None,
+        _ => Some(std::iter::once("self").chain(segments[supers..].iter().copied()).collect::<Vec<_>>().join("::")),
+    }
+}
+
+/// The usage of an item under its `#[cfg(...)]` attributes, within code
+/// already compiled under `usage`: `cfg(test)` and `cfg(all(test, ...))` are
+/// test-only, any other condition is conditional
+fn cfg_usage(node: &Node, source: &[u8], usage: ImportUsage) -> ImportUsage {
+    let mut usage = usage;
+    let mut sibling = node.prev_sibling();
+    while let Some(attribute_item) = sibling.filter(|s| s.kind() == "attribute_item") {
+        let attribute = find_child(&attribute_item, "attribute");
+        let is_cfg = attribute
+            .as_ref()
+            .and_then(|a| find_child(a, "identifier"))
+            .is_some_and(|ident| node_text(&ident, source) == "cfg");
+        let condition = attribute.and_then(|a| a.child_by_field_name("arguments"));
+        if let (true, Some(condition)) = (is_cfg, condition) {
+            let condition: String = node_text(&condition, source).split_whitespace().collect();
+            let test_only = condition == "(test)"
+                || condition.strip_prefix("(all(").is_some_and(|c| c.split([',', ')']).any(|t| t == "test"));
+            usage = match usage {
+                ImportUsage::TestOnly => ImportUsage::TestOnly,
+                _ if test_only => ImportUsage::TestOnly,
+                _ => ImportUsage::Conditional,
+            };
+        }
+        sibling = attribute_item.prev_sibling();
+    }
+    usage
+}
+
+/// The value of a `#[path = "..."]` attribute preceding an item
+fn path_attribute(node: &Node, source: &[u8]) -> Option<String> {
+    let mut sibling = node.prev_sibling();
+    while let Some(attribute_item) = sibling.filter(|s| s.kind() == "attribute_item") {
+        if let Some(attribute) = find_child(&attribute_item, "attribute") {
+            let is_path = find_child(&attribute, "identifier")
+                .is_some_and(|ident| node_text(&ident, source) == "path");
+            let value = attribute.child_by_field_name("value");
+            let value = value.as_ref().and_then(|v| find_child(v, "string_content"));
+            if let (true, Some(value)) = (is_path, value) {
+                return Some(node_text(&value, source).to_string());
+            }
+        }
+        sibling = attribute_item.prev_sibling();
+    }
+    None
 }
 
 #[cfg(test)]
@@ -337,6 +554,80 @@ pub struct User {
         assert!(result.symbols.iter().any(|s| s.name == "User" && s.kind == "struct"));
     }
 
+    #[test]
+    fn test_extract_members() {
+        let parser = Parser::new();
+        let source = r#"
+pub trait Store {
+    fn get(&self) -> u32;
+    fn len(&self) -> usize { 0 }
+}
+
+impl<T> Cache<T> {
+    pub fn new() -> Self { todo!() }
+}
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Rust, source).unwrap();
+        let store = result.symbols.iter().find(|s| s.name == "Store").unwrap();
+        let trait_methods: Vec<_> = result
+            .symbols
+            .iter()
+            .filter(|s| s.parent_id.as_ref() == Some(&store.id))
+            .map(|s| (s.name.as_str(), s.kind.as_str()))
+            .collect();
+        assert_eq!(trait_methods, vec![("get", "method"), ("len", "method")]);
+
+        // Impl methods are linked to their type after parsing
+        let new = result.symbols.iter().find(|s| s.name == "new").unwrap();
+        assert_eq!(new.parent_id, None);
+        assert_eq!(result.members.len(), 1);
+        assert_eq!(result.members[0].symbol_id, new.id);
+        assert_eq!(result.members[0].owner, "Cache");
+    }
+
+    #[test]
+    fn test_extract_trait_impls() {
+        let parser = Parser::new();
+        let source = r#"
+pub trait Shape: Sized + fmt::Debug {}
+impl<T> fmt::Display for Wrapper<T> {}
+impl Wrapper<u8> {}
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Rust, source).unwrap();
+        let bases: Vec<_> = result
+            .inheritance
+            .iter()
+            .map(|i| (i.symbol_name.as_str(), i.base.as_str(), i.kind.as_str(), i.symbol_id.is_some()))
+            .collect();
+        assert_eq!(bases, vec![
+            ("Shape", "Sized", "extends", true),
+            ("Shape", "Debug", "extends", true),
+            ("Wrapper", "Display", "implements", false),
+        ]);
+    }
+
+    #[test]
+    fn test_extract_calls() {
+        let parser = Parser::new();
+        let source = r#"
+fn run() {
+    let engine = Engine::new();
+    engine.start();
+    helper::<u32>();
+    println!("done");
+}
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Rust, source).unwrap();
+        let callees: Vec<_> = result.calls.iter().map(|c| c.callee.as_str()).collect();
+        assert_eq!(callees, vec!["new", "start", "helper"]);
+
+        let new = result.calls.iter().find(|c| c.callee == "new").unwrap();
+        assert_eq!(new.qualifier.as_deref(), Some("Engine"));
+    }
+
     #[test]
     fn test_extract_use() {
         let parser = Parser::new();
@@ -348,4 +639,84 @@ use serde::{Serialize, Deserialize};
         let result = parser.parse_file("test", SupportedLanguage::Rust, source).unwrap();
         assert!(result.imports.len() >= 2);
     }
+
+    #[test]
+    fn test_extract_use_tree() {
+        let parser = Parser::new();
+        let source = r#"
+use crate::{storage::Repository, analysis::{self, AnalysisEngine as Engine}};
+use super::super::parser::*;
+use serde;
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Rust, source).unwrap();
+        let imports: Vec<_> = result
+            .imports
+            .iter()
+            .map(|i| (i.source.as_str(), i.imported_names.join(",")))
+            .collect();
+        assert_eq!(imports, vec![
+            ("crate::storage", "Repository".to_string()),
+            ("crate::analysis", "self,AnalysisEngine".to_string()),
+            ("super::super::parser", "*".to_string()),
+            ("serde", String::new()),
+        ]);
+    }
+
+    #[test]
+    fn test_extract_mod_declarations() {
+        let parser = Parser::new();
+        let source = r#"
+#[cfg(unix)]
+#[path = "sys/unix.rs"]
+mod platform;
+pub mod storage;
+mod net {
+    mod socket;
+}
+mod tests {}
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Rust, source).unwrap();
+        let decls: Vec<_> = result.module_decls.iter().map(|d| (d.path.join("::"), d.path_attribute.clone())).collect();
+        assert_eq!(decls, vec![
+            ("platform".to_string(), Some("sys/unix.rs".to_string())),
+            ("storage".to_string(), None),
+            ("net::socket".to_string(), None),
+        ]);
+        assert!(result.imports.iter().any(|i| i.source == "self::net" && i.imported_names == vec!["socket"]));
+    }
+
+    #[test]
+    fn test_extract_cfg_gated_uses() {
+        let parser = Parser::new();
+        let source = r#"
+use crate::storage::Repository;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+#[cfg(all(test, unix))]
+mod fixtures;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::parser::Parser;
+    use tempfile::tempdir;
+}
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Rust, source).unwrap();
+        let imports: Vec<_> = result
+            .imports
+            .iter()
+            .map(|i| (i.source.as_str(), i.usage.as_str()))
+            .collect();
+        assert_eq!(imports, vec![
+            ("crate::storage", "runtime"),
+            ("std::os::unix::fs", "conditional"),
+            ("self", "test-only"),
+            ("self::parser", "test-only"),
+            ("tempfile", "test-only"),
+        ]);
+    }
 }
@@ -0,0 +1,261 @@
+use std::ops::Range;
+
+use crate::analysis::parser::{ComponentUsageInfo, ImportInfo, ParseResult};
+
+/// The script of a Vue or Svelte single-file component
+#[derive(Debug)]
+pub struct Script {
+    /// The file with everything outside `<script>` blocks blanked out, so that
+    /// line and column numbers match the component file
+    pub source: String,
+    /// Any block has `lang="ts"`
+    pub is_typescript: bool,
+}
+
+/// A `<script>` or `<style>` element
+struct Block<'a> {
+    name: &'a str,
+    /// Attributes of the opening tag
+    attributes: &'a str,
+    /// The whole element, tags included
+    element: Range<usize>,
+    content: Range<usize>,
+}
+
+/// Split out the `<script>` and `<script setup>` (Vue) or `<script
+/// context="module">` (Svelte) blocks of a component, to be parsed together as
+/// one module
+pub fn script(source: &str) -> Script {
+    let blocks = blocks(source);
+    let scripts: Vec<&Block> = blocks.iter().filter(|b| b.name == "script").collect();
+
+    let mut masked = blank(source.as_bytes());
+    for block in &scripts {
+        masked[block.content.clone()].copy_from_slice(&source.as_bytes()[block.content.clone()]);
+    }
+
+    let is_typescript = scripts
+        .iter()
+        .any(|b| matches!(attribute(b.attributes, "lang"), Some("ts" | "tsx" | "typescript")));
+
+    Script {
+        // Only ASCII was blanked, and whole blocks copied back
+        source: String::from_utf8(masked).unwrap_or_default(),
+        is_typescript,
+    }
+}
+
+/// Record the components the template renders, and `<script src="...">` files
+pub fn extract_template(source: &str, result: &mut ParseResult) {
+    let blocks = blocks(source);
+
+    for block in blocks.iter().filter(|b| b.name == "script") {
+        if let Some(src) = attribute(block.attributes, "src") {
+            result.imports.push(ImportInfo {
+                source: src.to_string(),
+                line: line(source, block.element.start),
+                ..Default::default()
+            });
+        }
+    }
+
+    let bytes = source.as_bytes();
+    let mut i = 0;
+    while let Some(offset) = source[i..].find('<') {
+        let start = i + offset;
+        if let Some(block) = blocks.iter().find(|b| b.element.start == start) {
+            i = block.element.end;
+            continue;
+        }
+        if source[start..].starts_with("<!--") {
+            i = source[start..].find("-->").map_or(source.len(), |end| start + end + 3);
+            continue;
+        }
+
+        let name_end = bytes[start + 1..]
+            .iter()
+            .position(|&c| !(c.is_ascii_alphanumeric() || matches!(c, b'-' | b'_' | b'.' | b':')))
+            .map_or(source.len(), |len| start + 1 + len);
+        if let Some(name) = component_name(&source[start + 1..name_end]) {
+            if !result.components.iter().any(|c| c.name == name) {
+                result.components.push(ComponentUsageInfo {
+                    name,
+                    line: line(source, start),
+                });
+            }
+        }
+        i = name_end.max(start + 1);
+    }
+}
+
+/// The component a tag renders: `UserCard`, `user-card` (Vue) or `Icons.Close`
+/// (a namespace import). Lowercase tags are HTML elements.
+fn component_name(tag: &str) -> Option<String> {
+    if tag.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return tag.split('.').next().map(str::to_string);
+    }
+    if tag.contains('-') && tag.starts_with(|c: char| c.is_ascii_lowercase()) && !tag.contains(':') {
+        return Some(
+            tag.split('-')
+                .map(|part| {
+                    let mut chars = part.chars();
+                    chars.next().map_or(String::new(), |first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                })
+                .collect(),
+        );
+    }
+    None
+}
+
+/// The top-level `<script>` and `<style>` elements, skipping HTML comments
+fn blocks(source: &str) -> Vec<Block<'_>> {
+    let mut blocks = Vec::new();
+    let lower = source.to_ascii_lowercase();
+    let mut i = 0;
+
+    while let Some(offset) = source[i..].find('<') {
+        let start = i + offset;
+        if source[start..].starts_with("<!--") {
+            i = source[start..].find("-->").map_or(source.len(), |end| start + end + 3);
+            continue;
+        }
+
+        let name = ["script", "style"].into_iter().find(|name| {
+            lower[start + 1..].starts_with(name)
+                && lower[start + 1 + name.len()..].starts_with(|c: char| c == '>' || c.is_ascii_whitespace())
+        });
+        let Some(name) = name else {
+            i = start + 1;
+            continue;
+        };
+
+        let attributes_start = start + 1 + name.len();
+        let Some(open_end) = source[attributes_start..].find('>').map(|end| attributes_start + end) else { break };
+        let closing = format!("</{}", name);
+        let content_end = lower[open_end..].find(&closing).map_or(source.len(), |end| open_end + end);
+        let element_end = source[content_end..].find('>').map_or(source.len(), |end| content_end + end + 1);
+
+        blocks.push(Block {
+            name,
+            attributes: source[attributes_start..open_end].trim_end_matches('/'),
+            element: start..element_end,
+            content: open_end + 1..content_end,
+        });
+        i = element_end;
+    }
+
+    blocks
+}
+
+/// The value of `name="value"`, `name='value'` or `name=value` in a tag
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+    while let Some(offset) = rest.find(name) {
+        let before = rest[..offset].chars().next_back();
+        let after = rest[offset + name.len()..].trim_start();
+        rest = &rest[offset + name.len()..];
+        if before.is_some_and(|c| !c.is_ascii_whitespace()) {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=').map(str::trim_start) else { continue };
+        return match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next(),
+            _ => value.split(|c: char| c.is_ascii_whitespace()).next(),
+        };
+    }
+    None
+}
+
+/// Spaces in place of everything but line breaks
+fn blank(source: &[u8]) -> Vec<u8> {
+    source.iter().map(|&c| if c == b'\n' || c == b'\r' { c } else { b' ' }).collect()
+}
+
+fn line(source: &str, offset: usize) -> i32 {
+    source[..offset].matches('\n').count() as i32 + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::parser::{Parser, SupportedLanguage};
+
+    #[test]
+    fn test_extract_vue_component() {
+        let parser = Parser::new();
+        let source = r#"<template>
+  <!-- <LegacyCard /> -->
+  <div class="list">
+    <UserCard v-for="u in users" :user="u" />
+    <base-button @click="load">Load</base-button>
+    <Icons.Refresh />
+  </div>
+</template>
+
+<script lang="ts">
+export default { name: 'UserList' }
+</script>
+
+<script setup lang="ts">
+import UserCard from './UserCard.vue'
+import BaseButton from '@/components/BaseButton.vue'
+import * as Icons from './icons'
+
+const users = ref<User[]>([])
+function load() {
+  users.value = fetchUsers()
+}
+</script>
+
+<style scoped>
+.list > div { color: red; }
+</style>
+"#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Vue, source).unwrap();
+
+        let imports: Vec<_> = result.imports.iter().map(|i| (i.source.as_str(), i.line)).collect();
+        assert_eq!(
+            imports,
+            vec![("./UserCard.vue", 15), ("@/components/BaseButton.vue", 16), ("./icons", 17)]
+        );
+
+        // Line numbers are those of the component file
+        let load = result.symbols.iter().find(|s| s.name == "load").unwrap();
+        assert_eq!((load.kind.as_str(), load.line), ("function", 20));
+        assert!(result.calls.iter().any(|c| c.callee == "fetchUsers" && c.line == 21));
+
+        let components: Vec<_> = result.components.iter().map(|c| (c.name.as_str(), c.line)).collect();
+        assert_eq!(components, vec![("UserCard", 4), ("BaseButton", 5), ("Icons", 6)]);
+    }
+
+    #[test]
+    fn test_extract_svelte_component() {
+        let parser = Parser::new();
+        let source = r#"<script context="module">
+  export const prerender = true;
+</script>
+
+<script>
+  import Header from '$lib/Header.svelte';
+  export let title;
+</script>
+
+<Header {title} />
+<svelte:head><title>{title}</title></svelte:head>
+<script src="./analytics.js"></script>
+"#;
+
+        let script = script(source);
+        assert!(!script.is_typescript);
+        assert_eq!(script.source.lines().count(), source.lines().count());
+
+        let result = parser.parse_file("test", SupportedLanguage::Svelte, source).unwrap();
+        let imports: Vec<_> = result.imports.iter().map(|i| i.source.as_str()).collect();
+        assert_eq!(imports, vec!["$lib/Header.svelte", "./analytics.js"]);
+        assert!(result.symbols.iter().any(|s| s.name == "prerender" && s.line == 2));
+
+        let components: Vec<_> = result.components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(components, vec!["Header"]);
+    }
+}
@@ -0,0 +1,189 @@
+use tree_sitter::Node;
+
+use super::{create_symbol, node_text};
+use crate::analysis::parser::{CallInfo, ImportInfo, ParseResult};
+
+/// Commands that load another script into the current shell
+const SOURCE_COMMANDS: &[&str] = &["source", "."];
+
+/// Commands that run the script given as their first argument
+const SCRIPT_RUNNERS: &[&str] = &["bash", "sh", "zsh", "dash", "ksh", "exec"];
+
+/// Extract function definitions, sourced and invoked scripts from a shell script
+pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResult) {
+    extract_node(file_id, root, source, result, None);
+}
+
+fn extract_node(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult, caller_id: Option<&str>) {
+    let mut cursor = node.walk();
+
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "function_definition" => extract_function(file_id, &child, source, result),
+            "command" => {
+                extract_command(&child, source, result, caller_id);
+                // Command substitutions in arguments: `out=$(helper)`
+                extract_node(file_id, &child, source, result, caller_id);
+            }
+            _ => extract_node(file_id, &child, source, result, caller_id),
+        }
+    }
+}
+
+fn extract_function(file_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) {
+    let Some(name) = node.child_by_field_name("name") else { return };
+    let name = node_text(&name, source);
+
+    let symbol = create_symbol(file_id, name, "function", node, Some(signature(node, source)), None, true, None);
+
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_node(file_id, &body, source, result, Some(&symbol.id));
+    }
+
+    result.symbols.push(symbol);
+}
+
+/// `source lib.sh` and `. lib.sh` load a script, `./build.sh` and `bash
+/// build.sh` run one: both are imports. Any other command inside a function
+/// may call a function of this or a sourced script.
+fn extract_command(node: &Node, source: &[u8], result: &mut ParseResult, caller_id: Option<&str>) {
+    let Some(name) = node.child_by_field_name("name") else { return };
+    let name = node_text(&name, source);
+    let mut cursor = node.walk();
+    let arguments: Vec<Node> = node.children_by_field_name("argument", &mut cursor).collect();
+
+    let script = if SOURCE_COMMANDS.contains(&name) {
+        arguments.first().and_then(|a| script_path(a, source))
+    } else if SCRIPT_RUNNERS.contains(&name) {
+        // `bash -c '...'` runs a command string, not a script
+        if arguments.iter().any(|a| node_text(a, source) == "-c") {
+            return;
+        }
+        arguments
+            .iter()
+            .find(|a| !node_text(a, source).starts_with('-'))
+            .and_then(|a| script_path(a, source))
+            .filter(|path| path.contains('/') || path.ends_with(".sh"))
+    } else if name.contains('/') && !name.contains('$') {
+        Some(name.to_string())
+    } else {
+        if let Some(caller_id) = caller_id {
+            result.calls.push(CallInfo {
+                caller_id: caller_id.to_string(),
+                callee: name.to_string(),
+                qualifier: None,
+                line: node.start_position().row as i32 + 1,
+            });
+        }
+        return;
+    };
+
+    if let Some(script) = script {
+        result.imports.push(ImportInfo {
+            source: script,
+            line: node.start_position().row as i32 + 1,
+            ..Default::default()
+        });
+    }
+}
+
+// Helper functions
+
+/// The path a command argument names, when it is literal. `"$DIR/lib.sh"` and
+/// `"$(dirname "$0")/lib.sh"` are taken to be relative to the script, which is
+/// what such a prefix almost always holds.
+fn script_path(node: &Node, source: &[u8]) -> Option<String> {
+    match node.kind() {
+        "word" | "raw_string" => {
+            let text = node_text(node, source).trim_matches('\'');
+            (!text.is_empty()).then(|| text.to_string())
+        }
+        "string" | "concatenation" => {
+            let parts: Vec<Node> = {
+                let mut cursor = node.walk();
+                node.named_children(&mut cursor).collect()
+            };
+            match parts.as_slice() {
+                [content] if matches!(content.kind(), "string_content" | "word") => {
+                    Some(node_text(content, source).to_string())
+                }
+                [prefix, rest] if is_expansion(prefix) && matches!(rest.kind(), "string_content" | "word" | "string") => {
+                    let rest = match rest.kind() {
+                        "string" => script_path(rest, source)?,
+                        _ => node_text(rest, source).to_string(),
+                    };
+                    rest.strip_prefix('/').map(|rest| format!("./{}", rest))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_expansion(node: &Node) -> bool {
+    matches!(node.kind(), "simple_expansion" | "expansion" | "command_substitution")
+}
+
+/// The definition up to its body: `function deploy()`, `cleanup()`
+fn signature(node: &Node, source: &[u8]) -> String {
+    let end = node.child_by_field_name("body").map_or(node.end_byte(), |body| body.start_byte());
+    let text = std::str::from_utf8(&source[node.start_byte()..end]).unwrap_or_default();
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::parser::{Parser, SupportedLanguage};
+
+    #[test]
+    fn test_extract_shell_script() {
+        let parser = Parser::new();
+        let source = r#"#!/usr/bin/env bash
+set -euo pipefail
+source ./lib/common.sh
+. "$SCRIPT_DIR/env.sh"
+. "$(dirname "$0")/paths.sh"
+
+function deploy() {
+  ./scripts/build.sh --release
+  bash -x tools/check.sh
+  version=$(current_version)
+  notify "deployed $version"
+}
+
+cleanup() { rm -rf "$TMP"; }
+
+sh -c "echo done"
+exec bin/server
+"#;
+
+        let result = parser.parse_file("test", SupportedLanguage::Shell, source).unwrap();
+
+        let imports: Vec<_> = result.imports.iter().map(|i| (i.source.as_str(), i.line)).collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("./lib/common.sh", 3),
+                ("./env.sh", 4),
+                ("./paths.sh", 5),
+                ("./scripts/build.sh", 8),
+                ("tools/check.sh", 9),
+                ("bin/server", 17),
+            ]
+        );
+
+        let functions: Vec<_> = result
+            .symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind.as_str(), s.signature.as_deref().unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            functions,
+            vec![("deploy", "function", "function deploy()"), ("cleanup", "function", "cleanup()")]
+        );
+
+        let calls: Vec<_> = result.calls.iter().map(|c| c.callee.as_str()).collect();
+        assert_eq!(calls, vec!["current_version", "notify", "rm"]);
+    }
+}
@@ -1,7 +1,7 @@
 use tree_sitter::Node;
 
-use super::{create_symbol, find_child, node_text};
-use crate::analysis::parser::{ImportInfo, ParseResult};
+use super::{add_inheritance, add_type_refs_in, create_symbol, find_child, node_text};
+use crate::analysis::parser::{ImportInfo, MemberInfo, ParseResult};
 
 /// Extract symbols and relationships from Swift AST
 pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResult) {
@@ -20,14 +20,19 @@ fn extract_node(
     for child in node.children(&mut cursor) {
         match child.kind() {
             "import_declaration" => extract_import(&child, source, result),
-            "class_declaration" => extract_class(file_id, &child, source, result, parent_id.clone()),
-            "struct_declaration" => extract_struct(file_id, &child, source, result, parent_id.clone()),
-            "enum_declaration" => extract_enum(file_id, &child, source, result, parent_id.clone()),
+            // Classes, structs, enums, actors and extensions share one node kind
+            "class_declaration" => match declaration_kind(&child, source) {
+                "extension" => extract_extension(file_id, &child, source, result),
+                kind => extract_type(file_id, &child, kind, source, result, parent_id.clone()),
+            },
             "protocol_declaration" => extract_protocol(file_id, &child, source, result, parent_id.clone()),
-            "function_declaration" => extract_function(file_id, &child, source, result, parent_id.clone()),
-            "property_declaration" => extract_property(file_id, &child, source, result, parent_id.clone()),
+            "function_declaration" | "protocol_function_declaration" | "init_declaration" => {
+                extract_function(file_id, &child, source, result, parent_id.clone())
+            }
+            "property_declaration" | "protocol_property_declaration" => {
+                extract_property(file_id, &child, source, result, parent_id.clone())
+            }
             "typealias_declaration" => extract_typealias(file_id, &child, source, result, parent_id.clone()),
-            "extension_declaration" => extract_extension(file_id, &child, source, result),
             _ => {
                 // Recurse into other node types
                 extract_node(file_id, &child, source, result, parent_id.clone());
@@ -37,108 +42,66 @@ fn extract_node(
 }
 
 fn extract_import(node: &Node, source: &[u8], result: &mut ParseResult) {
-    // Get the module name from import declaration
-    let mut cursor = node.walk();
-    for child in node.children(&mut cursor) {
-        if child.kind() == "identifier" || child.kind() == "simple_identifier" {
-            let module_name = node_text(&child, source);
-            result.imports.push(ImportInfo {
-                source: module_name.to_string(),
-                imported_names: vec![],
-                is_default: true,
-                line: node.start_position().row as i32 + 1,
-            });
-            break;
-        }
-    }
+    let Some(path) = find_child(node, "identifier") else { return };
+    let path = node_text(&path, source);
+
+    // `import struct Module.Type` imports a single declaration from the module
+    let imports_declaration = ["typealias", "struct", "class", "enum", "protocol", "let", "var", "func"]
+        .iter()
+        .any(|kind| find_child(node, kind).is_some());
+    let (module, imported_names) = match path.rsplit_once('.') {
+        Some((module, name)) if imports_declaration => (module, vec![name.to_string()]),
+        _ => (path, vec![]),
+    };
+
+    result.imports.push(ImportInfo {
+        source: module.to_string(),
+        imported_names,
+        is_default: true,
+        line: node.start_position().row as i32 + 1,
+        ..Default::default()
+    });
 }
 
-fn extract_class(
+/// A class, struct, enum or actor declaration
+fn extract_type(
     file_id: &str,
     node: &Node,
+    keyword: &str,
     source: &[u8],
     result: &mut ParseResult,
-    _parent_id: Option<String>,
+    parent_id: Option<String>,
 ) {
     let name = get_type_name(node, source);
     let is_public = is_exported(node, source);
+    let kind = match keyword {
+        "enum" => "enum",
+        // Structs and actors are "class" to match frontend types
+        _ => "class",
+    };
 
     let symbol = create_symbol(
         file_id,
         &name,
-        "class",
+        kind,
         node,
-        Some(format!("class {}", name)),
+        Some(format!("{} {}", keyword, name)),
         None,
         is_public,
-        None,
+        parent_id,
     );
 
-    let symbol_id = symbol.id.clone();
-    result.symbols.push(symbol);
-
-    // Extract members
-    if let Some(body) = find_child(node, "class_body") {
-        extract_node(file_id, &body, source, result, Some(symbol_id));
-    }
-}
-
-fn extract_struct(
-    file_id: &str,
-    node: &Node,
-    source: &[u8],
-    result: &mut ParseResult,
-    _parent_id: Option<String>,
-) {
-    let name = get_type_name(node, source);
-    let is_public = is_exported(node, source);
-
-    let symbol = create_symbol(
-        file_id,
-        &name,
-        "class", // Use "class" for struct to match frontend types
-        node,
-        Some(format!("struct {}", name)),
-        None,
-        is_public,
-        None,
-    );
+    // Only a class can have a superclass, and only as the first inherited type;
+    // everything else in the clause is a protocol it conforms to
+    let first_kind = if keyword == "class" { "extends" } else { "implements" };
+    extract_inheritance(Some(&symbol.id), &name, node, source, result, first_kind);
+    add_declaration_type_refs(&symbol.id, node, source, result);
 
     let symbol_id = symbol.id.clone();
     result.symbols.push(symbol);
 
     // Extract members
-    if let Some(body) = find_child(node, "struct_body") {
-        extract_node(file_id, &body, source, result, Some(symbol_id));
-    }
-}
-
-fn extract_enum(
-    file_id: &str,
-    node: &Node,
-    source: &[u8],
-    result: &mut ParseResult,
-    _parent_id: Option<String>,
-) {
-    let name = get_type_name(node, source);
-    let is_public = is_exported(node, source);
-
-    let symbol = create_symbol(
-        file_id,
-        &name,
-        "enum",
-        node,
-        Some(format!("enum {}", name)),
-        None,
-        is_public,
-        None,
-    );
-
-    let symbol_id = symbol.id.clone();
-    result.symbols.push(symbol);
-
-    // Extract cases
-    if let Some(body) = find_child(node, "enum_body") {
+    if let Some(body) = node.child_by_field_name("body") {
         extract_node(file_id, &body, source, result, Some(symbol_id));
     }
 }
@@ -148,7 +111,7 @@ fn extract_protocol(
     node: &Node,
     source: &[u8],
     result: &mut ParseResult,
-    _parent_id: Option<String>,
+    parent_id: Option<String>,
 ) {
     let name = get_type_name(node, source);
     let is_public = is_exported(node, source);
@@ -161,14 +124,17 @@ fn extract_protocol(
         Some(format!("protocol {}", name)),
         None,
         is_public,
-        None,
+        parent_id,
     );
 
+    extract_inheritance(Some(&symbol.id), &name, node, source, result, "extends");
+    add_declaration_type_refs(&symbol.id, node, source, result);
+
     let symbol_id = symbol.id.clone();
     result.symbols.push(symbol);
 
     // Extract protocol members
-    if let Some(body) = find_child(node, "protocol_body") {
+    if let Some(body) = node.child_by_field_name("body") {
         extract_node(file_id, &body, source, result, Some(symbol_id));
     }
 }
@@ -187,7 +153,7 @@ fn extract_function(
     // Build signature
     let signature = build_function_signature(node, source);
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         &name,
         kind,
@@ -196,7 +162,9 @@ fn extract_function(
         None,
         is_public,
         parent_id,
-    ));
+    );
+    add_declaration_type_refs(&symbol.id, node, source, result);
+    result.symbols.push(symbol);
 }
 
 fn extract_property(
@@ -211,10 +179,9 @@ fn extract_property(
     let is_public = is_exported(node, source);
 
     // Determine if it's a constant (let) or variable (var)
-    let is_constant = node_text(node, source).trim_start().starts_with("let");
-    let kind = if is_constant { "constant" } else { "variable" };
+    let kind = if mutability(node, source) == Some("let") { "constant" } else { "variable" };
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         &name,
         kind,
@@ -223,7 +190,9 @@ fn extract_property(
         None,
         is_public,
         parent_id,
-    ));
+    );
+    add_declaration_type_refs(&symbol.id, node, source, result);
+    result.symbols.push(symbol);
 }
 
 fn extract_typealias(
@@ -236,7 +205,7 @@ fn extract_typealias(
     let name = get_type_name(node, source);
     let is_public = is_exported(node, source);
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         &name,
         "type",
@@ -245,7 +214,9 @@ fn extract_typealias(
         None,
         is_public,
         parent_id,
-    ));
+    );
+    add_declaration_type_refs(&symbol.id, node, source, result);
+    result.symbols.push(symbol);
 }
 
 fn extract_extension(
@@ -254,73 +225,230 @@ fn extract_extension(
     source: &[u8],
     result: &mut ParseResult,
 ) {
-    // Extensions don't create new symbols, but we need to extract their contents
-    // Try to find the extended type name
-    if let Some(body) = find_child(node, "extension_body") {
+    // Extensions don't create new symbols, but `extension Foo: Proto` adds a conformance
+    let name = get_type_name(node, source);
+    extract_inheritance(None, &name, node, source, result, "implements");
+
+    // Extract their contents; members belong to the extended type, wherever it is declared
+    if let Some(body) = node.child_by_field_name("body") {
+        let first_member = result.symbols.len();
         extract_node(file_id, &body, source, result, None);
+
+        let members: Vec<_> = result.symbols[first_member..]
+            .iter()
+            .filter(|s| s.parent_id.is_none())
+            .map(|s| MemberInfo {
+                symbol_id: s.id.clone(),
+                owner: name.clone(),
+            })
+            .collect();
+        result.members.extend(members);
     }
 }
 
 // Helper functions
 
-fn get_type_name(node: &Node, source: &[u8]) -> String {
-    // Look for type_identifier or simple_identifier
-    let mut cursor = node.walk();
-    for child in node.children(&mut cursor) {
-        match child.kind() {
-            "type_identifier" | "simple_identifier" | "identifier" => {
-                return node_text(&child, source).to_string();
-            }
-            _ => {}
-        }
-    }
-    "anonymous".to_string()
+/// `class`, `struct`, `enum`, `actor` or `extension`
+fn declaration_kind<'a>(node: &Node, source: &'a [u8]) -> &'a str {
+    node.child_by_field_name("declaration_kind")
+        .map(|kind| node_text(&kind, source))
+        .unwrap_or("class")
 }
 
-fn get_function_name(node: &Node, source: &[u8]) -> String {
-    // Look for simple_identifier in function declaration
+/// Record the types in an inheritance clause (`class A: Base, Proto`).
+/// `first_kind` applies to the first entry, later entries are conformances.
+fn extract_inheritance(
+    symbol_id: Option<&str>,
+    name: &str,
+    node: &Node,
+    source: &[u8],
+    result: &mut ParseResult,
+    first_kind: &str,
+) {
     let mut cursor = node.walk();
-    for child in node.children(&mut cursor) {
-        if child.kind() == "simple_identifier" || child.kind() == "identifier" {
-            return node_text(&child, source).to_string();
-        }
+    let specifiers = node
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "inheritance_specifier")
+        .filter_map(|c| c.child_by_field_name("inherits_from"));
+
+    for (index, base) in specifiers.enumerate() {
+        let kind = if index == 0 { first_kind } else { "implements" };
+        add_inheritance(symbol_id, name, &base, kind, source, result);
     }
-    "anonymous".to_string()
 }
 
-fn find_property_name(node: &Node, source: &[u8]) -> String {
-    // Look for pattern which contains the property name
+/// Record the types a declaration names outside its own name, body and
+/// inheritance clause: parameter, return, property and aliased types, and
+/// generic constraints. The grammar puts most of these in `name` fields too,
+/// so they are picked out by what they are not.
+fn add_declaration_type_refs(symbol_id: &str, node: &Node, source: &[u8], result: &mut ParseResult) {
+    let skipped: Vec<usize> = ["name", "body", "value", "computed_value"]
+        .iter()
+        .filter_map(|field| node.child_by_field_name(field))
+        .map(|child| child.id())
+        .collect();
+
     let mut cursor = node.walk();
-    for child in node.children(&mut cursor) {
-        if child.kind() == "pattern" || child.kind() == "simple_identifier" || child.kind() == "identifier" {
-            let text = node_text(&child, source);
-            if !text.is_empty() {
-                return text.to_string();
-            }
-        }
-    }
-    "anonymous".to_string()
+    let types: Vec<Node> = node
+        .named_children(&mut cursor)
+        .filter(|c| !skipped.contains(&c.id()))
+        .filter(|c| !matches!(c.kind(), "modifiers" | "attribute" | "inheritance_specifier"))
+        .collect();
+
+    add_type_refs_in(symbol_id, node, &types, source, result);
 }
 
-fn build_function_signature(node: &Node, source: &[u8]) -> String {
-    let name = get_function_name(node, source);
+fn get_type_name(node: &Node, source: &[u8]) -> String {
+    // Declarations name a type_identifier, extensions the user_type they extend
+    node.child_by_field_name("name")
+        .and_then(|name| super::type_name(&name, source))
+        .unwrap_or("anonymous")
+        .to_string()
+}
 
-    // Get parameters if present
-    let params = find_child(node, "parameter_clause")
-        .map(|n| node_text(&n, source))
-        .unwrap_or("()");
+fn get_function_name(node: &Node, source: &[u8]) -> String {
+    node.child_by_field_name("name")
+        .map(|name| node_text(&name, source).to_string())
+        .unwrap_or_else(|| "anonymous".to_string())
+}
 
-    // Get return type if present
-    let return_type = find_child(node, "function_type")
-        .or_else(|| find_child(node, "type_annotation"))
-        .map(|n| format!(" -> {}", node_text(&n, source)))
-        .unwrap_or_default();
+fn find_property_name(node: &Node, source: &[u8]) -> String {
+    // The pattern's bound identifier; protocol requirements include `var` in the pattern
+    node.child_by_field_name("name")
+        .map(|pattern| pattern.child_by_field_name("bound_identifier").unwrap_or(pattern))
+        .map(|name| node_text(&name, source))
+        .filter(|name| !name.is_empty())
+        .unwrap_or("anonymous")
+        .to_string()
+}
 
-    format!("func {}{}{}", name, params, return_type)
+/// `let` or `var`; protocol requirements put the binding inside the name pattern
+fn mutability<'a>(node: &Node, source: &'a [u8]) -> Option<&'a str> {
+    [Some(*node), node.child_by_field_name("name")]
+        .into_iter()
+        .flatten()
+        .find_map(|n| {
+            let mut cursor = n.walk();
+            let binding = n.children(&mut cursor).find(|c| c.kind() == "value_binding_pattern");
+            binding
+        })
+        .and_then(|binding| binding.child_by_field_name("mutability"))
+        .map(|m| node_text(&m, source))
+}
+
+fn build_function_signature(node: &Node, source: &[u8]) -> String {
+    // Everything from `func`/`init` up to the body, without modifiers or attributes
+    let start = node
+        .child_by_field_name("name")
+        .and_then(|name| name.prev_sibling().filter(|keyword| keyword.kind() == "func"))
+        .or_else(|| node.child_by_field_name("name"))
+        .map_or(node.start_byte(), |n| n.start_byte());
+    let end = node
+        .child_by_field_name("body")
+        .map_or(node.end_byte(), |body| body.start_byte());
+
+    let text = std::str::from_utf8(&source[start..end]).unwrap_or("");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
 fn is_exported(node: &Node, source: &[u8]) -> bool {
     // In Swift, internal is the default. public/open are exported.
-    let text = node_text(node, source);
-    text.contains("public ") || text.contains("open ") || text.starts_with("public ") || text.starts_with("open ")
+    let Some(modifiers) = find_child(node, "modifiers") else { return false };
+    let mut cursor = modifiers.walk();
+    let exported = modifiers
+        .children(&mut cursor)
+        .filter(|m| m.kind() == "visibility_modifier")
+        .any(|m| matches!(node_text(&m, source), "public" | "open"));
+    exported
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::parser::{Parser, SupportedLanguage};
+
+    fn parse(source: &str) -> ParseResult {
+        Parser::new()
+            .parse_file("file", SupportedLanguage::Swift, source)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_extract_swift_symbols() {
+        let result = parse(
+            r#"
+import Foundation
+import struct Models.User
+
+public final class Store: Service {
+    private let items: [Item]
+    public init(items: [Item]) { self.items = items }
+    public func first(where match: Filter) -> Item? { nil }
+    struct Cache {}
+}
+
+protocol Service { var name: String { get } }
+enum Kind: String { case a }
+typealias Lookup = [String: Item]
+"#,
+        );
+
+        let symbol = |name: &str| result.symbols.iter().find(|s| s.name == name).unwrap();
+        let store = symbol("Store");
+        assert_eq!(store.kind, "class");
+        assert!(store.is_exported);
+        assert_eq!(symbol("items").kind, "constant");
+        assert!(!symbol("items").is_exported);
+        assert_eq!(symbol("init").parent_id.as_deref(), Some(store.id.as_str()));
+        assert_eq!(symbol("first").kind, "method");
+        assert_eq!(symbol("first").signature.as_deref(), Some("func first(where match: Filter) -> Item?"));
+        assert_eq!(symbol("Cache").parent_id.as_deref(), Some(store.id.as_str()));
+        assert_eq!(symbol("Service").kind, "interface");
+        assert_eq!(symbol("name").parent_id.as_deref(), Some(symbol("Service").id.as_str()));
+        assert_eq!(symbol("Kind").kind, "enum");
+        assert_eq!(symbol("Lookup").kind, "type");
+
+        assert_eq!(result.imports.len(), 2);
+        assert_eq!(result.imports[1].source, "Models");
+        assert_eq!(result.imports[1].imported_names, vec!["User"]);
+
+        let refs: Vec<_> = result.type_refs.iter().map(|r| r.name.as_str()).collect();
+        assert!(refs.contains(&"Filter"));
+        assert!(refs.contains(&"Item"));
+        assert!(!refs.contains(&"Store"));
+    }
+
+    #[test]
+    fn test_extract_inheritance_and_extensions() {
+        let result = parse(
+            r#"
+class Dog: Animal, Named {}
+struct Point: Hashable {}
+protocol Shape: Drawable {}
+extension Dog: CustomStringConvertible {
+    var description: String { "" }
+}
+"#,
+        );
+
+        let inheritance: Vec<_> = result
+            .inheritance
+            .iter()
+            .map(|i| (i.symbol_name.as_str(), i.base.as_str(), i.kind.as_str(), i.symbol_id.is_some()))
+            .collect();
+        assert_eq!(
+            inheritance,
+            vec![
+                ("Dog", "Animal", "extends", true),
+                ("Dog", "Named", "implements", true),
+                ("Point", "Hashable", "implements", true),
+                ("Shape", "Drawable", "extends", true),
+                ("Dog", "CustomStringConvertible", "implements", false),
+            ]
+        );
+
+        // Extension members belong to the extended type
+        let description = result.symbols.iter().find(|s| s.name == "description").unwrap();
+        assert!(result.members.iter().any(|m| m.symbol_id == description.id && m.owner == "Dog"));
+    }
 }
@@ -1,7 +1,7 @@
 use tree_sitter::Node;
 
-use super::{create_symbol, find_child, node_text};
-use crate::analysis::parser::{ExportInfo, ImportInfo, ParseResult};
+use super::{add_inheritance, add_type_refs, create_symbol, extract_calls, find_child, node_text, type_name};
+use crate::analysis::parser::{ExportInfo, ImportInfo, ImportStyle, ImportUsage, ParseResult, ReExportInfo};
 
 /// Extract symbols and relationships from TypeScript/JavaScript AST
 pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResult) {
@@ -12,7 +12,9 @@ pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResu
             "import_statement" => extract_import(&child, source, result),
             "export_statement" => extract_export(file_id, &child, source, result),
             "function_declaration" => extract_function(file_id, &child, source, result, false, None),
-            "class_declaration" => extract_class(file_id, &child, source, result, false),
+            "class_declaration" | "abstract_class_declaration" => {
+                extract_class(file_id, &child, source, result, false)
+            }
             "interface_declaration" => extract_interface(file_id, &child, source, result, false),
             "type_alias_declaration" => extract_type_alias(file_id, &child, source, result, false),
             "enum_declaration" => extract_enum(file_id, &child, source, result, false),
@@ -22,6 +24,76 @@ pub fn extract(file_id: &str, root: &Node, source: &[u8], result: &mut ParseResu
             _ => {}
         }
     }
+
+    extract_dynamic_imports(root, source, result);
+}
+
+/// `require('./x')` and `import('./x')` anywhere in the file, with a string
+/// literal (or substitution-free template) argument
+fn extract_dynamic_imports(node: &Node, source: &[u8], result: &mut ParseResult) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() == "call_expression" {
+            if let Some(import) = dynamic_import(&child, source) {
+                result.imports.push(import);
+            }
+        }
+        extract_dynamic_imports(&child, source, result);
+    }
+}
+
+fn dynamic_import(call: &Node, source: &[u8]) -> Option<ImportInfo> {
+    let function = call.child_by_field_name("function")?;
+    let style = match function.kind() {
+        "import" => ImportStyle::Dynamic,
+        "identifier" if node_text(&function, source) == "require" => ImportStyle::CommonJs,
+        _ => return None,
+    };
+
+    let arguments = call.child_by_field_name("arguments")?;
+    let specifier = arguments.named_child(0)?;
+    let has_substitution = find_child(&specifier, "template_substitution").is_some();
+    if !matches!(specifier.kind(), "string" | "template_string") || has_substitution {
+        return None;
+    }
+    let module = node_text(&specifier, source).trim_matches(|c| c == '"' || c == '\'' || c == '`');
+
+    Some(ImportInfo {
+        source: module.to_string(),
+        imported_names: bound_names(call, source),
+        style,
+        line: call.start_position().row as i32 + 1,
+        ..Default::default()
+    })
+}
+
+/// Names bound from a loaded module: `const { a, b: c } = require(..)` binds
+/// `a` and `b`; `const m = await import(..)` binds the whole module as `m`
+fn bound_names(call: &Node, source: &[u8]) -> Vec<String> {
+    let mut value = *call;
+    while let Some(parent) = value.parent().filter(|p| p.kind() == "await_expression") {
+        value = parent;
+    }
+    let Some(declarator) = value.parent().filter(|p| p.kind() == "variable_declarator") else {
+        return Vec::new();
+    };
+    let Some(name) = declarator.child_by_field_name("name") else { return Vec::new() };
+
+    match name.kind() {
+        "identifier" => vec![format!("* as {}", node_text(&name, source))],
+        "object_pattern" => {
+            let mut cursor = name.walk();
+            name.named_children(&mut cursor)
+                .filter_map(|property| match property.kind() {
+                    "shorthand_property_identifier_pattern" => Some(property),
+                    "pair_pattern" => property.child_by_field_name("key"),
+                    _ => None,
+                })
+                .map(|key| node_text(&key, source).to_string())
+                .collect()
+        }
+        _ => Vec::new(),
+    }
 }
 
 fn extract_import(node: &Node, source: &[u8], result: &mut ParseResult) {
@@ -41,6 +113,9 @@ fn extract_import(node: &Node, source: &[u8], result: &mut ParseResult) {
 
     let mut imported_names = Vec::new();
     let mut is_default = false;
+    // `import type { A }`, or `import { type A, type B }` with nothing else
+    let mut type_only = find_child(node, "type").is_some();
+    let mut has_value_import = false;
 
     // Check for import clause
     if let Some(clause) = find_child(node, "import_clause") {
@@ -50,6 +125,7 @@ fn extract_import(node: &Node, source: &[u8], result: &mut ParseResult) {
                 "identifier" => {
                     // Default import
                     is_default = true;
+                    has_value_import = true;
                     imported_names.push(node_text(&child, source).to_string());
                 }
                 "named_imports" => {
@@ -57,6 +133,7 @@ fn extract_import(node: &Node, source: &[u8], result: &mut ParseResult) {
                     let mut imports_cursor = child.walk();
                     for import_spec in child.children(&mut imports_cursor) {
                         if import_spec.kind() == "import_specifier" {
+                            has_value_import |= find_child(&import_spec, "type").is_none();
                             if let Some(name_node) = find_child(&import_spec, "identifier") {
                                 imported_names.push(node_text(&name_node, source).to_string());
                             }
@@ -65,6 +142,7 @@ fn extract_import(node: &Node, source: &[u8], result: &mut ParseResult) {
                 }
                 "namespace_import" => {
                     // import * as name
+                    has_value_import = true;
                     if let Some(name_node) = find_child(&child, "identifier") {
                         imported_names.push(format!("* as {}", node_text(&name_node, source)));
                     }
@@ -72,13 +150,16 @@ fn extract_import(node: &Node, source: &[u8], result: &mut ParseResult) {
                 _ => {}
             }
         }
+        type_only |= !has_value_import && !imported_names.is_empty();
     }
 
     result.imports.push(ImportInfo {
         source: import_source,
         imported_names,
         is_default,
+        usage: if type_only { ImportUsage::TypeOnly } else { ImportUsage::Runtime },
         line: node.start_position().row as i32 + 1,
+        ..Default::default()
     });
 }
 
@@ -93,7 +174,9 @@ fn extract_export(file_id: &str, node: &Node, source: &[u8], result: &mut ParseR
     for child in node.children(&mut cursor) {
         match child.kind() {
             "function_declaration" => extract_function(file_id, &child, source, result, true, None),
-            "class_declaration" => extract_class(file_id, &child, source, result, true),
+            "class_declaration" | "abstract_class_declaration" => {
+                extract_class(file_id, &child, source, result, true)
+            }
             "interface_declaration" => extract_interface(file_id, &child, source, result, true),
             "type_alias_declaration" => extract_type_alias(file_id, &child, source, result, true),
             "enum_declaration" => extract_enum(file_id, &child, source, result, true),
@@ -110,11 +193,14 @@ fn extract_export(file_id: &str, node: &Node, source: &[u8], result: &mut ParseR
                 });
             }
             "export_clause" => {
-                // export { foo, bar }
+                // export { foo, bar as baz } - recorded under the name importers use
                 let mut clause_cursor = child.walk();
                 for spec in child.children(&mut clause_cursor) {
                     if spec.kind() == "export_specifier" {
-                        if let Some(name_node) = find_child(&spec, "identifier") {
+                        let name_node = spec
+                            .child_by_field_name("alias")
+                            .or_else(|| spec.child_by_field_name("name"));
+                        if let Some(name_node) = name_node {
                             result.exports.push(ExportInfo {
                                 name: node_text(&name_node, source).to_string(),
                                 is_default: false,
@@ -124,9 +210,60 @@ fn extract_export(file_id: &str, node: &Node, source: &[u8], result: &mut ParseR
                     }
                 }
             }
+            "namespace_export" => {
+                // export * as ns from './module'
+                if let Some(name_node) = find_child(&child, "identifier") {
+                    result.exports.push(ExportInfo {
+                        name: node_text(&name_node, source).to_string(),
+                        is_default: false,
+                        line: node.start_position().row as i32 + 1,
+                    });
+                }
+            }
             _ => {}
         }
     }
+
+    if let Some(source_node) = node.child_by_field_name("source") {
+        extract_re_export(node, &source_node, source, result);
+    }
+}
+
+/// `export * from`, `export * as ns from` and `export { a as b } from`
+fn extract_re_export(node: &Node, source_node: &Node, source: &[u8], result: &mut ParseResult) {
+    let module = node_text(source_node, source)
+        .trim_matches(|c| c == '"' || c == '\'' || c == '`')
+        .to_string();
+    if module.is_empty() {
+        return;
+    }
+
+    let mut names = Vec::new();
+    if let Some(clause) = find_child(node, "export_clause") {
+        let mut cursor = clause.walk();
+        for spec in clause.children(&mut cursor).filter(|c| c.kind() == "export_specifier") {
+            let Some(name) = spec.child_by_field_name("name") else { continue };
+            let name = node_text(&name, source).to_string();
+            let alias = spec
+                .child_by_field_name("alias")
+                .map(|alias| node_text(&alias, source).to_string())
+                .unwrap_or_else(|| name.clone());
+            names.push((name, alias));
+        }
+    }
+
+    let namespace_export = find_child(node, "namespace_export");
+    let namespace = namespace_export
+        .as_ref()
+        .and_then(|ns| find_child(ns, "identifier"))
+        .map(|name| node_text(&name, source).to_string());
+
+    result.re_exports.push(ReExportInfo {
+        source: module,
+        names,
+        namespace,
+        line: node.start_position().row as i32 + 1,
+    });
 }
 
 fn extract_function(
@@ -158,7 +295,7 @@ fn extract_function(
         return_type.map(|t| format!("{}", t)).unwrap_or_default()
     );
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         name,
         "function",
@@ -167,7 +304,14 @@ fn extract_function(
         None,
         is_exported,
         parent_id,
-    ));
+    );
+
+    add_type_refs(&symbol.id, node, &["type_parameters", "parameters", "return_type"], source, result);
+    if let Some(body) = node.child_by_field_name("body") {
+        extract_calls(&symbol.id, &body, source, result);
+    }
+
+    result.symbols.push(symbol);
 }
 
 fn extract_class(
@@ -183,19 +327,22 @@ fn extract_class(
         .map(|n| node_text(&n, source))
         .unwrap_or("anonymous");
 
-    // Check for extends
-    let extends = if let Some(heritage) = find_child(node, "class_heritage") {
-        if let Some(extends_clause) = find_child(&heritage, "extends_clause") {
-            find_child(&extends_clause, "identifier")
-                .map(|n| node_text(&n, source).to_string())
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+    // Heritage: `extends Base implements A, B` (TS) or `extends Base` (JS)
+    let heritage = find_child(node, "class_heritage");
+    let extends = heritage.as_ref().and_then(|heritage| match find_child(heritage, "extends_clause") {
+        Some(clause) => clause.child_by_field_name("value"),
+        None => heritage.named_child(0).filter(|_| find_child(heritage, "implements_clause").is_none()),
+    });
+    let implements = heritage
+        .as_ref()
+        .and_then(|heritage| find_child(heritage, "implements_clause"))
+        .map(|clause| {
+            let mut cursor = clause.walk();
+            clause.named_children(&mut cursor).collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
 
-    let signature = if let Some(ext) = extends {
+    let signature = if let Some(ext) = extends.as_ref().and_then(|e| type_name(e, source)) {
         format!("class {} extends {}", name, ext)
     } else {
         format!("class {}", name)
@@ -217,6 +364,14 @@ fn extract_class(
         id
     };
 
+    add_type_refs(&class_id, node, &["type_parameters"], source, result);
+    if let Some(base) = &extends {
+        add_inheritance(Some(&class_id), name, base, "extends", source, result);
+    }
+    for base in &implements {
+        add_inheritance(Some(&class_id), name, base, "implements", source, result);
+    }
+
     // Extract class body members
     if let Some(body) = find_child(node, "class_body") {
         let mut body_cursor = body.walk();
@@ -250,7 +405,7 @@ fn extract_class_member(
         "property"
     };
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         name,
         kind,
@@ -259,7 +414,20 @@ fn extract_class_member(
         None,
         false,
         Some(parent_id.to_string()),
-    ));
+    );
+
+    let signature_fields = ["type_parameters", "parameters", "return_type", "type"];
+    add_type_refs(&symbol.id, node, &signature_fields, source, result);
+
+    // Methods have a body; field initializers may call too (`x = compute()`)
+    let body = node
+        .child_by_field_name("body")
+        .or_else(|| node.child_by_field_name("value"));
+    if let Some(body) = body {
+        extract_calls(&symbol.id, &body, source, result);
+    }
+
+    result.symbols.push(symbol);
 }
 
 fn extract_interface(
@@ -277,7 +445,7 @@ fn extract_interface(
 
     let signature = format!("interface {}", name);
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         name,
         "interface",
@@ -286,7 +454,19 @@ fn extract_interface(
         None,
         is_exported,
         None,
-    ));
+    );
+
+    add_type_refs(&symbol.id, node, &["type_parameters", "body"], source, result);
+
+    // interface A extends B, C
+    if let Some(clause) = find_child(node, "extends_type_clause") {
+        let mut cursor = clause.walk();
+        for base in clause.children_by_field_name("type", &mut cursor) {
+            add_inheritance(Some(&symbol.id), name, &base, "extends", source, result);
+        }
+    }
+
+    result.symbols.push(symbol);
 }
 
 fn extract_type_alias(
@@ -302,7 +482,7 @@ fn extract_type_alias(
         .map(|n| node_text(&n, source))
         .unwrap_or("anonymous");
 
-    result.symbols.push(create_symbol(
+    let symbol = create_symbol(
         file_id,
         name,
         "type",
@@ -311,7 +491,9 @@ fn extract_type_alias(
         None,
         is_exported,
         None,
-    ));
+    );
+    add_type_refs(&symbol.id, node, &["type_parameters", "value"], source, result);
+    result.symbols.push(symbol);
 }
 
 fn extract_enum(
@@ -354,6 +536,7 @@ fn extract_variable(
 
                 // Check if it's an arrow function or function expression
                 let value_node = find_child(&child, "arrow_function")
+                    .or_else(|| find_child(&child, "function_expression"))
                     .or_else(|| find_child(&child, "function"));
 
                 let kind = if value_node.is_some() {
@@ -362,7 +545,7 @@ fn extract_variable(
                     "variable"
                 };
 
-                result.symbols.push(create_symbol(
+                let symbol = create_symbol(
                     file_id,
                     name,
                     kind,
@@ -371,7 +554,17 @@ fn extract_variable(
                     None,
                     is_exported,
                     None,
-                ));
+                );
+
+                add_type_refs(&symbol.id, &child, &["type"], source, result);
+                if let Some(value) = &value_node {
+                    add_type_refs(&symbol.id, value, &["type_parameters", "parameters", "return_type"], source, result);
+                }
+                if let Some(body) = value_node.and_then(|v| v.child_by_field_name("body")) {
+                    extract_calls(&symbol.id, &body, source, result);
+                }
+
+                result.symbols.push(symbol);
             }
         }
     }
@@ -421,6 +614,131 @@ mod tests {
         assert_eq!(result.symbols[0].kind, "class");
     }
 
+    #[test]
+    fn test_extract_type_refs() {
+        let parser = Parser::new();
+        let source = r#"
+            interface Props<T extends Item> { node: GraphNode; onSelect(id: string): Selection<T> }
+            function render(props: Props<Item>): Element {}
+            type Handler = (event: NodeEvent) => void;
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::TypeScript, source).unwrap();
+        let refs = |name: &str| {
+            let id = &result.symbols.iter().find(|s| s.name == name).unwrap().id;
+            result
+                .type_refs
+                .iter()
+                .filter(|r| &r.symbol_id == id)
+                .map(|r| r.name.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        // The declared type parameter `T` is not a reference
+        assert_eq!(refs("Props"), vec!["Item", "GraphNode", "Selection"]);
+        assert_eq!(refs("render"), vec!["Props", "Item", "Element"]);
+        assert_eq!(refs("Handler"), vec!["NodeEvent"]);
+    }
+
+    #[test]
+    fn test_extract_type_only_imports() {
+        let parser = Parser::new();
+        let source = r#"
+            import type { Props } from './props';
+            import { type A, type B } from './types';
+            import { type C, render } from './render';
+            import './styles.css';
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::TypeScript, source).unwrap();
+        let usages: Vec<_> = result.imports.iter().map(|i| i.usage.as_str()).collect();
+        assert_eq!(usages, vec!["type-only", "type-only", "runtime", "runtime"]);
+    }
+
+    #[test]
+    fn test_extract_dynamic_imports() {
+        let parser = Parser::new();
+        let source = r#"
+            import { a } from './a';
+            const fs = require('fs');
+            const { join, resolve: res } = require('./paths');
+
+            async function load(name) {
+                const page = await import(`./pages/home`);
+                await import(`./pages/${name}`);
+                return require(name);
+            }
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::JavaScript, source).unwrap();
+        let imports: Vec<_> = result
+            .imports
+            .iter()
+            .map(|i| (i.source.as_str(), i.style.as_str(), i.imported_names.join(",")))
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("./a", "static", "a".to_string()),
+                ("fs", "commonjs", "* as fs".to_string()),
+                ("./paths", "commonjs", "join,resolve".to_string()),
+                ("./pages/home", "dynamic", "* as page".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_re_exports() {
+        let parser = Parser::new();
+        let source = r#"
+            export * from './shapes';
+            export * as geometry from './geometry';
+            export { Circle as Round, Square } from './square';
+            const local = 1;
+            export { local as value };
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::TypeScript, source).unwrap();
+        assert_eq!(result.re_exports.len(), 3);
+        assert_eq!(result.re_exports[0].source, "./shapes");
+        assert!(result.re_exports[0].names.is_empty());
+        assert_eq!(result.re_exports[1].namespace.as_deref(), Some("geometry"));
+        assert_eq!(
+            result.re_exports[2].names,
+            vec![("Circle".to_string(), "Round".to_string()), ("Square".to_string(), "Square".to_string())]
+        );
+
+        let exported: Vec<_> = result.exports.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(exported, vec!["geometry", "Round", "Square", "value"]);
+    }
+
+    #[test]
+    fn test_extract_inheritance() {
+        let parser = Parser::new();
+        let source = r#"
+            export abstract class View extends React.Component<Props> implements Disposable, ns.Named<T> {}
+            interface Shape extends Sized, geo.Located {}
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::TypeScript, source).unwrap();
+        let bases: Vec<_> = result
+            .inheritance
+            .iter()
+            .map(|i| (i.symbol_name.as_str(), i.base.as_str(), i.kind.as_str()))
+            .collect();
+        assert_eq!(bases, vec![
+            ("View", "Component", "extends"),
+            ("View", "Disposable", "implements"),
+            ("View", "Named", "implements"),
+            ("Shape", "Sized", "extends"),
+            ("Shape", "Located", "extends"),
+        ]);
+        assert!(result.inheritance.iter().all(|i| i.symbol_id.is_some()));
+
+        let js = parser.parse_file("test", SupportedLanguage::JavaScript, "class A extends B {}").unwrap();
+        assert_eq!(js.inheritance[0].base, "B");
+    }
+
     #[test]
     fn test_extract_imports() {
         let parser = Parser::new();
@@ -437,6 +755,51 @@ mod tests {
         assert!(result.imports[1].is_default);
     }
 
+    #[test]
+    fn test_extract_calls() {
+        let parser = Parser::new();
+        let source = r#"
+            function run() {
+                const svc = new Service();
+                svc.start(load());
+            }
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::TypeScript, source).unwrap();
+        let run_id = &result.symbols[0].id;
+        let callees: Vec<_> = result.calls.iter().map(|c| c.callee.as_str()).collect();
+        assert_eq!(callees.len(), 3);
+        assert!(callees.contains(&"Service"));
+        assert!(callees.contains(&"load"));
+        assert!(result.calls.iter().all(|c| &c.caller_id == run_id));
+
+        let start = result.calls.iter().find(|c| c.callee == "start").unwrap();
+        assert_eq!(start.qualifier.as_deref(), Some("svc"));
+    }
+
+    #[test]
+    fn test_extract_calls_skips_nested_declarations() {
+        let parser = Parser::new();
+        let source = r#"
+            function run() {
+                load();
+                const onDone = () => save();
+                items.forEach(function (item) { render(item); });
+                function retry() { schedule(); }
+            }
+        "#;
+
+        let result = parser.parse_file("test", SupportedLanguage::TypeScript, source).unwrap();
+        let run = result.symbols.iter().find(|s| s.name == "run").unwrap();
+        let callees: Vec<_> = result
+            .calls
+            .iter()
+            .filter(|c| c.caller_id == run.id)
+            .map(|c| c.callee.as_str())
+            .collect();
+        assert_eq!(callees, vec!["load", "save", "forEach", "render"]);
+    }
+
     #[test]
     fn test_extract_exports() {
         let parser = Parser::new();
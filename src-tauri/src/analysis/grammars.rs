@@ -0,0 +1,247 @@
+//! Grammars that need adapting before tree-sitter 0.24 can load them.
+//!
+//! tree-sitter-swift 0.7.1 and tree-sitter-php 0.24.2 are generated for
+//! language ABI 15, which tree-sitter 0.24 rejects (it reads ABI 13 and 14).
+//! The two layouts differ only in the fields ABI 15 appends to `TSLanguage`
+//! (name, reserved words, supertype maps, version metadata) and in the lex
+//! mode entries, which gained a `reserved_word_set_id`. The parse tables, lexer
+//! and external scanner work unchanged once the lex modes are repacked and the
+//! version is lowered to 14.
+//!
+//! Reserved words only make an ABI 15 parser reject a keyword where an
+//! identifier would otherwise be accepted. Without them a keyword is lexed as
+//! an identifier wherever the keyword itself is not valid, which changes error
+//! recovery on broken code but not the trees of code that parses.
+//!
+//! The layout mirrored here is only known to hold for the pinned grammar
+//! releases. Before converting, the version, grammar name and table sizes are
+//! checked against those releases; a grammar that does not match is handed to
+//! tree-sitter as it is, which refuses to load it rather than misread it, and
+//! the tests below fail so a grammar bump cannot ship unconverted.
+
+use std::ffi::{c_char, c_void, CStr};
+use std::sync::OnceLock;
+
+use tree_sitter::Language;
+use tree_sitter_language::LanguageFn;
+
+/// The ABI 14 `TSLanguage` layout, which is also the prefix of the ABI 15 one
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct RawLanguage {
+    version: u32,
+    symbol_count: u32,
+    alias_count: u32,
+    token_count: u32,
+    external_token_count: u32,
+    state_count: u32,
+    large_state_count: u32,
+    production_id_count: u32,
+    field_count: u32,
+    max_alias_sequence_length: u16,
+    parse_table: *const c_void,
+    small_parse_table: *const c_void,
+    small_parse_table_map: *const c_void,
+    parse_actions: *const c_void,
+    symbol_names: *const c_void,
+    field_names: *const c_void,
+    field_map_slices: *const c_void,
+    field_map_entries: *const c_void,
+    symbol_metadata: *const c_void,
+    public_symbol_map: *const c_void,
+    alias_map: *const c_void,
+    alias_sequences: *const c_void,
+    lex_modes: *const c_void,
+    lex_fn: *const c_void,
+    keyword_lex_fn: *const c_void,
+    keyword_capture_token: u16,
+    external_scanner: [*const c_void; 7],
+    primary_state_ids: *const c_void,
+}
+
+/// The start of the ABI 15 `TSLanguage` layout, up to the grammar name
+#[repr(C)]
+struct RawLanguageV15 {
+    base: RawLanguage,
+    name: *const c_char,
+}
+
+/// What a pinned grammar release is known to contain
+struct Expected {
+    crate_name: &'static str,
+    name: &'static str,
+    symbol_count: u32,
+    state_count: u32,
+    field_count: u32,
+}
+
+const SWIFT: Expected = Expected {
+    crate_name: "tree-sitter-swift 0.7.1",
+    name: "swift",
+    symbol_count: 547,
+    state_count: 9028,
+    field_count: 46,
+};
+
+const PHP: Expected = Expected {
+    crate_name: "tree-sitter-php 0.24.2",
+    name: "php",
+    symbol_count: 431,
+    state_count: 3371,
+    field_count: 31,
+};
+
+/// ABI 14 `TSLexMode`
+#[repr(C)]
+struct LexMode {
+    lex_state: u16,
+    external_lex_state: u16,
+}
+
+/// ABI 15 `TSLexerMode`
+#[repr(C)]
+struct LexerMode {
+    lex_state: u16,
+    external_lex_state: u16,
+    _reserved_word_set_id: u16,
+}
+
+/// The converted language lives for the rest of the process, like the static
+/// one generated grammars return
+struct StaticLanguage(RawLanguage);
+
+// SAFETY: every pointer refers to immutable static data or to the leaked lex
+// mode table, none of which is ever written after construction
+unsafe impl Send for StaticLanguage {}
+unsafe impl Sync for StaticLanguage {}
+
+/// The Swift grammar, converted to language ABI 14
+pub fn swift() -> Language {
+    unsafe { LanguageFn::from_raw(swift_v14) }.into()
+}
+
+/// The PHP grammar (PHP code with text outside `<?php` tags), converted to language ABI 14
+pub fn php() -> Language {
+    unsafe { LanguageFn::from_raw(php_v14) }.into()
+}
+
+extern "C" fn swift_v14() -> *const () {
+    static LANGUAGE: OnceLock<Option<StaticLanguage>> = OnceLock::new();
+    // SAFETY: the language function of a generated grammar returns a static TSLanguage
+    let language = LANGUAGE.get_or_init(|| unsafe { convert(tree_sitter_swift::LANGUAGE, &SWIFT) });
+    language_ptr(language, tree_sitter_swift::LANGUAGE)
+}
+
+extern "C" fn php_v14() -> *const () {
+    static LANGUAGE: OnceLock<Option<StaticLanguage>> = OnceLock::new();
+    // SAFETY: the language function of a generated grammar returns a static TSLanguage
+    let language = LANGUAGE.get_or_init(|| unsafe { convert(tree_sitter_php::LANGUAGE_PHP, &PHP) });
+    language_ptr(language, tree_sitter_php::LANGUAGE_PHP)
+}
+
+/// The converted language, or the upstream one for tree-sitter to reject
+fn language_ptr(language: &Option<StaticLanguage>, upstream: LanguageFn) -> *const () {
+    match language {
+        Some(language) => &language.0 as *const RawLanguage as *const (),
+        None => unsafe { upstream.into_raw()() },
+    }
+}
+
+/// Copy an ABI 15 language into the ABI 14 layout, repacking its lex modes,
+/// or None if it is not the grammar release the layout was checked against.
+///
+/// # Safety
+/// `upstream` must return a pointer to a static `TSLanguage`.
+unsafe fn convert(upstream: LanguageFn, expected: &Expected) -> Option<StaticLanguage> {
+    let raw = upstream.into_raw()();
+    // The version leads every ABI's layout; the rest is only read once it is 15
+    let version = *(raw as *const u32);
+    if version != 15 {
+        tracing::error!("{} has language ABI {}, not 15; not converting it", expected.crate_name, version);
+        return None;
+    }
+
+    let upstream = &*(raw as *const RawLanguageV15);
+    let counts = (upstream.base.symbol_count, upstream.base.state_count, upstream.base.field_count);
+    let matches = counts == (expected.symbol_count, expected.state_count, expected.field_count)
+        && !upstream.name.is_null()
+        && CStr::from_ptr(upstream.name).to_bytes() == expected.name.as_bytes();
+    if !matches {
+        tracing::error!(
+            "{} does not match the TSLanguage layout it is converted with; not converting it",
+            expected.crate_name
+        );
+        return None;
+    }
+
+    let states = upstream.base.state_count as usize;
+    // An ABI 15 grammar has one lex mode per parse state
+    let modes = std::slice::from_raw_parts(upstream.base.lex_modes as *const LexerMode, states);
+    let modes: &'static [LexMode] = modes
+        .iter()
+        .map(|mode| LexMode {
+            lex_state: mode.lex_state,
+            external_lex_state: mode.external_lex_state,
+        })
+        .collect::<Vec<_>>()
+        .leak();
+
+    Some(StaticLanguage(RawLanguage {
+        version: 14,
+        lex_modes: modes.as_ptr() as *const c_void,
+        ..upstream.base
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinned_grammars_convert() {
+        // A grammar bump must update `Expected` and re-check the layout, or
+        // tree-sitter will refuse to load the grammar at runtime
+        for (upstream, expected) in [(tree_sitter_swift::LANGUAGE, &SWIFT), (tree_sitter_php::LANGUAGE_PHP, &PHP)] {
+            assert!(
+                unsafe { convert(upstream, expected) }.is_some(),
+                "{} no longer matches the layout it is converted with",
+                expected.crate_name
+            );
+        }
+    }
+
+    #[test]
+    fn test_swift_loads() {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&swift()).unwrap();
+
+        let tree = parser.parse("class Dog: Animal { func bark() {} }", None).unwrap();
+        let root = tree.root_node();
+        assert!(!root.has_error());
+        assert_eq!(root.child(0).map(|n| n.kind()), Some("class_declaration"));
+    }
+
+    #[test]
+    fn test_php_loads() {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&php()).unwrap();
+
+        // `class`, `function` and `match` are keywords the grammar reserves
+        let tree = parser
+            .parse("<?php\nclass Dog extends Animal { public function bark(): string { return match(1) { 1 => 'woof' }; } }\n", None)
+            .unwrap();
+        let root = tree.root_node();
+        assert!(!root.has_error());
+        assert_eq!(root.named_child(1).map(|n| n.kind()), Some("class_declaration"));
+    }
+
+    #[test]
+    fn test_unexpected_grammar_is_not_converted() {
+        let resized = Expected {
+            field_count: PHP.field_count + 1,
+            ..PHP
+        };
+        assert!(unsafe { convert(tree_sitter_php::LANGUAGE_PHP, &resized) }.is_none());
+        assert!(unsafe { convert(tree_sitter_swift::LANGUAGE, &PHP) }.is_none());
+    }
+}
@@ -1,5 +1,7 @@
 mod engine;
+mod grammars;
 mod parser;
+mod resolvers;
 pub mod extractors;
 
 pub use engine::{AnalysisEngine, AnalysisProgress, AnalysisResult};
@@ -15,16 +15,29 @@ pub enum SupportedLanguage {
     Go,
     Rust,
     C,
-    // Swift has tree-sitter but disabled due to ABI incompatibility
+    Cpp,
     Swift,
+    Java,
+    CSharp,
+    Ruby,
+    Php,
+    Shell,
+    Html,
+    Css,
+    // Extracted without tree-sitter, see `extractors::kotlin` and `extractors::markdown`
+    Kotlin,
+    Markdown,
+    // Single-file components, whose scripts are parsed as TypeScript/JavaScript
+    Vue,
+    Svelte,
     // Discovery-only languages (no tree-sitter parsing)
     Json,
     Yaml,
-    Markdown,
-    Html,
-    Css,
+    Toml,
+    MsBuild,
     Plist,
-    Shell,
+    // Images, linked from HTML, CSS and imports
+    Image,
 }
 
 impl SupportedLanguage {
@@ -36,27 +49,55 @@ impl SupportedLanguage {
             "py" | "pyw" => Some(Self::Python),
             "go" => Some(Self::Go),
             "rs" => Some(Self::Rust),
+            // `.h` headers may be C++ too; see `for_header`
             "c" | "h" => Some(Self::C),
-            // Swift enabled for discovery, but parsing disabled due to ABI incompatibility
+            "cpp" | "cc" | "cxx" | "c++" | "hpp" | "hh" | "hxx" => Some(Self::Cpp),
             "swift" => Some(Self::Swift),
+            "java" => Some(Self::Java),
+            "cs" => Some(Self::CSharp),
+            "rb" => Some(Self::Ruby),
+            "php" => Some(Self::Php),
+            "sh" | "bash" | "zsh" => Some(Self::Shell),
+            "html" | "htm" => Some(Self::Html),
+            "css" | "scss" | "sass" | "less" => Some(Self::Css),
+            "kt" | "kts" => Some(Self::Kotlin),
+            "md" | "markdown" => Some(Self::Markdown),
+            "vue" => Some(Self::Vue),
+            "svelte" => Some(Self::Svelte),
             // Discovery-only languages (included in graph but no symbol extraction)
             "json" => Some(Self::Json),
             "yaml" | "yml" => Some(Self::Yaml),
-            "md" | "markdown" => Some(Self::Markdown),
-            "html" | "htm" => Some(Self::Html),
-            "css" | "scss" | "sass" | "less" => Some(Self::Css),
+            "toml" => Some(Self::Toml),
+            "csproj" => Some(Self::MsBuild),
             "plist" => Some(Self::Plist),
-            "sh" | "bash" | "zsh" => Some(Self::Shell),
+            "svg" | "png" | "jpg" | "jpeg" | "gif" | "webp" | "avif" | "ico" => Some(Self::Image),
             _ => None,
         }
     }
 
-    /// Returns true if this language supports full tree-sitter parsing
+    /// Returns true if symbols are extracted from this language
     pub fn requires_parsing(&self) -> bool {
         matches!(
             self,
-            Self::TypeScript | Self::JavaScript | Self::Python | Self::Go | Self::Rust | Self::C
-            // Swift excluded due to ABI issue
+            Self::TypeScript
+                | Self::JavaScript
+                | Self::Python
+                | Self::Go
+                | Self::Rust
+                | Self::C
+                | Self::Cpp
+                | Self::Swift
+                | Self::Java
+                | Self::CSharp
+                | Self::Ruby
+                | Self::Php
+                | Self::Shell
+                | Self::Html
+                | Self::Css
+                | Self::Markdown
+                | Self::Kotlin
+                | Self::Vue
+                | Self::Svelte
         )
     }
 
@@ -68,17 +109,84 @@ impl SupportedLanguage {
             Self::Go => "go",
             Self::Rust => "rust",
             Self::C => "c",
+            Self::Cpp => "cpp",
             Self::Swift => "swift",
+            Self::Java => "java",
+            Self::CSharp => "csharp",
+            Self::Ruby => "ruby",
+            Self::Php => "php",
+            Self::Kotlin => "kotlin",
+            Self::Vue => "vue",
+            Self::Svelte => "svelte",
             Self::Json => "json",
             Self::Yaml => "yaml",
+            Self::Toml => "toml",
+            Self::MsBuild => "msbuild",
             Self::Markdown => "markdown",
             Self::Html => "html",
             Self::Css => "css",
             Self::Plist => "plist",
+            Self::Image => "image",
             Self::Shell => "shell",
         }
     }
 
+    /// The language of an extensionless script, from its `#!` line:
+    /// `#!/bin/bash`, `#!/usr/bin/env zsh`
+    pub fn from_shebang(source: &str) -> Option<Self> {
+        let interpreter = source.lines().next()?.strip_prefix("#!")?;
+        let mut words = interpreter.split_whitespace();
+        let mut program = words.next()?.rsplit('/').next()?;
+        if program == "env" {
+            program = words.find(|w| !w.starts_with('-'))?;
+        }
+
+        matches!(program, "sh" | "bash" | "zsh" | "dash" | "ksh").then_some(Self::Shell)
+    }
+
+    /// C or C++ for a `.h` header, by whether any line outside comments uses
+    /// C++-only syntax: namespaces, templates, classes, access specifiers,
+    /// `using`, `::` scopes or extensionless standard headers (`<vector>`)
+    pub fn for_header(source: &str) -> Self {
+        let mut in_comment = false;
+
+        for line in source.lines() {
+            let mut line = line;
+            if in_comment {
+                match line.find("*/") {
+                    Some(end) => {
+                        line = &line[end + 2..];
+                        in_comment = false;
+                    }
+                    None => continue,
+                }
+            }
+            let code = line.split("//").next().unwrap_or_default();
+            let code = match code.find("/*") {
+                Some(start) => {
+                    in_comment = !code[start..].contains("*/");
+                    &code[..start]
+                }
+                None => code,
+            };
+            let code = code.trim();
+
+            let is_cpp = ["namespace ", "template", "class ", "using ", "public:", "protected:", "private:"]
+                .iter()
+                .any(|keyword| code.starts_with(keyword))
+                || code.contains("::")
+                || code
+                    .strip_prefix("#include <")
+                    .and_then(|header| header.split('>').next())
+                    .is_some_and(|header| !header.contains('.'));
+            if is_cpp {
+                return Self::Cpp;
+            }
+        }
+
+        Self::C
+    }
+
     fn tree_sitter_language(&self) -> Language {
         match self {
             Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
@@ -87,10 +195,21 @@ impl SupportedLanguage {
             Self::Go => tree_sitter_go::LANGUAGE.into(),
             Self::Rust => tree_sitter_rust::LANGUAGE.into(),
             Self::C => tree_sitter_c::LANGUAGE.into(),
-            // Swift disabled - tree-sitter ABI version incompatibility
-            Self::Swift => panic!("Swift parsing is currently disabled"),
-            // Discovery-only languages - no tree-sitter parsing
-            Self::Json | Self::Yaml | Self::Markdown | Self::Html | Self::Css | Self::Plist | Self::Shell => {
+            Self::Cpp => tree_sitter_cpp::LANGUAGE.into(),
+            Self::Swift => super::grammars::swift(),
+            Self::Java => tree_sitter_java::LANGUAGE.into(),
+            Self::CSharp => tree_sitter_c_sharp::LANGUAGE.into(),
+            Self::Ruby => tree_sitter_ruby::LANGUAGE.into(),
+            Self::Php => super::grammars::php(),
+            Self::Shell => tree_sitter_bash::LANGUAGE.into(),
+            Self::Html => tree_sitter_html::LANGUAGE.into(),
+            Self::Css => tree_sitter_css::LANGUAGE.into(),
+            // Discovery-only languages, Kotlin, Markdown and components - no grammar of their own
+            Self::Kotlin
+            | Self::Markdown
+            | Self::Vue
+            | Self::Svelte
+            | Self::Json | Self::Yaml | Self::Toml | Self::MsBuild | Self::Plist | Self::Image => {
                 panic!("Language {} does not support tree-sitter parsing", self.as_str())
             }
         }
@@ -103,17 +222,82 @@ pub struct ParseResult {
     pub symbols: Vec<SymbolRecord>,
     pub imports: Vec<ImportInfo>,
     pub exports: Vec<ExportInfo>,
+    pub re_exports: Vec<ReExportInfo>,
+    pub calls: Vec<CallInfo>,
+    pub module_decls: Vec<ModuleDeclInfo>,
+    pub inheritance: Vec<InheritanceInfo>,
+    pub members: Vec<MemberInfo>,
+    pub type_refs: Vec<TypeRefInfo>,
+    /// Namespaces the file declares its types in, fully qualified (C#, PHP)
+    pub namespaces: Vec<String>,
+    /// Components rendered by the template of a Vue or Svelte file
+    pub components: Vec<ComponentUsageInfo>,
+    /// Files a Markdown document links to or mentions
+    pub links: Vec<DocLinkInfo>,
 }
 
 /// Information about an import statement
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ImportInfo {
     pub source: String,
     pub imported_names: Vec<String>,
     pub is_default: bool,
+    /// `#include <...>`: searched only on the include path, never next to the including file
+    pub is_system: bool,
+    /// How the module is loaded
+    pub style: ImportStyle,
+    /// When the dependency exists at all
+    pub usage: ImportUsage,
     pub line: i32,
 }
 
+/// How an imported module is loaded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImportStyle {
+    /// An import declaration, loaded before the importing module runs
+    #[default]
+    Static,
+    /// `import('./x')`: loaded on demand, usually a code-split point
+    Dynamic,
+    /// `require('./x')`
+    CommonJs,
+}
+
+impl ImportStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Static => "static",
+            Self::Dynamic => "dynamic",
+            Self::CommonJs => "commonjs",
+        }
+    }
+}
+
+/// When an import is in effect
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImportUsage {
+    /// Always loaded when the importing module runs
+    #[default]
+    Runtime,
+    /// Only needed by the type checker: `import type`, `if TYPE_CHECKING:`
+    TypeOnly,
+    /// Only compiled into tests: `#[cfg(test)]`
+    TestOnly,
+    /// Depends on a runtime check or build configuration: `try: import x`, `#[cfg(unix)]`
+    Conditional,
+}
+
+impl ImportUsage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Runtime => "runtime",
+            Self::TypeOnly => "type-only",
+            Self::TestOnly => "test-only",
+            Self::Conditional => "conditional",
+        }
+    }
+}
+
 /// Information about an export
 #[derive(Debug, Clone)]
 pub struct ExportInfo {
@@ -122,6 +306,134 @@ pub struct ExportInfo {
     pub line: i32,
 }
 
+/// An `export ... from` statement passing on names from another module
+#[derive(Debug, Clone)]
+pub struct ReExportInfo {
+    pub source: String,
+    /// (name in the source module, name exported here); empty for `export *`
+    pub names: Vec<(String, String)>,
+    /// `export * as ns from`: the whole module re-exported under one name
+    pub namespace: Option<String>,
+    pub line: i32,
+}
+
+impl ReExportInfo {
+    /// Names passed on, in the same notation as `ImportInfo::imported_names`
+    pub fn exported_names(&self) -> Vec<String> {
+        match &self.namespace {
+            Some(namespace) => vec![format!("* as {}", namespace)],
+            None if self.names.is_empty() => vec!["*".to_string()],
+            None => self
+                .names
+                .iter()
+                .map(|(name, alias)| {
+                    if name == alias {
+                        name.clone()
+                    } else {
+                        format!("{} as {}", name, alias)
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Information about a call site inside a function body
+#[derive(Debug, Clone)]
+pub struct CallInfo {
+    /// ID of the symbol whose body contains the call
+    pub caller_id: String,
+    /// Name of the called function, method or constructor
+    pub callee: String,
+    /// Receiver or path in front of the callee (`this`, `self`, `pkg`, `Type`)
+    pub qualifier: Option<String>,
+    pub line: i32,
+}
+
+/// A type naming a supertype it extends or implements
+#[derive(Debug, Clone)]
+pub struct InheritanceInfo {
+    /// ID of the inheriting symbol, when it is declared in this file
+    pub symbol_id: Option<String>,
+    /// Name of the inheriting type; `impl Trait for Type` can be far from `Type`
+    pub symbol_name: String,
+    /// Name of the supertype, without module path or type arguments
+    pub base: String,
+    /// "extends" or "implements"
+    pub kind: String,
+    pub line: i32,
+}
+
+/// A type named in a symbol's parameters, return type, fields or generic arguments
+#[derive(Debug, Clone)]
+pub struct TypeRefInfo {
+    /// ID of the symbol whose declaration names the type
+    pub symbol_id: String,
+    /// Name of the type, without module path or type arguments
+    pub name: String,
+    pub line: i32,
+}
+
+/// A member declared outside its type's body (Rust `impl` blocks, Go method
+/// receivers, Swift extensions); its parent is resolved by type name
+#[derive(Debug, Clone)]
+pub struct MemberInfo {
+    pub symbol_id: String,
+    /// Name of the type the member belongs to
+    pub owner: String,
+}
+
+/// A Rust `mod foo;` declaration that loads another file
+#[derive(Debug, Clone)]
+pub struct ModuleDeclInfo {
+    /// Module path within the declaring file, e.g. `["net", "socket"]` for
+    /// `mod socket;` inside an inline `mod net { }`
+    pub path: Vec<String>,
+    /// Value of a `#[path = "..."]` attribute on the declaration
+    pub path_attribute: Option<String>,
+    pub line: i32,
+}
+
+/// A component used as a tag in a Vue or Svelte template: `<UserCard>`,
+/// `<user-card>`
+#[derive(Debug, Clone)]
+pub struct ComponentUsageInfo {
+    /// The name the component is bound to, in PascalCase
+    pub name: String,
+    /// First line the component is used on
+    pub line: i32,
+}
+
+/// A file a Markdown document points at
+#[derive(Debug, Clone)]
+pub struct DocLinkInfo {
+    /// The path as written, without any `#fragment` or `?query`
+    pub target: String,
+    pub line: i32,
+    pub kind: DocLinkKind,
+}
+
+/// How a document points at a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocLinkKind {
+    /// `[text](path)`, `[id]: path`, `<a href>`
+    Link,
+    /// `![alt](path)`, `<img src>`
+    Image,
+    /// A path named in a fenced code block, which need not exist
+    Mention,
+}
+
+impl DocLinkKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Link => "link",
+            Self::Image => "image",
+            Self::Mention => "mention",
+        }
+    }
+}
+
 /// Thread-safe parser that manages Tree-sitter parsers for different languages
 pub struct Parser {
     parsers: Mutex<HashMap<SupportedLanguage, tree_sitter::Parser>>,
@@ -168,10 +480,34 @@ impl Parser {
         language: SupportedLanguage,
         source: &str,
     ) -> NexusResult<ParseResult> {
+        let mut result = ParseResult::default();
+
+        // No tree-sitter grammar: the Kotlin and Markdown extractors scan the source themselves
+        if language == SupportedLanguage::Kotlin {
+            super::extractors::kotlin::extract(file_id, source, &mut result);
+            return Ok(result);
+        }
+        if language == SupportedLanguage::Markdown {
+            super::extractors::markdown::extract(source, &mut result);
+            return Ok(result);
+        }
+
+        // Single-file components: the script blocks form one module, parsed in place
+        if matches!(language, SupportedLanguage::Vue | SupportedLanguage::Svelte) {
+            let script = super::extractors::sfc::script(source);
+            let script_language = if script.is_typescript {
+                SupportedLanguage::TypeScript
+            } else {
+                SupportedLanguage::JavaScript
+            };
+            let tree = self.parse(script_language, &script.source)?;
+            super::extractors::typescript::extract(file_id, &tree.root_node(), script.source.as_bytes(), &mut result);
+            super::extractors::sfc::extract_template(source, &mut result);
+            return Ok(result);
+        }
+
         let tree = self.parse(language, source)?;
         let root = tree.root_node();
-
-        let mut result = ParseResult::default();
         let source_bytes = source.as_bytes();
 
         // Use language-specific extractor
@@ -191,17 +527,45 @@ impl Parser {
             SupportedLanguage::C => {
                 super::extractors::c::extract(file_id, &root, source_bytes, &mut result);
             }
+            SupportedLanguage::Cpp => {
+                super::extractors::cpp::extract(file_id, &root, source_bytes, &mut result);
+            }
             SupportedLanguage::Swift => {
                 super::extractors::swift::extract(file_id, &root, source_bytes, &mut result);
             }
-            // Discovery-only languages - should never reach here (engine checks requires_parsing())
-            SupportedLanguage::Json
-            | SupportedLanguage::Yaml
+            SupportedLanguage::Java => {
+                super::extractors::java::extract(file_id, &root, source_bytes, &mut result);
+            }
+            SupportedLanguage::CSharp => {
+                super::extractors::csharp::extract(file_id, &root, source_bytes, &mut result);
+            }
+            SupportedLanguage::Ruby => {
+                super::extractors::ruby::extract(file_id, &root, source_bytes, &mut result);
+            }
+            SupportedLanguage::Php => {
+                super::extractors::php::extract(file_id, &root, source_bytes, &mut result);
+            }
+            SupportedLanguage::Shell => {
+                super::extractors::shell::extract(file_id, &root, source_bytes, &mut result);
+            }
+            SupportedLanguage::Html => {
+                super::extractors::html::extract(&root, source_bytes, &mut result);
+            }
+            SupportedLanguage::Css => {
+                super::extractors::css::extract(&root, source_bytes, &mut result);
+            }
+            // Kotlin, Markdown and components are extracted above; discovery-only languages
+            // should never reach here (engine checks requires_parsing())
+            SupportedLanguage::Kotlin
             | SupportedLanguage::Markdown
-            | SupportedLanguage::Html
-            | SupportedLanguage::Css
+            | SupportedLanguage::Vue
+            | SupportedLanguage::Svelte
+            | SupportedLanguage::Json
+            | SupportedLanguage::Yaml
+            | SupportedLanguage::Toml
+            | SupportedLanguage::MsBuild
             | SupportedLanguage::Plist
-            | SupportedLanguage::Shell => {
+            | SupportedLanguage::Image => {
                 // Return empty result - no symbols to extract
             }
         }
@@ -230,29 +594,62 @@ mod tests {
         assert_eq!(SupportedLanguage::from_extension("go"), Some(SupportedLanguage::Go));
         assert_eq!(SupportedLanguage::from_extension("rs"), Some(SupportedLanguage::Rust));
         assert_eq!(SupportedLanguage::from_extension("c"), Some(SupportedLanguage::C));
+        assert_eq!(SupportedLanguage::from_extension("cc"), Some(SupportedLanguage::Cpp));
+        assert_eq!(SupportedLanguage::from_extension("hpp"), Some(SupportedLanguage::Cpp));
         assert_eq!(SupportedLanguage::from_extension("swift"), Some(SupportedLanguage::Swift));
+        assert_eq!(SupportedLanguage::from_extension("java"), Some(SupportedLanguage::Java));
+        assert_eq!(SupportedLanguage::from_extension("kt"), Some(SupportedLanguage::Kotlin));
+        assert_eq!(SupportedLanguage::from_extension("cs"), Some(SupportedLanguage::CSharp));
+        assert_eq!(SupportedLanguage::from_extension("rb"), Some(SupportedLanguage::Ruby));
+        assert_eq!(SupportedLanguage::from_extension("php"), Some(SupportedLanguage::Php));
+        assert_eq!(SupportedLanguage::from_extension("vue"), Some(SupportedLanguage::Vue));
+        assert_eq!(SupportedLanguage::from_extension("svelte"), Some(SupportedLanguage::Svelte));
+        assert_eq!(SupportedLanguage::from_extension("sh"), Some(SupportedLanguage::Shell));
+        assert_eq!(SupportedLanguage::from_extension("md"), Some(SupportedLanguage::Markdown));
         // Discovery-only languages
         assert_eq!(SupportedLanguage::from_extension("json"), Some(SupportedLanguage::Json));
         assert_eq!(SupportedLanguage::from_extension("yaml"), Some(SupportedLanguage::Yaml));
         assert_eq!(SupportedLanguage::from_extension("yml"), Some(SupportedLanguage::Yaml));
-        assert_eq!(SupportedLanguage::from_extension("md"), Some(SupportedLanguage::Markdown));
+        assert_eq!(SupportedLanguage::from_extension("toml"), Some(SupportedLanguage::Toml));
+        assert_eq!(SupportedLanguage::from_extension("csproj"), Some(SupportedLanguage::MsBuild));
         assert_eq!(SupportedLanguage::from_extension("html"), Some(SupportedLanguage::Html));
         assert_eq!(SupportedLanguage::from_extension("css"), Some(SupportedLanguage::Css));
         assert_eq!(SupportedLanguage::from_extension("plist"), Some(SupportedLanguage::Plist));
-        assert_eq!(SupportedLanguage::from_extension("sh"), Some(SupportedLanguage::Shell));
+        assert_eq!(SupportedLanguage::from_extension("svg"), Some(SupportedLanguage::Image));
         // Unknown
         assert_eq!(SupportedLanguage::from_extension("unknown"), None);
     }
 
+    #[test]
+    fn test_language_from_shebang() {
+        assert_eq!(SupportedLanguage::from_shebang("#!/bin/bash\necho hi\n"), Some(SupportedLanguage::Shell));
+        assert_eq!(SupportedLanguage::from_shebang("#!/usr/bin/env -S zsh -f\n"), Some(SupportedLanguage::Shell));
+        assert_eq!(SupportedLanguage::from_shebang("#!/usr/bin/env python3\n"), None);
+        assert_eq!(SupportedLanguage::from_shebang("echo no shebang\n"), None);
+    }
+
+    #[test]
+    fn test_header_language() {
+        let c_header = "#include <stdio.h>\n/* a class of its own */\n#ifdef __cplusplus\nextern \"C\" {\n#endif\nint add(int a, int b); // std::plus\n";
+        assert_eq!(SupportedLanguage::for_header(c_header), SupportedLanguage::C);
+        assert_eq!(SupportedLanguage::for_header("#include <vector>\n"), SupportedLanguage::Cpp);
+        assert_eq!(SupportedLanguage::for_header("namespace geo {\n}\n"), SupportedLanguage::Cpp);
+        assert_eq!(SupportedLanguage::for_header("/*\n * notes\n */\nclass Shape {\npublic:\n};\n"), SupportedLanguage::Cpp);
+        assert_eq!(SupportedLanguage::for_header("std::string name();\n"), SupportedLanguage::Cpp);
+    }
+
     #[test]
     fn test_requires_parsing() {
         assert!(SupportedLanguage::TypeScript.requires_parsing());
         assert!(SupportedLanguage::JavaScript.requires_parsing());
         assert!(SupportedLanguage::Python.requires_parsing());
-        assert!(!SupportedLanguage::Swift.requires_parsing()); // Disabled
+        assert!(SupportedLanguage::Swift.requires_parsing());
+        assert!(SupportedLanguage::Kotlin.requires_parsing());
+        assert!(SupportedLanguage::CSharp.requires_parsing());
+        assert!(SupportedLanguage::Shell.requires_parsing());
         assert!(!SupportedLanguage::Json.requires_parsing());
         assert!(!SupportedLanguage::Yaml.requires_parsing());
-        assert!(!SupportedLanguage::Markdown.requires_parsing());
+        assert!(SupportedLanguage::Markdown.requires_parsing());
     }
 
     #[test]
@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use super::{normalize_path, ImportResolver};
+use crate::analysis::parser::ImportInfo;
+use crate::storage::FileRecord;
+
+/// Where build systems usually leave compile_commands.json
+const COMPILE_COMMANDS_LOCATIONS: &[&str] = &[
+    "compile_commands.json",
+    "build/compile_commands.json",
+    "out/compile_commands.json",
+    "cmake-build-debug/compile_commands.json",
+    "cmake-build-release/compile_commands.json",
+];
+
+/// Include directories of one translation unit, project-relative
+#[derive(Debug, Default, Clone)]
+struct SearchPath {
+    /// `-iquote`: searched for `#include "..."` only
+    quote: Vec<PathBuf>,
+    /// `-I` then `-isystem`: searched for both include forms
+    angle: Vec<PathBuf>,
+}
+
+impl SearchPath {
+    fn extend(&mut self, other: &SearchPath) {
+        for dir in &other.quote {
+            if !self.quote.contains(dir) {
+                self.quote.push(dir.clone());
+            }
+        }
+        for dir in &other.angle {
+            if !self.angle.contains(dir) {
+                self.angle.push(dir.clone());
+            }
+        }
+    }
+}
+
+/// Include search paths for C and C++ files, from compile_commands.json and
+/// the include directories configured for the project
+#[derive(Debug, Default)]
+pub struct CIncludes<'a> {
+    /// Source file -> include directories it was compiled with
+    per_file: HashMap<PathBuf, SearchPath>,
+    /// Every directory in the compilation database, for headers and files it does not list
+    shared: SearchPath,
+    /// Include directories configured for the project, searched last
+    configured: Vec<PathBuf>,
+    /// Header file name -> project-relative paths and IDs of files with that name
+    by_name: HashMap<&'a str, Vec<(&'a str, &'a str)>>,
+}
+
+impl<'a> CIncludes<'a> {
+    /// Read every compilation database in the usual build locations
+    pub fn load(project_path: &Path, files: &'a [FileRecord]) -> Self {
+        let mut includes = Self::default();
+
+        for file in files.iter().filter(|f| f.language == "c" || f.language == "cpp") {
+            includes
+                .by_name
+                .entry(file.name.as_str())
+                .or_default()
+                .push((file.path.as_str(), file.id.as_str()));
+        }
+        if includes.by_name.is_empty() {
+            return includes;
+        }
+
+        for location in COMPILE_COMMANDS_LOCATIONS {
+            let Ok(content) = fs::read_to_string(project_path.join(location)) else { continue };
+            let Ok(Value::Array(entries)) = serde_json::from_str::<Value>(&content) else {
+                tracing::warn!("Ignoring malformed {}", location);
+                continue;
+            };
+            for entry in &entries {
+                if let Some((file, search)) = parse_compile_command(project_path, entry) {
+                    includes.shared.extend(&search);
+                    includes.per_file.entry(file).or_default().extend(&search);
+                }
+            }
+        }
+
+        includes
+    }
+
+    /// Add include directories configured for the project (absolute or project-relative)
+    pub fn add_configured(&mut self, project_path: &Path, dirs: &[PathBuf]) {
+        self.configured
+            .extend(dirs.iter().filter_map(|dir| project_relative(project_path, dir)));
+    }
+}
+
+impl<'a> ImportResolver<'a> {
+    /// Resolve an `#include`. Quoted includes are looked up next to the including
+    /// file first, then on the include path, then by matching the path suffix
+    /// anywhere in the project. System includes are only looked up on the
+    /// include path; if they are not found there they are external headers.
+    pub(super) fn resolve_c(&self, import: &ImportInfo, current_path: &str) -> Option<&'a str> {
+        let includes = &self.c;
+        let current = Path::new(current_path);
+        let search = includes.per_file.get(current).unwrap_or(&includes.shared);
+
+        let mut dirs: Vec<&Path> = Vec::new();
+        if !import.is_system {
+            dirs.push(current.parent().unwrap_or(Path::new("")));
+            dirs.extend(search.quote.iter().map(PathBuf::as_path));
+        }
+        dirs.extend(search.angle.iter().map(PathBuf::as_path));
+        dirs.extend(includes.configured.iter().map(PathBuf::as_path));
+
+        let found = dirs
+            .iter()
+            .find_map(|dir| self.file_id(&normalize_path(&dir.join(&import.source))));
+        if found.is_some() || import.is_system {
+            return found;
+        }
+
+        // `#include "net/socket.h"` matches `*/net/socket.h`, preferring the
+        // candidate closest to the including file
+        let include = Path::new(&import.source);
+        let name = include.file_name()?.to_str()?;
+        includes
+            .by_name
+            .get(name)?
+            .iter()
+            .filter(|(path, _)| Path::new(path).ends_with(include))
+            .max_by_key(|(path, _)| common_prefix_len(Path::new(path), current))
+            .map(|(_, id)| *id)
+    }
+}
+
+/// The source file and include directories of one compilation database entry
+fn parse_compile_command(project_path: &Path, entry: &Value) -> Option<(PathBuf, SearchPath)> {
+    let directory = Path::new(entry.get("directory")?.as_str()?);
+    let file = project_relative(project_path, &directory.join(entry.get("file")?.as_str()?))?;
+
+    let arguments = match entry.get("arguments").and_then(Value::as_array) {
+        Some(arguments) => arguments
+            .iter()
+            .filter_map(|a| a.as_str().map(str::to_string))
+            .collect(),
+        None => split_command(entry.get("command")?.as_str()?),
+    };
+
+    let mut search = SearchPath::default();
+    let mut system = Vec::new();
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        for (flag, target) in [("-iquote", &mut search.quote), ("-isystem", &mut system), ("-I", &mut search.angle)] {
+            let Some(value) = argument.strip_prefix(flag) else { continue };
+            let value = if value.is_empty() { arguments.next().cloned() } else { Some(value.to_string()) };
+            if let Some(dir) = value.and_then(|v| project_relative(project_path, &directory.join(v))) {
+                target.push(dir);
+            }
+            break;
+        }
+    }
+    // -isystem directories are searched after all -I directories
+    search.angle.extend(system);
+
+    Some((file, search))
+}
+
+/// Split a shell command line into arguments, honouring quotes and backslash escapes
+fn split_command(command: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', Some('\'')) => current.get_or_insert_with(String::new).push(c),
+            ('\\', _) => {
+                if let Some(escaped) = chars.next() {
+                    current.get_or_insert_with(String::new).push(escaped);
+                }
+            }
+            (q @ ('"' | '\''), None) => {
+                quote = Some(q);
+                current.get_or_insert_with(String::new);
+            }
+            (q, Some(open)) if q == open => quote = None,
+            (c, None) if c.is_whitespace() => arguments.extend(current.take()),
+            (c, _) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    arguments.extend(current);
+    arguments
+}
+
+/// `path` relative to the project root, or None if it lies outside the project
+fn project_relative(project_path: &Path, path: &Path) -> Option<PathBuf> {
+    if path.is_relative() {
+        return Some(normalize_path(path)).filter(|p| !p.starts_with(".."));
+    }
+    let path = normalize_path(path);
+    path.strip_prefix(project_path)
+        .ok()
+        .or_else(|| path.strip_prefix(project_path.canonicalize().ok()?).ok())
+        .map(Path::to_path_buf)
+}
+
+/// Number of leading components two paths share
+fn common_prefix_len(a: &Path, b: &Path) -> usize {
+    a.components()
+        .zip(b.components())
+        .take_while(|(x, y)| x == y)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::resolvers::file_record;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    fn include(source: &str, is_system: bool) -> ImportInfo {
+        ImportInfo {
+            source: source.to_string(),
+            is_system,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_split_command() {
+        assert_eq!(
+            split_command(r#"cc -I"my dir" -DNAME=\"x\" 'a b.c'"#),
+            vec!["cc", "-Imy dir", "-DNAME=\"x\"", "a b.c"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_with_compile_commands() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().display().to_string();
+        let commands = serde_json::json!([
+            {
+                "directory": format!("{}/build", root),
+                "command": "cc -iquote ../src -I ../include -isystem ../third_party -c ../src/main.c",
+                "file": "../src/main.c"
+            },
+            {
+                "directory": root,
+                "arguments": ["cc", "-Ilib/include", "-I/usr/include", "-c", "lib/util.c"],
+                "file": "lib/util.c"
+            }
+        ]);
+        fs::create_dir_all(dir.path().join("build")).unwrap();
+        fs::write(dir.path().join("build/compile_commands.json"), commands.to_string()).unwrap();
+
+        let files = vec![
+            file_record("src/main.c"),
+            file_record("src/config.h"),
+            file_record("include/net/socket.h"),
+            file_record("third_party/zlib.h"),
+            file_record("lib/util.c"),
+            file_record("lib/include/util.h"),
+            file_record("lib/include/config.h"),
+        ];
+        let resolver = ImportResolver::new(dir.path(), &files, &HashMap::new());
+
+        assert_eq!(resolver.resolve_c(&include("net/socket.h", false), "src/main.c"), Some("include/net/socket.h"));
+        assert_eq!(resolver.resolve_c(&include("zlib.h", true), "src/main.c"), Some("third_party/zlib.h"));
+        assert_eq!(resolver.resolve_c(&include("config.h", false), "src/main.c"), Some("src/config.h"));
+        assert_eq!(resolver.resolve_c(&include("config.h", true), "lib/util.c"), Some("lib/include/config.h"));
+        // -iquote directories do not apply to system includes
+        assert_eq!(resolver.resolve_c(&include("config.h", true), "src/main.c"), None);
+        assert_eq!(resolver.resolve_c(&include("stdio.h", true), "src/main.c"), None);
+    }
+
+    #[test]
+    fn test_resolve_without_compile_commands() {
+        let dir = tempdir().unwrap();
+        let files = vec![
+            file_record("app/main.c"),
+            file_record("app/net/socket.h"),
+            file_record("vendor/net/socket.h"),
+            file_record("vendor/socket.h"),
+            file_record("include/api.h"),
+        ];
+        let resolver = ImportResolver::new(dir.path(), &files, &HashMap::new())
+            .with_include_dirs(&[dir.path().join("include")]);
+
+        assert_eq!(resolver.resolve_c(&include("net/socket.h", false), "app/main.c"), Some("app/net/socket.h"));
+        assert_eq!(resolver.resolve_c(&include("net/socket.h", false), "vendor/lib/zip.c"), Some("vendor/net/socket.h"));
+        assert_eq!(resolver.resolve_c(&include("api.h", true), "app/main.c"), Some("include/api.h"));
+        // System includes are never guessed by name
+        assert_eq!(resolver.resolve_c(&include("socket.h", true), "app/main.c"), None);
+    }
+}
@@ -0,0 +1,455 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use super::typescript::extends_paths;
+use super::{normalize_path, parse_jsonc, ImportResolver};
+use crate::analysis::extractors::is_analyzed_file;
+use crate::storage::FileRecord;
+
+/// Extensions of the files a tsconfig `include` pattern picks up
+const TS_SOURCE_EXTENSIONS: &[&str] = &["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs"];
+
+/// Files an action directory named by `uses: ./path` is defined in
+const ACTION_FILES: &[&str] = &["action.yml", "action.yaml"];
+
+/// A file a configuration points at, and the key naming it
+#[derive(Debug, Clone, PartialEq)]
+struct ConfigReference {
+    /// Project-relative path
+    path: PathBuf,
+    key: String,
+}
+
+impl<'a> ImportResolver<'a> {
+    /// The files a build, tool or CI configuration points at, with the key that
+    /// names each: package.json entry points and scripts, tsconfig `extends`
+    /// and `include`, Cargo.toml targets, tauri.conf.json `frontendDist` and
+    /// GitHub Actions `uses: ./...`. Files that are not configurations give
+    /// nothing.
+    pub fn resolve_config(&self, file: &FileRecord) -> Vec<(&'a str, String)> {
+        let path = Path::new(&file.path);
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let name = file.name.as_str();
+
+        let is_tsconfig = (name.starts_with("tsconfig") && name.ends_with(".json")) || name == "jsconfig.json";
+        let is_workflow = path.starts_with(".github/workflows") && matches!(file.language.as_str(), "yaml");
+        if !(is_tsconfig
+            || is_workflow
+            || ACTION_FILES.contains(&name)
+            || matches!(name, "package.json" | "Cargo.toml" | "tauri.conf.json"))
+        {
+            return Vec::new();
+        }
+
+        let Ok(content) = fs::read_to_string(self.project_path.join(path)) else { return Vec::new() };
+
+        let references = match name {
+            "package.json" => package_json_references(&content, dir),
+            "Cargo.toml" => cargo_references(&content, dir),
+            "tauri.conf.json" => {
+                return tauri_references(&content, dir)
+                    .into_iter()
+                    .filter_map(|r| {
+                        // `frontendDist` names the directory the built page is served from
+                        let id = self.file_id(&r.path).or_else(|| self.file_id(&r.path.join("index.html")))?;
+                        Some((id, r.key))
+                    })
+                    .collect();
+            }
+            _ if is_tsconfig => return self.tsconfig_targets(&content, path),
+            _ => {
+                return workflow_references(&content)
+                    .into_iter()
+                    .filter_map(|r| {
+                        let id = self.file_id(&r.path).or_else(|| {
+                            ACTION_FILES.iter().find_map(|action| self.file_id(&r.path.join(action)))
+                        })?;
+                        Some((id, r.key))
+                    })
+                    .collect();
+            }
+        };
+
+        references
+            .into_iter()
+            .filter_map(|r| Some((self.file_id(&r.path)?, r.key)))
+            .collect()
+    }
+
+    /// Configs a tsconfig extends, and the sources its `files` and `include`
+    /// patterns (less `exclude`) pick up
+    fn tsconfig_targets(&self, content: &str, path: &Path) -> Vec<(&'a str, String)> {
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let json = match parse_jsonc(content) {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::warn!("Failed to parse {}: {}", path.display(), e);
+                return Vec::new();
+            }
+        };
+
+        let mut targets: Vec<(&'a str, String)> = extends_paths(&json["extends"], dir)
+            .iter()
+            .filter_map(|path| Some((self.file_id(path)?, "extends".to_string())))
+            .collect();
+
+        let strings = |key: &str| -> Vec<&str> {
+            json[key].as_array().into_iter().flatten().filter_map(Value::as_str).collect()
+        };
+        for file in strings("files") {
+            if let Some(id) = self.file_id(&normalize_path(&dir.join(file))) {
+                targets.push((id, "files".to_string()));
+            }
+        }
+
+        let patterns = |key: &str| -> Vec<String> {
+            strings(key)
+                .into_iter()
+                .map(|pattern| include_pattern(&normalize_path(&dir.join(pattern))))
+                .collect()
+        };
+        let include = patterns("include");
+        let exclude = patterns("exclude");
+        if !include.is_empty() {
+            let mut included: Vec<(&'a str, &'a str)> = self
+                .path_to_id
+                .iter()
+                .filter(|(path, _)| {
+                    Path::new(path)
+                        .extension()
+                        .and_then(|ext| ext.to_str())
+                        .is_some_and(|ext| TS_SOURCE_EXTENSIONS.contains(&ext))
+                })
+                .filter(|(path, _)| include.iter().any(|pattern| glob_matches(pattern, path)))
+                .filter(|(path, _)| !exclude.iter().any(|pattern| glob_matches(pattern, path)))
+                .map(|(&path, &id)| (path, id))
+                .collect();
+            included.sort();
+            targets.extend(included.into_iter().map(|(_, id)| (id, "include".to_string())));
+        }
+
+        targets
+    }
+}
+
+/// `main`, `module`, `bin`, `exports` and the files `scripts` run
+fn package_json_references(content: &str, dir: &Path) -> Vec<ConfigReference> {
+    let json: Value = match serde_json::from_str(content) {
+        Ok(json) => json,
+        Err(e) => {
+            tracing::warn!("Failed to parse {}: {}", dir.join("package.json").display(), e);
+            return Vec::new();
+        }
+    };
+    let mut references = Vec::new();
+    let mut add = |path: &str, key: String| {
+        references.push(ConfigReference {
+            path: normalize_path(&dir.join(path)),
+            key,
+        });
+    };
+
+    for key in ["main", "module"] {
+        if let Some(path) = json[key].as_str() {
+            add(path, key.to_string());
+        }
+    }
+
+    // "bin": "cli.js" or { "name": "cli.js" }
+    match &json["bin"] {
+        Value::String(path) => add(path, "bin".to_string()),
+        Value::Object(bins) => {
+            for (name, path) in bins {
+                if let Some(path) = path.as_str() {
+                    add(path, format!("bin.{}", name));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    // Subpaths and conditions nest to any depth; the leaves are files
+    let mut exports = vec![&json["exports"]];
+    while let Some(value) = exports.pop() {
+        match value {
+            Value::String(path) if !path.contains('*') => add(path, "exports".to_string()),
+            Value::Object(entries) => exports.extend(entries.values().rev()),
+            Value::Array(items) => exports.extend(items.iter().rev()),
+            _ => {}
+        }
+    }
+
+    if let Some(scripts) = json["scripts"].as_object() {
+        for (name, command) in scripts {
+            for path in command.as_str().map(command_paths).unwrap_or_default() {
+                add(path, format!("scripts.{}", name));
+            }
+        }
+    }
+
+    references
+}
+
+/// Library, binary, example, test and bench targets and the build script,
+/// explicit or at Cargo's default locations
+fn cargo_references(content: &str, dir: &Path) -> Vec<ConfigReference> {
+    let manifest: toml::Table = match content.parse() {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            tracing::warn!("Failed to parse {}: {}", dir.join("Cargo.toml").display(), e);
+            return Vec::new();
+        }
+    };
+    let Some(package) = manifest.get("package") else { return Vec::new() };
+
+    let mut references = Vec::new();
+    let mut add = |path: &str, key: String| {
+        references.push(ConfigReference {
+            path: normalize_path(&dir.join(path)),
+            key,
+        });
+    };
+
+    let lib_path = manifest.get("lib").and_then(|lib| lib.get("path")).and_then(|p| p.as_str());
+    add(lib_path.unwrap_or("src/lib.rs"), "lib".to_string());
+
+    let mut has_bin = false;
+    for kind in ["bin", "example", "test", "bench"] {
+        let targets = manifest.get(kind).and_then(|t| t.as_array()).into_iter().flatten();
+        for target in targets {
+            let Some(path) = target.get("path").and_then(|p| p.as_str()) else { continue };
+            let name = target.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+            has_bin |= kind == "bin";
+            add(path, format!("{}.{}", kind, name).trim_end_matches('.').to_string());
+        }
+    }
+    if !has_bin {
+        add("src/main.rs", "bin".to_string());
+    }
+
+    // `build = "build.rs"`, `build = false`, or build.rs when not set
+    match package.get("build") {
+        Some(toml::Value::String(path)) => add(path, "build".to_string()),
+        Some(_) => {}
+        None => add("build.rs", "build".to_string()),
+    }
+
+    references
+}
+
+/// `build.frontendDist` (Tauri 2) or `build.distDir` (Tauri 1), unless a dev server URL
+fn tauri_references(content: &str, dir: &Path) -> Vec<ConfigReference> {
+    let Ok(json) = parse_jsonc(content) else { return Vec::new() };
+
+    ["frontendDist", "distDir"]
+        .iter()
+        .filter_map(|key| Some((key, json["build"][key].as_str()?)))
+        .filter(|(_, path)| !path.contains("://"))
+        .map(|(key, path)| ConfigReference {
+            path: normalize_path(&dir.join(path)),
+            key: format!("build.{}", key),
+        })
+        .collect()
+}
+
+/// Local actions and reusable workflows: `uses: ./.github/actions/setup`.
+/// Paths are relative to the repository root, taken to be the project root.
+fn workflow_references(content: &str) -> Vec<ConfigReference> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let value = line.trim_start().trim_start_matches("- ").trim_start().strip_prefix("uses:")?;
+            let value = value.split(" #").next().unwrap_or_default().trim();
+            let path = value.trim_matches(|c| c == '"' || c == '\'').strip_prefix("./")?;
+            Some(ConfigReference {
+                path: normalize_path(Path::new(path)),
+                key: "uses".to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Paths of analysed files a script command runs or passes: `node
+/// scripts/build.mjs`, `vite --config web/vite.config.ts`
+fn command_paths(command: &str) -> Vec<&str> {
+    command
+        .split(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '=' | ';' | '&' | '|' | '(' | ')'))
+        .filter(|word| !word.starts_with('-') && !word.contains(['$', '*']) && is_analyzed_file(word))
+        .collect()
+}
+
+/// A tsconfig pattern as a glob: a pattern without wildcards or extension
+/// names a directory, and covers everything below it
+fn include_pattern(pattern: &Path) -> String {
+    let pattern = pattern.to_string_lossy();
+    let last = pattern.rsplit('/').next().unwrap_or_default();
+    if last.contains(['*', '?', '.']) {
+        pattern.to_string()
+    } else if pattern.is_empty() {
+        "**/*".to_string()
+    } else {
+        format!("{}/**/*", pattern)
+    }
+}
+
+/// Match a project-relative path against a glob of `*` and `?`, which stay
+/// within a path segment, and `**`, which spans any number of segments
+fn glob_matches(pattern: &str, path: &str) -> bool {
+    fn matches(pattern: &[&str], path: &[&str]) -> bool {
+        match pattern.split_first() {
+            None => path.is_empty(),
+            Some((&"**", rest)) => (0..=path.len()).any(|skip| matches(rest, &path[skip..])),
+            Some((segment, rest)) => {
+                path.split_first().is_some_and(|(name, path)| segment_matches(segment, name) && matches(rest, path))
+            }
+        }
+    }
+
+    fn segment_matches(pattern: &str, name: &str) -> bool {
+        match pattern.chars().next() {
+            None => name.is_empty(),
+            Some('*') => (0..=name.len())
+                .filter(|&i| name.is_char_boundary(i))
+                .any(|i| segment_matches(&pattern[1..], &name[i..])),
+            Some(c) => {
+                let mut chars = name.chars();
+                chars.next().is_some_and(|first| (c == '?' || c == first) && segment_matches(&pattern[c.len_utf8()..], chars.as_str()))
+            }
+        }
+    }
+
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+    matches(&pattern, &path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::resolvers::file_record;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("src/**/*", "src/a/b/c.ts"));
+        assert!(glob_matches("src/**/*.ts", "src/c.ts"));
+        assert!(glob_matches("src/*.test.ts", "src/app.test.ts"));
+        assert!(!glob_matches("src/*.ts", "src/a/app.ts"));
+        assert!(glob_matches("**/*.spec.?s", "a/b.spec.js"));
+        assert!(!glob_matches("lib/**/*", "src/lib.ts"));
+    }
+
+    #[test]
+    fn test_command_paths() {
+        assert_eq!(
+            command_paths("node scripts/build.mjs && tsx watch src/server.ts --env-file=.env"),
+            vec!["scripts/build.mjs", "src/server.ts"]
+        );
+        assert_eq!(command_paths("vite build --config=web/vite.config.ts"), vec!["web/vite.config.ts"]);
+        assert!(command_paths("eslint . --ext .ts && prettier --check src").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_config_references() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("app/src-tauri/src")).unwrap();
+        fs::create_dir_all(dir.path().join(".github/workflows")).unwrap();
+        fs::write(dir.path().join("app/package.json"), r#"{
+            "main": "./dist/index.js",
+            "module": "src/index.ts",
+            "bin": { "app-cli": "bin/cli.js" },
+            "exports": { ".": { "import": "./src/index.ts", "types": "./src/types.ts" }, "./utils/*": "./src/utils/*.ts" },
+            "scripts": { "build": "node scripts/build.mjs", "lint": "eslint src" }
+        }"#).unwrap();
+        fs::write(dir.path().join("app/tsconfig.json"), r#"{
+            // Shared options
+            "extends": "./tsconfig.base.json",
+            "files": ["types/global.d.ts"],
+            "include": ["src", "scripts/*.mjs"],
+            "exclude": ["src/**/*.test.ts"],
+        }"#).unwrap();
+        fs::write(dir.path().join("app/src-tauri/Cargo.toml"), r#"
+            [package]
+            name = "app"
+
+            [[bin]]
+            name = "server"
+            path = "src/server.rs"
+        "#).unwrap();
+        fs::write(dir.path().join("app/src-tauri/tauri.conf.json"), r#"{ "build": { "frontendDist": "../public", "devUrl": "http://localhost:1420" } }"#).unwrap();
+        fs::write(dir.path().join(".github/workflows/ci.yml"), "jobs:\n  test:\n    steps:\n      - uses: actions/checkout@v4\n      - uses: ./.github/actions/setup # local\n  deploy:\n    uses: './.github/workflows/deploy.yml'\n").unwrap();
+
+        let files: Vec<FileRecord> = [
+            "app/package.json",
+            "app/tsconfig.json",
+            "app/tsconfig.base.json",
+            "app/src/index.ts",
+            "app/src/types.ts",
+            "app/src/index.test.ts",
+            "app/types/global.d.ts",
+            "app/bin/cli.js",
+            "app/scripts/build.mjs",
+            "app/public/index.html",
+            "app/src-tauri/Cargo.toml",
+            "app/src-tauri/tauri.conf.json",
+            "app/src-tauri/src/lib.rs",
+            "app/src-tauri/src/server.rs",
+            "app/src-tauri/build.rs",
+            ".github/workflows/ci.yml",
+            ".github/workflows/deploy.yml",
+            ".github/actions/setup/action.yml",
+        ]
+        .iter()
+        .map(|path| file_record(path))
+        .collect();
+        let resolver = ImportResolver::new(dir.path(), &files, &HashMap::new());
+        let references = |path: &str| {
+            let file = files.iter().find(|f| f.path == path).unwrap();
+            resolver.resolve_config(file)
+        };
+        let pairs = |expected: &[(&'static str, &str)]| -> Vec<(&'static str, String)> {
+            expected.iter().map(|&(id, key)| (id, key.to_string())).collect()
+        };
+
+        assert_eq!(
+            references("app/package.json"),
+            pairs(&[
+                ("app/src/index.ts", "module"),
+                ("app/bin/cli.js", "bin.app-cli"),
+                ("app/src/index.ts", "exports"),
+                ("app/src/types.ts", "exports"),
+                ("app/scripts/build.mjs", "scripts.build"),
+            ])
+        );
+        assert_eq!(
+            references("app/tsconfig.json"),
+            pairs(&[
+                ("app/tsconfig.base.json", "extends"),
+                ("app/types/global.d.ts", "files"),
+                ("app/scripts/build.mjs", "include"),
+                ("app/src/index.ts", "include"),
+                ("app/src/types.ts", "include"),
+            ])
+        );
+        assert_eq!(
+            references("app/src-tauri/Cargo.toml"),
+            pairs(&[
+                ("app/src-tauri/src/lib.rs", "lib"),
+                ("app/src-tauri/src/server.rs", "bin.server"),
+                ("app/src-tauri/build.rs", "build"),
+            ])
+        );
+        assert_eq!(
+            references("app/src-tauri/tauri.conf.json"),
+            pairs(&[("app/public/index.html", "build.frontendDist")])
+        );
+        assert_eq!(
+            references(".github/workflows/ci.yml"),
+            pairs(&[(".github/actions/setup/action.yml", "uses"), (".github/workflows/deploy.yml", "uses")])
+        );
+        assert!(references("app/src/index.ts").is_empty());
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{normalize_path, ImportResolver};
+use crate::analysis::parser::{ImportInfo, ParseResult};
+use crate::storage::{FileRecord, SymbolRecord};
+
+/// Symbol kinds a `using static` or alias directive can name
+const TYPE_KINDS: &[&str] = &["class", "struct", "interface", "enum", "type"];
+
+/// C# files by the namespaces they declare
+#[derive(Debug, Default)]
+pub struct CSharpNamespaces<'a> {
+    /// Fully qualified namespace -> files declaring types in it
+    namespaces: HashMap<String, Vec<&'a str>>,
+    /// File ID -> names of the types it declares
+    types: HashMap<&'a str, Vec<String>>,
+}
+
+impl<'a> CSharpNamespaces<'a> {
+    pub fn load(files: &'a [FileRecord], parsed_files: &HashMap<String, ParseResult>) -> Self {
+        let mut this = Self::default();
+
+        for file in files.iter().filter(|f| f.language == "csharp") {
+            this.types.insert(file.id.as_str(), Vec::new());
+            let Some(parsed) = parsed_files.get(&file.id) else { continue };
+            for namespace in &parsed.namespaces {
+                this.namespaces.entry(namespace.clone()).or_default().push(file.id.as_str());
+            }
+        }
+
+        this
+    }
+
+    /// Record the types each file declares
+    pub fn add_declarations(&mut self, symbols: &[SymbolRecord]) {
+        for symbol in symbols.iter().filter(|s| TYPE_KINDS.contains(&s.kind.as_str())) {
+            if let Some(types) = self.types.get_mut(symbol.file_id.as_str()) {
+                types.push(symbol.name.clone());
+            }
+        }
+    }
+}
+
+impl<'a> ImportResolver<'a> {
+    /// Resolve a `using` directive: every file declaring the namespace, or for
+    /// `using static A.B.Type` and aliases of a type, the file declaring `Type`
+    pub(super) fn resolve_csharp(&self, import: &ImportInfo) -> Vec<&'a str> {
+        if let Some(files) = self.csharp.namespaces.get(&import.source) {
+            return files.clone();
+        }
+
+        let Some((namespace, type_name)) = import.source.rsplit_once('.') else { return Vec::new() };
+        self.csharp
+            .namespaces
+            .get(namespace)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|id| self.csharp.types.get(id).is_some_and(|types| types.iter().any(|t| t == type_name)))
+            .collect()
+    }
+}
+
+/// An MSBuild project (`.csproj`) and the projects it references
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CSharpProject {
+    /// Project file name without extension, the default assembly name
+    pub name: String,
+    /// Project-relative path of the `.csproj` file
+    pub path: PathBuf,
+    /// Project-relative paths of the `<ProjectReference>`d `.csproj` files
+    pub references: Vec<PathBuf>,
+}
+
+/// Find the `.csproj` file nearest above every C# file, and the projects
+/// those reference, in the order they are found
+pub fn load_projects(project_path: &Path, files: &[FileRecord]) -> Vec<CSharpProject> {
+    let mut pending: Vec<PathBuf> = Vec::new();
+    let mut searched: HashSet<&Path> = HashSet::new();
+
+    for file in files.iter().filter(|f| f.language == "csharp") {
+        let dir = Path::new(&file.path).parent().unwrap_or(Path::new(""));
+        for ancestor in dir.ancestors() {
+            if !searched.insert(ancestor) {
+                break;
+            }
+            let Ok(entries) = fs::read_dir(project_path.join(ancestor)) else { continue };
+            let mut found: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "csproj"))
+                .filter_map(|path| path.file_name().map(|name| ancestor.join(name)))
+                .collect();
+            if !found.is_empty() {
+                found.sort();
+                pending.extend(found);
+                break;
+            }
+        }
+    }
+
+    let mut projects: Vec<CSharpProject> = Vec::new();
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    while let Some(path) = pending.pop() {
+        if !seen.insert(path.clone()) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(project_path.join(&path)) else { continue };
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let references: Vec<PathBuf> = parse_project_references(&content)
+            .iter()
+            .map(|include| normalize_path(&dir.join(include.replace('\\', "/"))))
+            .collect();
+        pending.extend(references.iter().cloned());
+        projects.push(CSharpProject {
+            name: path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string(),
+            path,
+            references,
+        });
+    }
+
+    projects.sort_by(|a, b| a.path.cmp(&b.path));
+    projects
+}
+
+/// The `Include` attributes of a project file's `<ProjectReference>` items
+fn parse_project_references(csproj: &str) -> Vec<&str> {
+    csproj
+        .split("<ProjectReference")
+        .skip(1)
+        .filter_map(|item| {
+            let item = &item[..item.find('>')?];
+            let value = item.split_once("Include")?.1.trim_start().strip_prefix('=')?.trim_start();
+            let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            value[1..].split(quote).next()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::resolvers::file_record;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_project_references() {
+        let csproj = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <ProjectReference Include="..\Core\Core.csproj" />
+    <ProjectReference
+        Include='../Data/Data.csproj'>
+      <Private>false</Private>
+    </ProjectReference>
+    <PackageReference Include="Newtonsoft.Json" Version="13.0.3" />
+  </ItemGroup>
+</Project>"#;
+        assert_eq!(parse_project_references(csproj), vec![r"..\Core\Core.csproj", "../Data/Data.csproj"]);
+    }
+
+    #[test]
+    fn test_load_projects() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/App/Services")).unwrap();
+        fs::create_dir_all(dir.path().join("src/Core")).unwrap();
+        fs::write(
+            dir.path().join("src/App/App.csproj"),
+            r#"<Project><ItemGroup><ProjectReference Include="..\Core\Core.csproj" /></ItemGroup></Project>"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("src/Core/Core.csproj"), "<Project></Project>").unwrap();
+
+        // Core has no C# files of its own but is found through App's reference
+        let files = vec![file_record("src/App/Services/OrderService.cs")];
+        let projects = load_projects(dir.path(), &files);
+
+        assert_eq!(
+            projects,
+            vec![
+                CSharpProject {
+                    name: "App".to_string(),
+                    path: PathBuf::from("src/App/App.csproj"),
+                    references: vec![PathBuf::from("src/Core/Core.csproj")],
+                },
+                CSharpProject {
+                    name: "Core".to_string(),
+                    path: PathBuf::from("src/Core/Core.csproj"),
+                    references: Vec::new(),
+                },
+            ]
+        );
+    }
+}
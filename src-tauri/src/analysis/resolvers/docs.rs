@@ -0,0 +1,73 @@
+use std::path::{Path, PathBuf};
+
+use super::{normalize_path, ImportResolver};
+use crate::analysis::parser::{DocLinkInfo, DocLinkKind};
+
+impl<'a> ImportResolver<'a> {
+    /// Resolve a file a Markdown document links to. Links are relative to the
+    /// document, or to the project root when they start with `/`, as on
+    /// GitHub. Paths named in code blocks are usually run from the project
+    /// root, so they are tried there too.
+    pub fn resolve_doc_link(&self, link: &DocLinkInfo, current_path: &str) -> Option<&'a str> {
+        doc_link_candidates(link, current_path)
+            .iter()
+            .find_map(|path| self.file_id(path))
+    }
+
+    /// Whether a link that did not resolve to an analysed file still points at
+    /// something on disk: a directory, a LICENSE, a PDF
+    pub fn doc_link_exists(&self, link: &DocLinkInfo, current_path: &str) -> bool {
+        doc_link_candidates(link, current_path)
+            .iter()
+            .any(|path| self.project_path.join(path).exists())
+    }
+}
+
+fn doc_link_candidates(link: &DocLinkInfo, current_path: &str) -> Vec<PathBuf> {
+    let current_dir = Path::new(current_path).parent().unwrap_or(Path::new(""));
+
+    match link.target.strip_prefix('/') {
+        Some(rooted) => vec![normalize_path(Path::new(rooted))],
+        None if link.kind == DocLinkKind::Mention => vec![
+            normalize_path(&current_dir.join(&link.target)),
+            normalize_path(Path::new(&link.target)),
+        ],
+        None => vec![normalize_path(&current_dir.join(&link.target))],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::resolvers::file_record;
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_resolve_doc_links() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/api")).unwrap();
+        fs::write(dir.path().join("LICENSE"), "MIT").unwrap();
+
+        let files = vec![file_record("docs/guide.md"), file_record("src/main.rs"), file_record("docs/setup.md")];
+        let resolver = ImportResolver::new(dir.path(), &files, &HashMap::new());
+        let link = |target: &str, kind: DocLinkKind| DocLinkInfo {
+            target: target.to_string(),
+            line: 1,
+            kind,
+        };
+        let guide = "docs/guide.md";
+
+        assert_eq!(resolver.resolve_doc_link(&link("../src/main.rs", DocLinkKind::Link), guide), Some("src/main.rs"));
+        assert_eq!(resolver.resolve_doc_link(&link("/src/main.rs", DocLinkKind::Link), guide), Some("src/main.rs"));
+        assert_eq!(resolver.resolve_doc_link(&link("./setup.md", DocLinkKind::Link), guide), Some("docs/setup.md"));
+        // Links are relative to the document, code block paths may be relative to the root
+        assert_eq!(resolver.resolve_doc_link(&link("src/main.rs", DocLinkKind::Link), guide), None);
+        assert_eq!(resolver.resolve_doc_link(&link("src/main.rs", DocLinkKind::Mention), guide), Some("src/main.rs"));
+
+        assert!(resolver.doc_link_exists(&link("../LICENSE", DocLinkKind::Link), guide));
+        assert!(resolver.doc_link_exists(&link("/src/api", DocLinkKind::Link), guide));
+        assert!(!resolver.doc_link_exists(&link("../src/gone.rs", DocLinkKind::Link), guide));
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{normalize_path, ImportResolver};
+use crate::storage::FileRecord;
+
+/// Go modules and packages in a project
+#[derive(Debug, Default)]
+pub struct GoWorkspace<'a> {
+    /// (module path from go.mod, module root directory), longest module path first
+    modules: Vec<(String, PathBuf)>,
+    /// Package directory -> non-test Go files in it
+    packages: HashMap<PathBuf, Vec<&'a str>>,
+}
+
+impl<'a> GoWorkspace<'a> {
+    /// Find go.mod files above every Go file and in every `use` directory of go.work
+    pub fn load(project_path: &Path, files: &'a [FileRecord]) -> Self {
+        let mut packages: HashMap<PathBuf, Vec<&'a str>> = HashMap::new();
+        for file in files.iter().filter(|f| f.language == "go" && !f.name.ends_with("_test.go")) {
+            let dir = Path::new(&file.path).parent().map(Path::to_path_buf).unwrap_or_default();
+            packages.entry(dir).or_default().push(file.id.as_str());
+        }
+
+        let mut module_dirs: HashSet<PathBuf> = HashSet::new();
+        if let Ok(content) = fs::read_to_string(project_path.join("go.work")) {
+            module_dirs.extend(parse_go_work_uses(&content).iter().map(|dir| normalize_path(dir)));
+        }
+        for package_dir in packages.keys() {
+            if let Some(module_dir) = package_dir
+                .ancestors()
+                .find(|dir| project_path.join(dir).join("go.mod").is_file())
+            {
+                module_dirs.insert(module_dir.to_path_buf());
+            }
+        }
+
+        let mut modules: Vec<(String, PathBuf)> = module_dirs
+            .into_iter()
+            .filter_map(|dir| {
+                let content = fs::read_to_string(project_path.join(&dir).join("go.mod")).ok()?;
+                parse_module_path(&content).map(|module| (module, dir))
+            })
+            .collect();
+        modules.sort_by_key(|(module, _)| std::cmp::Reverse(module.len()));
+
+        Self { modules, packages }
+    }
+
+    /// Directory of the package an import path refers to, if it is inside one of our modules
+    fn package_dir(&self, import_path: &str) -> Option<PathBuf> {
+        self.modules.iter().find_map(|(module, dir)| {
+            let rest = import_path.strip_prefix(module.as_str())?;
+            if rest.is_empty() {
+                Some(dir.clone())
+            } else {
+                rest.strip_prefix('/').map(|rest| normalize_path(&dir.join(rest)))
+            }
+        })
+    }
+}
+
+impl<'a> ImportResolver<'a> {
+    /// Resolve a Go import path to every file of the imported package. Module-local
+    /// packages are found through go.mod; others only if vendored. Standard library
+    /// and third-party packages resolve to nothing.
+    pub(super) fn resolve_go(&self, import_path: &str, current_path: &str) -> Vec<&'a str> {
+        let package_dir = self.go.package_dir(import_path).or_else(|| {
+            // vendor/ lives next to the go.mod of the importing module
+            let current = Path::new(current_path);
+            self.go
+                .modules
+                .iter()
+                .map(|(_, dir)| dir)
+                .filter(|dir| current.starts_with(dir))
+                .map(|dir| dir.join("vendor").join(import_path))
+                .find(|vendored| self.go.packages.contains_key(vendored))
+        });
+
+        package_dir
+            .and_then(|dir| self.go.packages.get(&dir))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// The `module` directive of a go.mod file
+fn parse_module_path(go_mod: &str) -> Option<String> {
+    go_mod.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix("module")?;
+        if !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let module = rest.split("//").next()?.trim().trim_matches('"');
+        (!module.is_empty()).then(|| module.to_string())
+    })
+}
+
+/// Directories listed by `use` directives in go.work (single line or block form)
+fn parse_go_work_uses(go_work: &str) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    let mut in_block = false;
+
+    for line in go_work.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        if in_block {
+            if line == ")" {
+                in_block = false;
+            } else if !line.is_empty() {
+                dirs.push(PathBuf::from(line.trim_matches('"')));
+            }
+        } else if let Some(rest) = line.strip_prefix("use") {
+            let rest = rest.trim();
+            if rest == "(" {
+                in_block = true;
+            } else if !rest.is_empty() {
+                dirs.push(PathBuf::from(rest.trim_matches('"')));
+            }
+        }
+    }
+
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::resolvers::file_record;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_go_mod_and_work() {
+        assert_eq!(
+            parse_module_path("// comment\nmodule github.com/acme/svc // trailing\n\ngo 1.22\n"),
+            Some("github.com/acme/svc".to_string())
+        );
+        assert_eq!(
+            parse_go_work_uses("go 1.22\n\nuse (\n\t./api\n\t./worker // jobs\n)\nuse ./tools\n"),
+            vec![PathBuf::from("./api"), PathBuf::from("./worker"), PathBuf::from("./tools")]
+        );
+    }
+
+    #[test]
+    fn test_resolve_packages_across_workspace_modules() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("go.work"), "go 1.22\nuse (\n  ./svc\n  ./lib\n)\n").unwrap();
+        for module in ["svc", "lib"] {
+            fs::create_dir_all(dir.path().join(module)).unwrap();
+        }
+        fs::write(dir.path().join("svc/go.mod"), "module github.com/acme/svc\n").unwrap();
+        fs::write(dir.path().join("lib/go.mod"), "module github.com/acme/lib\n").unwrap();
+
+        let files = vec![
+            file_record("svc/main.go"),
+            file_record("svc/internal/store/store.go"),
+            file_record("svc/internal/store/cache.go"),
+            file_record("svc/internal/store/store_test.go"),
+            file_record("lib/log.go"),
+            file_record("svc/vendor/github.com/x/y/y.go"),
+        ];
+        let resolver = ImportResolver::new(dir.path(), &files, &HashMap::new());
+        let main = &files[0];
+
+        let mut store = resolver.resolve_go("github.com/acme/svc/internal/store", &main.path);
+        store.sort();
+        assert_eq!(store, vec!["svc/internal/store/cache.go", "svc/internal/store/store.go"]);
+        assert_eq!(resolver.resolve_go("github.com/acme/lib", &main.path), vec!["lib/log.go"]);
+        assert_eq!(resolver.resolve_go("github.com/x/y", &main.path), vec!["svc/vendor/github.com/x/y/y.go"]);
+        assert!(resolver.resolve_go("fmt", &main.path).is_empty());
+        assert!(resolver.resolve_go("github.com/other/store", &main.path).is_empty());
+    }
+}
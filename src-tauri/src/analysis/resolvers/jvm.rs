@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::ImportResolver;
+use crate::analysis::parser::ImportInfo;
+use crate::storage::{FileRecord, SymbolRecord};
+
+/// Maven and Gradle source directories; the package path starts below them
+const SOURCE_ROOTS: &[&str] = &["src/main/java", "src/main/kotlin", "src/test/java", "src/test/kotlin"];
+
+/// A Java or Kotlin file and the top-level names it declares
+#[derive(Debug)]
+struct JvmFile<'a> {
+    id: &'a str,
+    /// File name without extension: the public class of a Java file
+    stem: &'a str,
+    /// Kotlin files can declare any number of classes, functions and properties
+    declares: Vec<String>,
+}
+
+/// Java and Kotlin files by package directory
+#[derive(Debug, Default)]
+pub struct JvmPackages<'a> {
+    packages: HashMap<PathBuf, Vec<JvmFile<'a>>>,
+}
+
+impl<'a> JvmPackages<'a> {
+    pub fn load(files: &'a [FileRecord]) -> Self {
+        let mut packages: HashMap<PathBuf, Vec<JvmFile<'a>>> = HashMap::new();
+
+        for file in files.iter().filter(|f| f.language == "java" || f.language == "kotlin") {
+            let path = Path::new(&file.path);
+            packages.entry(package_dir(path).to_path_buf()).or_default().push(JvmFile {
+                id: file.id.as_str(),
+                stem: path.file_stem().and_then(|s| s.to_str()).unwrap_or_default(),
+                declares: Vec::new(),
+            });
+        }
+
+        Self { packages }
+    }
+
+    /// Record the top-level symbols of each file
+    pub fn add_declarations(&mut self, symbols: &[SymbolRecord]) {
+        let mut by_file: HashMap<&str, &mut JvmFile<'a>> =
+            self.packages.values_mut().flatten().map(|f| (f.id, f)).collect();
+
+        for symbol in symbols.iter().filter(|s| s.parent_id.is_none()) {
+            if let Some(file) = by_file.get_mut(symbol.file_id.as_str()) {
+                file.declares.push(symbol.name.clone());
+            }
+        }
+    }
+}
+
+impl<'a> ImportResolver<'a> {
+    /// Resolve a Java or Kotlin import: the files of package `a.b` declaring
+    /// the imported names (all of them for `a.b.*`), or for `a.b.Type.member`
+    /// and nested types the file declaring `Type`
+    pub(super) fn resolve_jvm(&self, import: &ImportInfo) -> Vec<&'a str> {
+        let segments: Vec<&str> = import.source.split('.').collect();
+
+        if let Some(files) = self.jvm.packages.get(&segments.iter().collect::<PathBuf>()) {
+            let wildcard = import.imported_names.iter().any(|n| n == "*");
+            let found: Vec<&'a str> = files
+                .iter()
+                .filter(|f| wildcard || import.imported_names.iter().any(|n| f.stem == n || f.declares.contains(n)))
+                .map(|f| f.id)
+                .collect();
+            if !found.is_empty() {
+                return found;
+            }
+        }
+
+        // The longest prefix that is a package followed by a type declared in it
+        (1..segments.len())
+            .rev()
+            .find_map(|len| {
+                let files = self.jvm.packages.get(&segments[..len].iter().collect::<PathBuf>())?;
+                let type_name = segments[len];
+                files
+                    .iter()
+                    .find(|f| f.stem == type_name || f.declares.iter().any(|d| d == type_name))
+                    .map(|f| vec![f.id])
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Package directory of a Java or Kotlin file, relative to its source root:
+/// `app/src/main/java/com/acme/App.java` -> `com/acme`. Files outside a
+/// Maven/Gradle source root are placed by their project-relative directory.
+pub fn package_dir(path: &Path) -> &Path {
+    let dir = path.parent().unwrap_or(Path::new(""));
+    dir.ancestors()
+        .find(|ancestor| SOURCE_ROOTS.iter().any(|root| ancestor.ends_with(root)))
+        .and_then(|root| dir.strip_prefix(root).ok())
+        .unwrap_or(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::resolvers::file_record;
+
+    fn import(source: &str, name: &str) -> ImportInfo {
+        ImportInfo {
+            source: source.to_string(),
+            imported_names: vec![name.to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_package_dir() {
+        assert_eq!(package_dir(Path::new("app/src/main/java/com/acme/App.java")), Path::new("com/acme"));
+        assert_eq!(package_dir(Path::new("src/test/kotlin/com/acme/AppTest.kt")), Path::new("com/acme"));
+        assert_eq!(package_dir(Path::new("scripts/Tool.java")), Path::new("scripts"));
+    }
+
+    #[test]
+    fn test_resolve_imports_by_package() {
+        let files = vec![
+            file_record("core/src/main/java/com/acme/model/Order.java"),
+            file_record("core/src/main/java/com/acme/model/Item.java"),
+            file_record("core/src/main/kotlin/com/acme/util/Strings.kt"),
+            file_record("app/src/main/java/com/acme/App.java"),
+        ];
+        let slugify = SymbolRecord {
+            id: "slugify".to_string(),
+            file_id: files[2].id.clone(),
+            name: "slugify".to_string(),
+            kind: "function".to_string(),
+            line: 1,
+            column: 1,
+            end_line: None,
+            end_column: None,
+            signature: None,
+            documentation: None,
+            is_exported: true,
+            parent_id: None,
+        };
+
+        let resolver = ImportResolver::new(Path::new("/proj"), &files, &HashMap::new()).with_declarations(&[slugify]);
+        let order = files[0].id.as_str();
+        let item = files[1].id.as_str();
+        let strings = files[2].id.as_str();
+
+        assert_eq!(resolver.resolve_jvm(&import("com.acme.model", "Order")), vec![order]);
+        let mut model = resolver.resolve_jvm(&import("com.acme.model", "*"));
+        model.sort();
+        assert_eq!(model, vec![item, order]);
+        // Static imports and nested types resolve to the declaring file
+        assert_eq!(resolver.resolve_jvm(&import("com.acme.model.Order", "create")), vec![order]);
+        assert_eq!(resolver.resolve_jvm(&import("com.acme.model.Order", "Line")), vec![order]);
+        // Kotlin top-level functions live in files of any name
+        assert_eq!(resolver.resolve_jvm(&import("com.acme.util", "slugify")), vec![strings]);
+        assert!(resolver.resolve_jvm(&import("java.util", "List")).is_empty());
+    }
+}
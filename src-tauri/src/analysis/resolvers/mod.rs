@@ -0,0 +1,304 @@
+pub mod c;
+pub mod config;
+pub mod csharp;
+pub mod docs;
+pub mod go;
+pub mod jvm;
+pub mod packages;
+pub mod php;
+pub mod python;
+pub mod ruby;
+pub mod rust;
+pub mod shell;
+pub mod typescript;
+pub mod web;
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::analysis::parser::{ImportInfo, ParseResult};
+use crate::storage::{FileRecord, SymbolRecord};
+
+/// Extensions to try when resolving imports
+/// Includes code files and config files that code might import
+const IMPORT_EXTENSIONS: &[&str] = &[
+    "", ".ts", ".tsx", ".js", ".jsx", ".py", ".go", ".rs", ".c", ".h", ".swift",
+    // Config files that code might import
+    ".json", ".yaml", ".yml", ".md", ".html", ".css", ".scss", ".plist",
+];
+
+/// Resolves import specifiers to project files, using per-language project
+/// configuration (tsconfig.json, ...) where available
+pub struct ImportResolver<'a> {
+    project_path: PathBuf,
+    /// Project-relative file path -> file ID
+    path_to_id: HashMap<&'a str, &'a str>,
+    /// File name -> file ID, for imports we cannot resolve by path
+    name_to_id: HashMap<&'a str, &'a str>,
+    c: c::CIncludes<'a>,
+    csharp: csharp::CSharpNamespaces<'a>,
+    tsconfigs: typescript::TsConfigSet,
+    python_roots: python::PythonRoots,
+    go: go::GoWorkspace<'a>,
+    jvm: jvm::JvmPackages<'a>,
+    php: php::PhpAutoload<'a>,
+    ruby: ruby::RubyLoadPath,
+    rust: rust::RustCrates<'a>,
+}
+
+impl<'a> ImportResolver<'a> {
+    pub fn new(
+        project_path: &Path,
+        files: &'a [FileRecord],
+        parsed_files: &HashMap<String, ParseResult>,
+    ) -> Self {
+        let path_to_id = files
+            .iter()
+            .map(|f| (f.path.as_str(), f.id.as_str()))
+            .collect();
+
+        let name_to_id = files
+            .iter()
+            .map(|f| (f.name.as_str(), f.id.as_str()))
+            .collect();
+
+        Self {
+            project_path: project_path.to_path_buf(),
+            path_to_id,
+            name_to_id,
+            c: c::CIncludes::load(project_path, files),
+            csharp: csharp::CSharpNamespaces::load(files, parsed_files),
+            tsconfigs: typescript::TsConfigSet::load(project_path, files),
+            python_roots: python::PythonRoots::load(project_path, files),
+            go: go::GoWorkspace::load(project_path, files),
+            jvm: jvm::JvmPackages::load(files),
+            php: php::PhpAutoload::load(project_path, files, parsed_files),
+            ruby: ruby::RubyLoadPath::load(files),
+            rust: rust::RustCrates::load(project_path, files, parsed_files),
+        }
+    }
+
+    /// Search the given directories for C/C++ includes not found on the
+    /// compilation database's include path
+    pub fn with_include_dirs(mut self, dirs: &[PathBuf]) -> Self {
+        self.c.add_configured(&self.project_path, dirs);
+        self
+    }
+
+    /// Index the top-level symbols of every file, for languages whose imports
+    /// name a declaration rather than a file (Kotlin top-level functions, C#
+    /// `using static`, PHP `use function`)
+    pub fn with_declarations(mut self, symbols: &[SymbolRecord]) -> Self {
+        self.jvm.add_declarations(symbols);
+        self.csharp.add_declarations(symbols);
+        self.php.add_declarations(symbols);
+        self
+    }
+
+    /// Resolve an import made by `file` to the IDs of the imported files
+    pub fn resolve(&self, import: &ImportInfo, file: &FileRecord) -> Vec<&'a str> {
+        match file.language.as_str() {
+            "typescript" | "javascript" | "vue" | "svelte" => self
+                .resolve_typescript(&import.source, &file.path)
+                .into_iter()
+                .collect(),
+            "c" | "cpp" => self.resolve_c(import, &file.path).into_iter().collect(),
+            "python" => self.resolve_python(import, &file.path),
+            "go" => self.resolve_go(&import.source, &file.path),
+            "java" | "kotlin" => self.resolve_jvm(import),
+            "csharp" => self.resolve_csharp(import),
+            "php" => self.resolve_php(import, &file.path),
+            "ruby" => self.resolve_ruby(&import.source, &file.path).into_iter().collect(),
+            "html" | "css" => self.resolve_web(&import.source, &file.path).into_iter().collect(),
+            "shell" => self.resolve_shell(&import.source, &file.path).into_iter().collect(),
+            "rust" => self.resolve_rust(&import.source, &import.imported_names, &file.path),
+            _ => self
+                .resolve_by_path_or_name(&import.source, &file.path)
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// Look up a project-relative path
+    fn file_id(&self, path: &Path) -> Option<&'a str> {
+        self.path_to_id.get(path.to_string_lossy().as_ref()).copied()
+    }
+
+    /// Look up `path` with each of the given extensions appended
+    fn file_id_with_extensions(&self, path: &Path, extensions: &[&str]) -> Option<&'a str> {
+        extensions.iter().find_map(|ext| {
+            let mut candidate = path.as_os_str().to_owned();
+            candidate.push(ext);
+            self.file_id(Path::new(&candidate))
+        })
+    }
+
+    /// Fallback resolution: relative path, then bare file name anywhere in the project
+    fn resolve_by_path_or_name(&self, import_source: &str, current_path: &str) -> Option<&'a str> {
+        // Handle relative imports
+        if import_source.starts_with('.') {
+            let current_dir = Path::new(current_path).parent()?;
+            let import_path = normalize_path(&current_dir.join(import_source));
+
+            if let Some(id) = self.file_id_with_extensions(&import_path, IMPORT_EXTENSIONS) {
+                return Some(id);
+            }
+
+            // Try index files
+            for index in &["index.ts", "index.tsx", "index.js", "index.jsx"] {
+                if let Some(id) = self.file_id(&import_path.join(index)) {
+                    return Some(id);
+                }
+            }
+        }
+
+        // Try direct file name match
+        let file_name = Path::new(import_source)
+            .file_name()
+            .and_then(|n| n.to_str())?;
+
+        IMPORT_EXTENSIONS.iter().find_map(|ext| {
+            let name_with_ext = format!("{}{}", file_name, ext);
+            self.name_to_id.get(name_with_ext.as_str()).copied()
+        })
+    }
+}
+
+/// Lexically normalize a project-relative path, resolving `.` and `..` components
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                // Keep leading `..` for paths that escape the project root
+                if normalized.file_name().is_some() {
+                    normalized.pop();
+                } else {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+
+    normalized
+}
+
+/// Parse JSON that may contain comments and trailing commas (tsconfig.json, jsconfig.json)
+pub fn parse_jsonc(content: &str) -> serde_json::Result<Value> {
+    let mut stripped = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+
+    // Drop comments, leaving strings untouched
+    while let Some(c) = chars.next() {
+        if in_string {
+            stripped.push(c);
+            match c {
+                '\\' => stripped.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                stripped.push(c);
+            }
+            ('/', Some('/')) => {
+                while chars.next_if(|&n| n != '\n').is_some() {}
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for n in chars.by_ref() {
+                    if previous == '*' && n == '/' {
+                        break;
+                    }
+                    previous = n;
+                }
+            }
+            _ => stripped.push(c),
+        }
+    }
+
+    // Drop commas directly before a closing bracket
+    let mut cleaned = String::with_capacity(stripped.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in stripped.char_indices() {
+        if in_string {
+            in_string = escaped || c != '"';
+            escaped = !escaped && c == '\\';
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' {
+            let next = stripped[i + 1..].trim_start().chars().next();
+            if matches!(next, Some('}') | Some(']')) {
+                continue;
+            }
+        }
+        cleaned.push(c);
+    }
+
+    serde_json::from_str(&cleaned)
+}
+
+/// A file record for resolver tests, in the language its extension implies;
+/// extensionless files are taken to be shell scripts
+#[cfg(test)]
+pub(crate) fn file_record(path: &str) -> FileRecord {
+    let language = Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(crate::analysis::parser::SupportedLanguage::from_extension)
+        .map_or("shell", |language| language.as_str());
+
+    FileRecord {
+        id: path.to_string(),
+        project_id: "proj".to_string(),
+        name: Path::new(path).file_name().unwrap().to_string_lossy().to_string(),
+        path: path.to_string(),
+        absolute_path: format!("/proj/{}", path),
+        language: language.to_string(),
+        line_count: 1,
+        is_hidden: false,
+        content_hash: None,
+        last_modified: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path(Path::new("src/a/../b/./c.ts")), PathBuf::from("src/b/c.ts"));
+        assert_eq!(normalize_path(Path::new("./src")), PathBuf::from("src"));
+        assert_eq!(normalize_path(Path::new("../shared/x")), PathBuf::from("../shared/x"));
+        assert_eq!(normalize_path(Path::new("a/../../../x")), PathBuf::from("../../x"));
+    }
+
+    #[test]
+    fn test_parse_jsonc() {
+        let json = parse_jsonc(r#"{
+            // line comment
+            "url": "http://example.com/*not a comment*/",
+            /* block
+               comment */
+            "list": [1, 2,],
+            "quote": "say \"hi\",",
+        }"#).unwrap();
+
+        assert_eq!(json["url"], "http://example.com/*not a comment*/");
+        assert_eq!(json["list"].as_array().unwrap().len(), 2);
+        assert_eq!(json["quote"], "say \"hi\",");
+    }
+}
@@ -0,0 +1,311 @@
+use crate::analysis::parser::ImportInfo;
+
+/// A third-party package an unresolved import points into
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExternalPackage {
+    /// "npm", "crates", "pypi", "go", "rubygems" or "nuget"
+    pub ecosystem: &'static str,
+    /// Package root: `@scope/name`, crate name, top-level module, module path
+    /// or root namespace
+    pub name: String,
+}
+
+/// Why an import did not resolve to a project file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unresolved {
+    /// A third-party package
+    Package(ExternalPackage),
+    /// The standard library, a runtime built-in or a system header
+    Builtin,
+    /// A relative or crate-local path with no file behind it
+    MissingFile,
+    /// Neither recognisably local nor external: an unconfigured alias or a resolver gap
+    Unknown,
+}
+
+impl Unresolved {
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::Package(_) => "external",
+            Self::Builtin => "builtin",
+            Self::MissingFile => "missing-file",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// Node.js built-in modules, importable with or without the `node:` prefix
+const NODE_BUILTINS: &[&str] = &[
+    "assert", "async_hooks", "buffer", "child_process", "cluster", "console", "constants",
+    "crypto", "dgram", "diagnostics_channel", "dns", "domain", "events", "fs", "http", "http2",
+    "https", "inspector", "module", "net", "os", "path", "perf_hooks", "process", "punycode",
+    "querystring", "readline", "repl", "stream", "string_decoder", "sys", "timers", "tls",
+    "trace_events", "tty", "url", "util", "v8", "vm", "wasi", "worker_threads", "zlib",
+];
+
+/// Top-level modules of the Python standard library
+const PYTHON_STDLIB: &[&str] = &[
+    "__future__", "_thread", "abc", "argparse", "array", "ast", "asyncio", "atexit", "base64",
+    "binascii", "bisect", "builtins", "bz2", "calendar", "cmath", "cmd", "code", "codecs",
+    "collections", "colorsys", "concurrent", "configparser", "contextlib", "contextvars", "copy",
+    "copyreg", "cProfile", "csv", "ctypes", "curses", "dataclasses", "datetime", "dbm", "decimal",
+    "difflib", "dis", "doctest", "email", "encodings", "enum", "errno", "faulthandler", "fcntl",
+    "filecmp", "fileinput", "fnmatch", "fractions", "ftplib", "functools", "gc", "getopt",
+    "getpass", "gettext", "glob", "graphlib", "grp", "gzip", "hashlib", "heapq", "hmac", "html",
+    "http", "imaplib", "importlib", "inspect", "io", "ipaddress", "itertools", "json", "keyword",
+    "linecache", "locale", "logging", "lzma", "mailbox", "marshal", "math", "mimetypes", "mmap",
+    "multiprocessing", "netrc", "numbers", "operator", "optparse", "os", "pathlib", "pdb",
+    "pickle", "pkgutil", "platform", "plistlib", "poplib", "posix", "pprint", "profile",
+    "pstats", "pty", "pwd", "py_compile", "queue", "quopri", "random", "re", "readline",
+    "reprlib", "resource", "rlcompleter", "runpy", "sched", "secrets", "select", "selectors",
+    "shelve", "shlex", "shutil", "signal", "site", "smtplib", "socket", "socketserver",
+    "sqlite3", "ssl", "stat", "statistics", "string", "stringprep", "struct", "subprocess",
+    "symtable", "sys", "sysconfig", "syslog", "tabnanny", "tarfile", "tempfile", "termios",
+    "textwrap", "threading", "time", "timeit", "tkinter", "token", "tokenize", "tomllib",
+    "trace", "traceback", "tracemalloc", "tty", "turtle", "types", "typing", "unicodedata",
+    "unittest", "urllib", "uuid", "venv", "warnings", "wave", "weakref", "webbrowser", "winreg",
+    "wsgiref", "xml", "xmlrpc", "zipapp", "zipfile", "zipimport", "zlib", "zoneinfo",
+];
+
+/// Crates that ship with the Rust toolchain
+const RUST_STD_CRATES: &[&str] = &["std", "core", "alloc", "proc_macro", "test"];
+
+/// Rust path roots that stay inside the current crate
+const RUST_LOCAL_ROOTS: &[&str] = &["crate", "self", "super", "Self"];
+
+/// Modules of the Swift standard library and Apple SDKs
+const SWIFT_SDK_MODULES: &[&str] = &[
+    "AppKit", "AVFoundation", "AVKit", "Combine", "CoreData", "CoreFoundation", "CoreGraphics",
+    "CoreImage", "CoreLocation", "CoreML", "CoreMotion", "CoreText", "CryptoKit", "Darwin",
+    "Dispatch", "Foundation", "GameKit", "HealthKit", "MapKit", "Metal", "MetalKit", "Network",
+    "Observation", "os", "OSLog", "PhotosUI", "QuartzCore", "SafariServices", "SceneKit",
+    "Security", "SpriteKit", "StoreKit", "Swift", "SwiftData", "SwiftUI", "UIKit",
+    "UniformTypeIdentifiers", "UserNotifications", "WebKit", "WidgetKit", "XCTest",
+];
+
+/// Package roots of the Java, Kotlin and Android platform libraries
+const JVM_PLATFORM_PACKAGES: &[&str] = &["java", "javax", "jdk", "sun", "kotlin", "android"];
+
+/// Namespace roots of the .NET base class library and Microsoft frameworks
+const DOTNET_FRAMEWORK_NAMESPACES: &[&str] = &["System", "Microsoft", "Windows"];
+
+/// Go module hosts whose module paths are `host/owner/repo`
+const GO_REPO_HOSTS: &[&str] = &["github.com", "gitlab.com", "bitbucket.org", "golang.org"];
+
+/// Libraries that ship with Ruby, loaded with `require` but not installed as gems
+const RUBY_STDLIB: &[&str] = &[
+    "abbrev", "base64", "benchmark", "bigdecimal", "cgi", "coverage", "csv", "date", "delegate",
+    "digest", "English", "erb", "etc", "fcntl", "fiddle", "fileutils", "find", "forwardable",
+    "io", "ipaddr", "irb", "json", "logger", "monitor", "mutex_m", "net", "objspace", "observer",
+    "open-uri", "open3", "openssl", "optparse", "ostruct", "pathname", "pp", "prettyprint",
+    "pstore", "psych", "rbconfig", "readline", "resolv", "ripper", "securerandom", "set",
+    "shellwords", "singleton", "socket", "stringio", "strscan", "tempfile", "time", "timeout",
+    "tmpdir", "tsort", "un", "uri", "weakref", "yaml", "zlib",
+];
+
+/// The package behind an import that did not resolve to a project file, or
+/// None for relative paths, standard library modules and unsupported languages
+pub fn external_package(language: &str, import: &ImportInfo) -> Option<ExternalPackage> {
+    let source = import.source.as_str();
+    let (ecosystem, name) = match language {
+        "typescript" | "javascript" | "vue" | "svelte" => ("npm", npm_package(source)?),
+        "python" => ("pypi", python_package(source)?),
+        "go" => ("go", go_module(source)?),
+        "rust" => ("crates", rust_crate(source)?),
+        "ruby" => ("rubygems", ruby_gem(source)?),
+        "csharp" => ("nuget", nuget_package(source)?),
+        _ => return None,
+    };
+
+    Some(ExternalPackage { ecosystem, name })
+}
+
+/// Classify an import that did not resolve to a project file
+pub fn classify_unresolved(language: &str, import: &ImportInfo) -> Unresolved {
+    if let Some(package) = external_package(language, import) {
+        return Unresolved::Package(package);
+    }
+
+    let source = import.source.as_str();
+    let root = |separator: &str| source.trim_start_matches(separator).split(separator).next().unwrap_or_default();
+    let (builtin, local) = match language {
+        "typescript" | "javascript" | "vue" | "svelte" => (
+            source.starts_with("node:") || NODE_BUILTINS.contains(&root("/")),
+            source.starts_with(['.', '/']),
+        ),
+        "python" => (PYTHON_STDLIB.contains(&root(".")), source.starts_with('.')),
+        "go" => (!root("/").contains('.'), false),
+        "rust" => (RUST_STD_CRATES.contains(&root("::")), RUST_LOCAL_ROOTS.contains(&root("::"))),
+        "c" | "cpp" => (import.is_system, !import.is_system),
+        "swift" => (SWIFT_SDK_MODULES.contains(&root(".")), false),
+        "java" | "kotlin" => (JVM_PLATFORM_PACKAGES.contains(&root(".")), false),
+        "csharp" => (DOTNET_FRAMEWORK_NAMESPACES.contains(&root(".")), false),
+        // Scripts, pages, stylesheets and images are only recorded when named by path
+        "shell" | "html" | "css" => (false, true),
+        "ruby" => (RUBY_STDLIB.contains(&root("/")), source.starts_with('.')),
+        // Global names (`use Exception;`) are built-in classes, paths are `require`d files
+        "php" => {
+            let is_path = source.contains('/') || source.ends_with(".php");
+            (!is_path && !source.trim_start_matches('\\').contains('\\'), is_path)
+        }
+        _ => (false, false),
+    };
+
+    match (builtin, local) {
+        (true, _) => Unresolved::Builtin,
+        (_, true) => Unresolved::MissingFile,
+        _ => Unresolved::Unknown,
+    }
+}
+
+/// `@scope/name/sub/path` -> `@scope/name`, `name/sub` -> `name`
+fn npm_package(source: &str) -> Option<String> {
+    // Relative and absolute paths, URLs, `node:` builtins, `~/` and `#internal` aliases
+    if source.starts_with(['.', '/', '~', '#']) || source.contains(':') {
+        return None;
+    }
+
+    let mut segments = source.split('/');
+    let first = segments.next()?;
+    let name = match first.strip_prefix('@') {
+        // `@/components/...` is a path alias, not a scope
+        Some("") => return None,
+        Some(_) => format!("{}/{}", first, segments.next()?),
+        None if NODE_BUILTINS.contains(&first) => return None,
+        None => first.to_string(),
+    };
+
+    Some(name)
+}
+
+/// `numpy.linalg` -> `numpy`
+fn python_package(source: &str) -> Option<String> {
+    let root = source.split('.').next().filter(|root| !root.is_empty())?;
+    (!PYTHON_STDLIB.contains(&root)).then(|| root.to_string())
+}
+
+/// `github.com/owner/repo/pkg` -> `github.com/owner/repo`; standard library
+/// packages have no dot in their first path element
+fn go_module(source: &str) -> Option<String> {
+    let segments: Vec<&str> = source.split('/').collect();
+    if !segments[0].contains('.') {
+        return None;
+    }
+
+    let len = if GO_REPO_HOSTS.contains(&segments[0]) { 3 } else { 2 };
+    Some(segments[..len.min(segments.len())].join("/"))
+}
+
+/// `serde::de::Deserialize` -> `serde`
+fn rust_crate(source: &str) -> Option<String> {
+    let root = source.trim_start_matches("::").split("::").next()?;
+    // Lowercase roots only: `Kind::*` names a type in scope, not a crate
+    let is_crate_name = root.starts_with(|c: char| c.is_ascii_lowercase() || c == '_');
+    let is_builtin = RUST_STD_CRATES.contains(&root) || RUST_LOCAL_ROOTS.contains(&root);
+    (is_crate_name && !is_builtin).then(|| root.to_string())
+}
+
+/// `active_record/base` -> `active_record`; the gem name is assumed to match
+/// its top-level require path
+fn ruby_gem(source: &str) -> Option<String> {
+    if source.starts_with(['.', '/']) {
+        return None;
+    }
+    let root = source.split('/').next().filter(|root| !root.is_empty())?;
+    (!RUBY_STDLIB.contains(&root)).then(|| root.to_string())
+}
+
+/// `Newtonsoft.Json.Linq` -> `Newtonsoft`; NuGet package IDs are not
+/// recorded in namespaces, so the root namespace stands in for the package
+fn nuget_package(source: &str) -> Option<String> {
+    let root = source.trim_start_matches("global::").split('.').next().filter(|root| !root.is_empty())?;
+    (!DOTNET_FRAMEWORK_NAMESPACES.contains(&root)).then(|| root.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(language: &str, source: &str) -> Option<String> {
+        let import = ImportInfo {
+            source: source.to_string(),
+            ..Default::default()
+        };
+        external_package(language, &import).map(|p| format!("{}:{}", p.ecosystem, p.name))
+    }
+
+    #[test]
+    fn test_npm_packages() {
+        assert_eq!(package("typescript", "react").as_deref(), Some("npm:react"));
+        assert_eq!(package("typescript", "react-dom/client").as_deref(), Some("npm:react-dom"));
+        assert_eq!(package("javascript", "@tauri-apps/api/core").as_deref(), Some("npm:@tauri-apps/api"));
+        assert_eq!(package("typescript", "./utils"), None);
+        assert_eq!(package("typescript", "@/types"), None);
+        assert_eq!(package("typescript", "node:fs"), None);
+        assert_eq!(package("javascript", "path"), None);
+    }
+
+    #[test]
+    fn test_other_ecosystems() {
+        assert_eq!(package("python", "numpy.linalg").as_deref(), Some("pypi:numpy"));
+        assert_eq!(package("python", "os.path"), None);
+        assert_eq!(package("python", ".models"), None);
+
+        assert_eq!(package("go", "github.com/spf13/cobra/doc").as_deref(), Some("go:github.com/spf13/cobra"));
+        assert_eq!(package("go", "go.uber.org/zap/zapcore").as_deref(), Some("go:go.uber.org/zap"));
+        assert_eq!(package("go", "net/http"), None);
+
+        assert_eq!(package("rust", "serde::Deserialize").as_deref(), Some("crates:serde"));
+        assert_eq!(package("rust", "std::collections"), None);
+        assert_eq!(package("rust", "crate::model"), None);
+        assert_eq!(package("rust", "Kind"), None);
+
+        assert_eq!(package("ruby", "active_record/base").as_deref(), Some("rubygems:active_record"));
+        assert_eq!(package("ruby", "json"), None);
+        assert_eq!(package("ruby", "../lib/helper"), None);
+
+        assert_eq!(package("csharp", "Newtonsoft.Json.Linq").as_deref(), Some("nuget:Newtonsoft"));
+        assert_eq!(package("csharp", "System.Collections.Generic"), None);
+        assert_eq!(package("csharp", "Microsoft.Extensions.Logging"), None);
+
+        assert_eq!(package("c", "stdio.h"), None);
+    }
+
+    #[test]
+    fn test_classify_unresolved() {
+        let reason = |language: &str, source: &str, is_system: bool| {
+            let import = ImportInfo {
+                source: source.to_string(),
+                is_system,
+                ..Default::default()
+            };
+            classify_unresolved(language, &import).reason()
+        };
+
+        assert_eq!(reason("typescript", "react", false), "external");
+        assert_eq!(reason("typescript", "node:fs", false), "builtin");
+        assert_eq!(reason("javascript", "fs/promises", false), "builtin");
+        assert_eq!(reason("typescript", "./missing", false), "missing-file");
+        assert_eq!(reason("typescript", "@/components/Button", false), "unknown");
+        assert_eq!(reason("python", "os.path", false), "builtin");
+        assert_eq!(reason("python", ".models", false), "missing-file");
+        assert_eq!(reason("go", "net/http", false), "builtin");
+        assert_eq!(reason("rust", "std::fs", false), "builtin");
+        assert_eq!(reason("rust", "crate::gone", false), "missing-file");
+        assert_eq!(reason("c", "stdio.h", true), "builtin");
+        assert_eq!(reason("c", "config.h", false), "missing-file");
+        assert_eq!(reason("swift", "SwiftUI", false), "builtin");
+        assert_eq!(reason("swift", "Alamofire", false), "unknown");
+        assert_eq!(reason("java", "java.util", false), "builtin");
+        assert_eq!(reason("kotlin", "kotlinx.coroutines", false), "unknown");
+        assert_eq!(reason("csharp", "System.Collections.Generic", false), "builtin");
+        assert_eq!(reason("csharp", "Newtonsoft.Json", false), "external");
+        assert_eq!(reason("shell", "./scripts/gone.sh", false), "missing-file");
+        assert_eq!(reason("css", "/styles/gone.css", false), "missing-file");
+        assert_eq!(reason("ruby", "net/http", false), "builtin");
+        assert_eq!(reason("ruby", "./missing", false), "missing-file");
+        assert_eq!(reason("php", "Exception", false), "builtin");
+        assert_eq!(reason("php", "config/app.php", false), "missing-file");
+        assert_eq!(reason("php", "Psr\\Log\\LoggerInterface", false), "unknown");
+    }
+}
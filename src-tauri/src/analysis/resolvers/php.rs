@@ -0,0 +1,237 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use super::{normalize_path, ImportResolver};
+use crate::analysis::parser::{ImportInfo, ParseResult};
+use crate::storage::{FileRecord, SymbolRecord};
+
+/// PSR-4 autoload mappings and PHP files by the namespaces they declare
+#[derive(Debug, Default)]
+pub struct PhpAutoload<'a> {
+    /// Namespace prefix (with trailing `\`) -> project-relative base directories,
+    /// longest prefix first
+    psr4: Vec<(String, Vec<PathBuf>)>,
+    /// Fully qualified namespace -> files declaring it
+    namespaces: HashMap<String, Vec<&'a str>>,
+    /// File ID -> names of the classes, functions and constants it declares
+    /// at namespace level
+    declarations: HashMap<&'a str, Vec<String>>,
+}
+
+impl<'a> PhpAutoload<'a> {
+    /// Read `autoload` and `autoload-dev` PSR-4 mappings from the project's
+    /// composer.json and any composer.json above a PHP file
+    pub fn load(project_path: &Path, files: &'a [FileRecord], parsed_files: &HashMap<String, ParseResult>) -> Self {
+        let mut this = Self::default();
+        let mut searched: HashSet<&Path> = HashSet::new();
+
+        for file in files.iter().filter(|f| f.language == "php") {
+            this.declarations.insert(file.id.as_str(), Vec::new());
+            if let Some(parsed) = parsed_files.get(&file.id) {
+                for namespace in &parsed.namespaces {
+                    this.namespaces.entry(namespace.clone()).or_default().push(file.id.as_str());
+                }
+            }
+
+            let dir = Path::new(&file.path).parent().unwrap_or(Path::new(""));
+            for ancestor in dir.ancestors() {
+                if !searched.insert(ancestor) {
+                    break;
+                }
+                let Ok(content) = fs::read_to_string(project_path.join(ancestor).join("composer.json")) else {
+                    continue;
+                };
+                match serde_json::from_str::<Value>(&content) {
+                    Ok(composer) => this.psr4.extend(psr4_mappings(&composer, ancestor)),
+                    Err(e) => tracing::warn!("Failed to parse {}: {}", ancestor.join("composer.json").display(), e),
+                }
+            }
+        }
+
+        this.psr4.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        this
+    }
+
+    /// Record the declarations at namespace level of each file
+    pub fn add_declarations(&mut self, symbols: &[SymbolRecord]) {
+        let namespaces: HashSet<&str> = symbols
+            .iter()
+            .filter(|s| s.kind == "namespace")
+            .map(|s| s.id.as_str())
+            .collect();
+
+        for symbol in symbols.iter().filter(|s| s.kind != "namespace") {
+            if symbol.parent_id.as_deref().is_some_and(|p| !namespaces.contains(p)) {
+                continue;
+            }
+            if let Some(declarations) = self.declarations.get_mut(symbol.file_id.as_str()) {
+                declarations.push(symbol.name.clone());
+            }
+        }
+    }
+}
+
+impl<'a> ImportResolver<'a> {
+    /// Resolve a PHP import. File paths from `require`/`include` are relative
+    /// to the including file, then the project root. Names from `use` resolve
+    /// through the PSR-4 autoloader, then to the file declaring the name in
+    /// its namespace.
+    pub(super) fn resolve_php(&self, import: &ImportInfo, current_path: &str) -> Vec<&'a str> {
+        let source = import.source.as_str();
+
+        if !source.contains('\\') && (source.contains('/') || source.ends_with(".php")) {
+            let current_dir = Path::new(current_path).parent().unwrap_or(Path::new(""));
+            return [current_dir, Path::new("")]
+                .iter()
+                .find_map(|dir| self.file_id(&normalize_path(&dir.join(source))))
+                .into_iter()
+                .collect();
+        }
+
+        let name = source.trim_start_matches('\\');
+        for (prefix, dirs) in &self.php.psr4 {
+            let Some(rest) = name.strip_prefix(prefix.as_str()) else { continue };
+            let relative = format!("{}.php", rest.replace('\\', "/"));
+            if let Some(id) = dirs.iter().find_map(|dir| self.file_id(&normalize_path(&dir.join(&relative)))) {
+                return vec![id];
+            }
+        }
+
+        // Classes outside the autoloader and `use function`/`use const`
+        if let Some((namespace, declared)) = name.rsplit_once('\\') {
+            let found: Vec<&'a str> = self
+                .php
+                .namespaces
+                .get(namespace)
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|id| self.php.declarations.get(id).is_some_and(|names| names.iter().any(|n| n == declared)))
+                .collect();
+            if !found.is_empty() {
+                return found;
+            }
+        }
+
+        // `use App\Models;` aliases a namespace
+        self.php.namespaces.get(name).cloned().unwrap_or_default()
+    }
+}
+
+/// PSR-4 prefixes and base directories of a composer.json in `dir`
+fn psr4_mappings(composer: &Value, dir: &Path) -> Vec<(String, Vec<PathBuf>)> {
+    let mut mappings = Vec::new();
+
+    for section in ["autoload", "autoload-dev"] {
+        let Some(psr4) = composer.get(section).and_then(|a| a.get("psr-4")).and_then(Value::as_object) else {
+            continue;
+        };
+        for (prefix, paths) in psr4 {
+            // "App\\": "src/" or "App\\": ["src/", "lib/"]
+            let paths: Vec<PathBuf> = match paths {
+                Value::String(path) => vec![normalize_path(&dir.join(path))],
+                Value::Array(items) => items
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(|path| normalize_path(&dir.join(path)))
+                    .collect(),
+                _ => continue,
+            };
+            mappings.push((prefix.trim_start_matches('\\').to_string(), paths));
+        }
+    }
+
+    mappings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::resolvers::file_record;
+    use tempfile::tempdir;
+
+    fn import(source: &str) -> ImportInfo {
+        ImportInfo {
+            source: source.to_string(),
+            imported_names: source.rsplit('\\').next().map(str::to_string).into_iter().collect(),
+            line: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_psr4_mappings() {
+        let composer: Value = serde_json::from_str(
+            r#"{
+                "autoload": { "psr-4": { "App\\": "src/", "Lib\\": ["lib/", "vendor-lib/"] } },
+                "autoload-dev": { "psr-4": { "Tests\\": "tests/" } }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            psr4_mappings(&composer, Path::new("packages/core")),
+            vec![
+                ("App\\".to_string(), vec![PathBuf::from("packages/core/src")]),
+                (
+                    "Lib\\".to_string(),
+                    vec![PathBuf::from("packages/core/lib"), PathBuf::from("packages/core/vendor-lib")]
+                ),
+                ("Tests\\".to_string(), vec![PathBuf::from("packages/core/tests")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_php_imports() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("composer.json"),
+            r#"{ "autoload": { "psr-4": { "App\\": "src/", "App\\Legacy\\": "legacy/" } } }"#,
+        )
+        .unwrap();
+
+        let files = vec![
+            file_record("src/Models/User.php"),
+            file_record("legacy/Mailer.php"),
+            file_record("src/Support/helpers.php"),
+            file_record("public/index.php"),
+            file_record("bootstrap.php"),
+        ];
+        let mut parsed_files = HashMap::new();
+        parsed_files.insert(
+            "src/Support/helpers.php".to_string(),
+            ParseResult {
+                namespaces: vec!["App\\Support".to_string()],
+                ..Default::default()
+            },
+        );
+        let symbols = vec![SymbolRecord {
+            id: "helper".to_string(),
+            file_id: "src/Support/helpers.php".to_string(),
+            name: "format_money".to_string(),
+            kind: "function".to_string(),
+            line: 1,
+            column: 0,
+            end_line: None,
+            end_column: None,
+            signature: None,
+            documentation: None,
+            is_exported: true,
+            parent_id: None,
+        }];
+        let resolver = ImportResolver::new(dir.path(), &files, &parsed_files).with_declarations(&symbols);
+        let index = &files[3];
+
+        assert_eq!(resolver.resolve(&import("App\\Models\\User"), index), vec!["src/Models/User.php"]);
+        // The longest prefix wins
+        assert_eq!(resolver.resolve(&import("\\App\\Legacy\\Mailer"), index), vec!["legacy/Mailer.php"]);
+        assert_eq!(resolver.resolve(&import("App\\Support\\format_money"), index), vec!["src/Support/helpers.php"]);
+        assert_eq!(resolver.resolve(&import("./../bootstrap.php"), index), vec!["bootstrap.php"]);
+        assert_eq!(resolver.resolve(&import("src/Support/helpers.php"), index), vec!["src/Support/helpers.php"]);
+        assert!(resolver.resolve(&import("Psr\\Log\\LoggerInterface"), index).is_empty());
+    }
+}
@@ -0,0 +1,299 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{normalize_path, ImportResolver};
+use crate::analysis::parser::ImportInfo;
+use crate::storage::FileRecord;
+
+/// Files that make a path importable as a module, in lookup order
+const MODULE_SUFFIXES: &[&str] = &[".py", ".pyi", "/__init__.py", "/__init__.pyi"];
+
+/// Directories Python imports are resolved against, project-relative
+#[derive(Debug, Default)]
+pub struct PythonRoots {
+    roots: Vec<PathBuf>,
+}
+
+impl PythonRoots {
+    /// Source roots declared in pyproject.toml/setup.cfg, the conventional `src/` layout,
+    /// and finally the project root itself
+    pub fn load(project_path: &Path, files: &[FileRecord]) -> Self {
+        let mut roots = Vec::new();
+
+        if let Ok(content) = fs::read_to_string(project_path.join("pyproject.toml")) {
+            roots.extend(roots_from_pyproject(&content));
+        }
+        if let Ok(content) = fs::read_to_string(project_path.join("setup.cfg")) {
+            roots.extend(roots_from_setup_cfg(&content));
+        }
+
+        // src/ layout without packaging metadata, unless src is itself a package
+        let has_src_modules = files
+            .iter()
+            .any(|f| f.language == "python" && f.path.starts_with("src/"));
+        let src_is_package = files.iter().any(|f| f.path == "src/__init__.py");
+        if has_src_modules && !src_is_package {
+            roots.push(PathBuf::from("src"));
+        }
+
+        roots.push(PathBuf::new());
+
+        let mut unique = Vec::new();
+        for root in roots.into_iter().map(|r| normalize_path(&r)) {
+            if !unique.contains(&root) {
+                unique.push(root);
+            }
+        }
+
+        Self { roots: unique }
+    }
+}
+
+impl<'a> ImportResolver<'a> {
+    /// Resolve a Python import. Dotted names map to `a/b/c.py` or `a/b/c/__init__.py`
+    /// under each source root; leading dots are relative to the importing package.
+    /// `from pkg import mod` resolves to the submodule file when `mod` is one.
+    pub(super) fn resolve_python(&self, import: &ImportInfo, current_path: &str) -> Vec<&'a str> {
+        let current_dir = Path::new(current_path).parent().unwrap_or(Path::new(""));
+        let level = import.source.chars().take_while(|&c| c == '.').count();
+        let module_path = module_to_path(&import.source[level..]);
+
+        // `import a.b` binds modules only; `from a import b` may name submodules
+        let names: &[String] = if import.is_default { &[] } else { &import.imported_names };
+
+        if level > 0 {
+            // One dot is the current package, each further dot goes up a level
+            let mut package = current_dir.to_path_buf();
+            for _ in 1..level {
+                package = normalize_path(&package.join(".."));
+            }
+            return self.python_targets(&normalize_path(&package.join(module_path)), names);
+        }
+
+        // Absolute imports: source roots first, then the importing file's directory
+        // (scripts run directly get their own directory on sys.path)
+        self.python_roots
+            .roots
+            .iter()
+            .map(PathBuf::as_path)
+            .chain(std::iter::once(current_dir))
+            .map(|root| self.python_targets(&root.join(&module_path), names))
+            .find(|targets| !targets.is_empty())
+            .unwrap_or_default()
+    }
+
+    /// Files for a module path plus any imported names that are submodules of it
+    fn python_targets(&self, module_path: &Path, names: &[String]) -> Vec<&'a str> {
+        let mut targets = Vec::new();
+        let mut needs_module = names.is_empty();
+
+        for name in names {
+            match (name != "*").then(|| self.python_module(&module_path.join(name))).flatten() {
+                Some(id) => targets.push(id),
+                None => needs_module = true,
+            }
+        }
+
+        if needs_module {
+            if let Some(id) = self.python_module(module_path) {
+                targets.insert(0, id);
+            }
+        }
+
+        targets
+    }
+
+    /// The file implementing a module path, if it exists in the project
+    fn python_module(&self, module_path: &Path) -> Option<&'a str> {
+        if module_path.as_os_str().is_empty() {
+            // The package itself, e.g. `from . import x` at a package root
+            return self.file_id(Path::new("__init__.py"));
+        }
+        self.file_id_with_extensions(module_path, MODULE_SUFFIXES)
+    }
+}
+
+/// `a.b.c` -> `a/b/c`
+fn module_to_path(module: &str) -> PathBuf {
+    module.split('.').filter(|part| !part.is_empty()).collect()
+}
+
+/// Source roots from setuptools, poetry, hatch and pytest settings in pyproject.toml
+fn roots_from_pyproject(content: &str) -> Vec<PathBuf> {
+    let doc: toml::Value = match content.parse() {
+        Ok(doc) => doc,
+        Err(e) => {
+            tracing::warn!("Failed to parse pyproject.toml: {}", e);
+            return vec![];
+        }
+    };
+
+    let mut roots = Vec::new();
+    let tool = doc.get("tool");
+    let get = |path: &[&str]| -> Option<&toml::Value> {
+        path.iter().try_fold(tool?, |value, key| value.get(key))
+    };
+    let strings = |value: Option<&toml::Value>| -> Vec<String> {
+        match value {
+            Some(toml::Value::String(s)) => vec![s.clone()],
+            Some(toml::Value::Array(items)) => items
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect(),
+            _ => vec![],
+        }
+    };
+
+    // [tool.setuptools.packages.find] where = ["src"]
+    roots.extend(strings(get(&["setuptools", "packages", "find", "where"])).into_iter().map(PathBuf::from));
+
+    // [tool.setuptools] package-dir = { "" = "src" }
+    if let Some(dir) = get(&["setuptools", "package-dir", ""]).and_then(|v| v.as_str()) {
+        roots.push(PathBuf::from(dir));
+    }
+
+    // [tool.poetry] packages = [{ include = "pkg", from = "src" }]
+    if let Some(packages) = get(&["poetry", "packages"]).and_then(|v| v.as_array()) {
+        roots.extend(
+            packages
+                .iter()
+                .filter_map(|p| p.get("from").and_then(|f| f.as_str()))
+                .map(PathBuf::from),
+        );
+    }
+
+    // [tool.hatch.build.targets.wheel] packages = ["src/pkg"]
+    roots.extend(
+        strings(get(&["hatch", "build", "targets", "wheel", "packages"]))
+            .iter()
+            .filter_map(|p| Path::new(p).parent().map(Path::to_path_buf)),
+    );
+
+    // [tool.pytest.ini_options] pythonpath = ["src"]
+    roots.extend(strings(get(&["pytest", "ini_options", "pythonpath"])).into_iter().map(PathBuf::from));
+
+    roots
+}
+
+/// Source roots from `package_dir` and `where` in setup.cfg
+fn roots_from_setup_cfg(content: &str) -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    let mut section = String::new();
+    let mut key = String::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+            continue;
+        }
+
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            section = trimmed[1..trimmed.len() - 1].trim().to_string();
+            continue;
+        }
+
+        // Indented lines continue the previous key's value
+        let value = if line.starts_with(char::is_whitespace) {
+            trimmed
+        } else if let Some((k, v)) = trimmed.split_once('=') {
+            key = k.trim().to_string();
+            v.trim()
+        } else {
+            continue;
+        };
+
+        match (section.as_str(), key.as_str()) {
+            // package_dir =
+            //     =src
+            ("options", "package_dir") => {
+                if let Some(dir) = value.strip_prefix('=') {
+                    roots.push(PathBuf::from(dir.trim()));
+                }
+            }
+            ("options.packages.find", "where") if !value.is_empty() => {
+                roots.push(PathBuf::from(value));
+            }
+            _ => {}
+        }
+    }
+
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::resolvers::file_record;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    fn import(source: &str, names: &[&str]) -> ImportInfo {
+        ImportInfo {
+            source: source.to_string(),
+            imported_names: names.iter().map(|n| n.to_string()).collect(),
+            is_default: names.is_empty(),
+            line: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_packages_and_relative_levels() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("pyproject.toml"), r#"
+[tool.setuptools.packages.find]
+where = ["lib"]
+        "#).unwrap();
+
+        let files = vec![
+            file_record("lib/app/__init__.py"),
+            file_record("lib/app/services/__init__.py"),
+            file_record("lib/app/services/auth.py"),
+            file_record("lib/app/utils.py"),
+            file_record("lib/app/models/user.py"),
+            file_record("scripts/auth.py"),
+        ];
+        let resolver = ImportResolver::new(dir.path(), &files, &HashMap::new());
+        let auth = &files[2];
+
+        assert_eq!(resolver.resolve(&import("app.services.auth", &[]), &files[5]), vec!["lib/app/services/auth.py"]);
+        assert_eq!(resolver.resolve(&import("app.services", &[]), &files[5]), vec!["lib/app/services/__init__.py"]);
+        assert_eq!(resolver.resolve(&import("..utils", &["helper"]), auth), vec!["lib/app/utils.py"]);
+        assert_eq!(resolver.resolve(&import(".", &["auth"]), &files[1]), vec!["lib/app/services/auth.py"]);
+        // Submodule and package attribute imported together
+        assert_eq!(
+            resolver.resolve(&import("..", &["utils", "VERSION"]), auth),
+            vec!["lib/app/__init__.py", "lib/app/utils.py"]
+        );
+        assert_eq!(resolver.resolve(&import("..models.user", &["User"]), auth), vec!["lib/app/models/user.py"]);
+        // Standard library and third-party modules don't resolve by file name
+        assert!(resolver.resolve(&import("os.path", &[]), auth).is_empty());
+    }
+
+    #[test]
+    fn test_source_roots_from_config() {
+        let pyproject = r#"
+[tool.poetry]
+packages = [{ include = "pkg", from = "source" }]
+
+[tool.pytest.ini_options]
+pythonpath = ["tests/helpers"]
+        "#;
+        assert_eq!(
+            roots_from_pyproject(pyproject),
+            vec![PathBuf::from("source"), PathBuf::from("tests/helpers")]
+        );
+
+        let setup_cfg = "[options]\npackage_dir =\n    =src\n\n[options.packages.find]\nwhere = lib\n";
+        assert_eq!(roots_from_setup_cfg(setup_cfg), vec![PathBuf::from("src"), PathBuf::from("lib")]);
+    }
+
+    #[test]
+    fn test_src_layout_detected_without_config() {
+        let dir = tempdir().unwrap();
+        let files = vec![file_record("src/pkg/__init__.py"), file_record("src/pkg/core.py"), file_record("tests/test_core.py")];
+        let resolver = ImportResolver::new(dir.path(), &files, &HashMap::new());
+
+        assert_eq!(resolver.resolve(&import("pkg", &["core"]), &files[2]), vec!["src/pkg/core.py"]);
+    }
+}
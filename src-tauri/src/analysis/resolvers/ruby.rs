@@ -0,0 +1,87 @@
+use std::path::{Path, PathBuf};
+
+use super::{normalize_path, ImportResolver};
+use crate::storage::FileRecord;
+
+/// Directories `require` is resolved against, project-relative
+#[derive(Debug, Default)]
+pub struct RubyLoadPath {
+    roots: Vec<PathBuf>,
+}
+
+impl RubyLoadPath {
+    /// Every `lib/` directory holding Ruby files (gems and Rails apps put
+    /// them on `$LOAD_PATH`), then the project root
+    pub fn load(files: &[FileRecord]) -> Self {
+        let mut roots: Vec<PathBuf> = Vec::new();
+
+        for file in files.iter().filter(|f| f.language == "ruby") {
+            let lib = Path::new(&file.path)
+                .ancestors()
+                .skip(1)
+                .find(|dir| dir.file_name().is_some_and(|name| name == "lib"));
+            if let Some(lib) = lib {
+                if !roots.iter().any(|r| r == lib) {
+                    roots.push(lib.to_path_buf());
+                }
+            }
+        }
+
+        roots.sort();
+        roots.push(PathBuf::new());
+        Self { roots }
+    }
+}
+
+impl<'a> ImportResolver<'a> {
+    /// Resolve a `require`: `./` and `../` paths (from `require_relative`) are
+    /// relative to the requiring file, anything else is looked up on the load path
+    pub(super) fn resolve_ruby(&self, source: &str, current_path: &str) -> Option<&'a str> {
+        let extensions: &[&str] = if source.ends_with(".rb") { &[""] } else { &[".rb", ""] };
+
+        if source.starts_with("./") || source.starts_with("../") {
+            let current_dir = Path::new(current_path).parent().unwrap_or(Path::new(""));
+            return self.file_id_with_extensions(&normalize_path(&current_dir.join(source)), extensions);
+        }
+
+        self.ruby
+            .roots
+            .iter()
+            .find_map(|root| self.file_id_with_extensions(&root.join(source), extensions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::parser::ImportInfo;
+    use crate::analysis::resolvers::file_record;
+    use std::collections::HashMap;
+
+    fn import(source: &str) -> ImportInfo {
+        ImportInfo {
+            source: source.to_string(),
+            line: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_requires() {
+        let files = vec![
+            file_record("lib/billing.rb"),
+            file_record("lib/billing/invoice.rb"),
+            file_record("lib/billing/formatter.rb"),
+            file_record("spec/billing_spec.rb"),
+            file_record("spec/spec_helper.rb"),
+        ];
+        let resolver = ImportResolver::new(Path::new("/proj"), &files, &HashMap::new());
+
+        assert_eq!(resolver.resolve(&import("billing/invoice"), &files[3]), vec!["lib/billing/invoice.rb"]);
+        assert_eq!(resolver.resolve(&import("./formatter"), &files[1]), vec!["lib/billing/formatter.rb"]);
+        assert_eq!(resolver.resolve(&import("../lib/billing"), &files[3]), vec!["lib/billing.rb"]);
+        assert_eq!(resolver.resolve(&import("spec/spec_helper.rb"), &files[3]), vec!["spec/spec_helper.rb"]);
+        // Gems and the standard library are not project files
+        assert!(resolver.resolve(&import("json"), &files[0]).is_empty());
+    }
+}
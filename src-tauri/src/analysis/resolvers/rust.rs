@@ -0,0 +1,458 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{normalize_path, ImportResolver};
+use crate::analysis::parser::{ModuleDeclInfo, ParseResult};
+use crate::storage::FileRecord;
+
+/// Source directories whose top-level files are each the root of their own crate
+const CRATE_ROOT_DIRS: &[&str] = &["src/bin", "examples", "tests", "benches"];
+
+/// Rust crates in a project and the module tree of each
+#[derive(Debug, Default)]
+pub struct RustCrates<'a> {
+    /// Library crate name (as written in `use`, with `-` replaced by `_`) -> crate index
+    libraries: HashMap<String, usize>,
+    /// File path -> (crate index, module path). A file reachable from several
+    /// crate roots (lib.rs and main.rs both declaring `mod x;`) keeps the first.
+    file_modules: HashMap<PathBuf, (usize, Vec<String>)>,
+    /// (crate index, module path) -> file ID. Inline modules map to the file containing them.
+    modules: HashMap<(usize, Vec<String>), &'a str>,
+}
+
+/// A Cargo package as described by its Cargo.toml
+#[derive(Debug)]
+struct Package {
+    dir: PathBuf,
+    name: String,
+    lib: Option<PathBuf>,
+    /// Explicit `[[bin]]`, `[[example]]`, `[[test]]` and `[[bench]]` paths
+    targets: Vec<PathBuf>,
+}
+
+impl<'a> RustCrates<'a> {
+    /// Discover crates from Cargo.toml files (including workspace members) and
+    /// walk each crate's `mod` declarations from its root file
+    pub fn load(
+        project_path: &Path,
+        files: &'a [FileRecord],
+        parsed_files: &HashMap<String, ParseResult>,
+    ) -> Self {
+        let rust_files: HashMap<PathBuf, &'a FileRecord> = files
+            .iter()
+            .filter(|f| f.language == "rust")
+            .map(|f| (PathBuf::from(&f.path), f))
+            .collect();
+        if rust_files.is_empty() {
+            return Self::default();
+        }
+
+        let packages = find_packages(project_path, rust_files.keys());
+
+        // (root file, library name) for every crate
+        let mut roots: Vec<(PathBuf, Option<String>)> = Vec::new();
+        for package in &packages {
+            if let Some(lib) = package.lib.as_ref().filter(|lib| rust_files.contains_key(*lib)) {
+                roots.push((lib.clone(), Some(package.name.clone())));
+            }
+            for path in rust_files.keys() {
+                if is_target_root(package, path) && !roots.iter().any(|(root, _)| root == path) {
+                    roots.push((path.clone(), None));
+                }
+            }
+        }
+
+        // Without a manifest, any lib.rs or main.rs is a crate root named after its directory
+        for path in rust_files.keys() {
+            let claimed = packages.iter().any(|p| path.starts_with(&p.dir));
+            let file_name = path.file_name().and_then(|n| n.to_str());
+            if claimed || !matches!(file_name, Some("lib.rs" | "main.rs")) {
+                continue;
+            }
+            let dir = path.parent().unwrap_or(Path::new(""));
+            let dir = if dir.ends_with("src") { dir.parent().unwrap_or(dir) } else { dir };
+            let name = dir
+                .file_name()
+                .map(|n| n.to_string_lossy().replace('-', "_"));
+            roots.push((path.clone(), name.filter(|_| file_name == Some("lib.rs"))));
+        }
+        roots.sort();
+
+        let mut crates = Self::default();
+        for (index, (root, lib_name)) in roots.iter().enumerate() {
+            if let Some(name) = lib_name {
+                crates.libraries.entry(name.clone()).or_insert(index);
+            }
+            crates.walk_modules(index, root, &rust_files, parsed_files);
+        }
+
+        crates
+    }
+
+    /// Breadth-first walk of the module tree of the crate rooted at `root`
+    fn walk_modules(
+        &mut self,
+        crate_index: usize,
+        root: &Path,
+        rust_files: &HashMap<PathBuf, &'a FileRecord>,
+        parsed_files: &HashMap<String, ParseResult>,
+    ) {
+        let mut visited = HashSet::new();
+        // (file path, module path, whether the file owns its directory like mod.rs)
+        let mut queue = VecDeque::from([(root.to_path_buf(), Vec::new(), true)]);
+
+        while let Some((path, module_path, owns_dir)) = queue.pop_front() {
+            let Some(&file) = rust_files.get(&path) else { continue };
+            if !visited.insert(path.clone()) {
+                continue;
+            }
+
+            self.file_modules
+                .entry(path.clone())
+                .or_insert_with(|| (crate_index, module_path.clone()));
+            self.modules
+                .entry((crate_index, module_path.clone()))
+                .or_insert(file.id.as_str());
+
+            let Some(parsed) = parsed_files.get(&file.id) else { continue };
+            let file_dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+            // `foo.rs` keeps its submodules in `foo/`, `mod.rs` and crate roots in their own directory
+            let module_dir = if owns_dir {
+                file_dir.clone()
+            } else {
+                file_dir.join(path.file_stem().unwrap_or_default())
+            };
+
+            for decl in &parsed.module_decls {
+                let Some((name, inline)) = decl.path.split_last() else { continue };
+
+                // Inline modules along the way live in this file
+                for depth in 1..=inline.len() {
+                    let inline_path = [module_path.as_slice(), &inline[..depth]].concat();
+                    self.modules
+                        .entry((crate_index, inline_path))
+                        .or_insert(file.id.as_str());
+                }
+
+                let child_path = [module_path.as_slice(), &decl.path].concat();
+                let inline_dir = inline.iter().fold(module_dir.clone(), |dir, m| dir.join(m));
+                for (candidate, owns_dir) in module_file_candidates(decl, name, &file_dir, &inline_dir) {
+                    if rust_files.contains_key(&candidate) {
+                        queue.push_back((candidate, child_path.clone(), owns_dir));
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_module(&self, crate_index: usize, module_path: &[String]) -> bool {
+        self.modules.contains_key(&(crate_index, module_path.to_vec()))
+    }
+}
+
+impl<'a> ImportResolver<'a> {
+    /// Resolve a Rust `use` path to the files of the modules it names, walking
+    /// `crate::`, `self::`, `super::` and workspace crate names through the module
+    /// tree. Paths into external crates resolve to nothing.
+    pub(super) fn resolve_rust(&self, source: &str, names: &[String], current_path: &str) -> Vec<&'a str> {
+        let crates = &self.rust;
+        let Some((current_crate, current_module)) = crates.file_modules.get(Path::new(current_path)) else {
+            return Vec::new();
+        };
+
+        let mut segments = source.split("::").filter(|s| !s.is_empty()).peekable();
+        let (crate_index, mut module_path) = match segments.peek().copied() {
+            Some("crate") => {
+                segments.next();
+                (*current_crate, Vec::new())
+            }
+            Some("self" | "super") => {
+                let mut module_path = current_module.clone();
+                while let Some(segment) = segments.next_if(|s| *s == "self" || *s == "super") {
+                    if segment == "super" && module_path.pop().is_none() {
+                        return Vec::new();
+                    }
+                }
+                (*current_crate, module_path)
+            }
+            Some(first) => {
+                // Child modules of the current module shadow crate names
+                let child = [current_module.as_slice(), &[first.to_string()]].concat();
+                if crates.is_module(*current_crate, &child) {
+                    (*current_crate, current_module.clone())
+                } else if let Some(&index) = crates.libraries.get(first) {
+                    segments.next();
+                    (index, Vec::new())
+                } else {
+                    return Vec::new();
+                }
+            }
+            None => return Vec::new(),
+        };
+
+        // Descend while segments name modules; the rest are items inside the last one
+        for segment in segments {
+            module_path.push(segment.to_string());
+            if !crates.is_module(crate_index, &module_path) {
+                module_path.pop();
+                break;
+            }
+        }
+
+        let mut targets = Vec::new();
+        let mut imports_items = names.is_empty();
+        for name in names {
+            let child = [module_path.as_slice(), std::slice::from_ref(name)].concat();
+            match crates.modules.get(&(crate_index, child)) {
+                Some(&id) => targets.push(id),
+                None => imports_items = true,
+            }
+        }
+        if imports_items {
+            if let Some(&id) = crates.modules.get(&(crate_index, module_path)) {
+                targets.insert(0, id);
+            }
+        }
+
+        let current_id = self.file_id(Path::new(current_path));
+        let mut seen = HashSet::new();
+        targets.retain(|id| Some(*id) != current_id && seen.insert(*id));
+        targets
+    }
+}
+
+/// Files a `mod` declaration may load, with whether each owns its directory
+fn module_file_candidates(
+    decl: &ModuleDeclInfo,
+    name: &str,
+    file_dir: &Path,
+    inline_dir: &Path,
+) -> Vec<(PathBuf, bool)> {
+    match &decl.path_attribute {
+        // `#[path]` is relative to the file's directory, or to the inline module's
+        Some(path) if decl.path.len() == 1 => vec![(normalize_path(&file_dir.join(path)), true)],
+        Some(path) => vec![(normalize_path(&inline_dir.join(path)), true)],
+        None => vec![
+            (inline_dir.join(format!("{}.rs", name)), false),
+            (inline_dir.join(name).join("mod.rs"), true),
+        ],
+    }
+}
+
+/// Whether `path` is a binary, example, test, bench or build script root of `package`
+fn is_target_root(package: &Package, path: &Path) -> bool {
+    if package.targets.iter().any(|target| target == path) {
+        return true;
+    }
+    let Ok(relative) = path.strip_prefix(&package.dir) else { return false };
+    let relative = relative.to_string_lossy();
+
+    if relative == "src/main.rs" || relative == "build.rs" {
+        return true;
+    }
+    CRATE_ROOT_DIRS.iter().any(|dir| {
+        let Some(rest) = relative.strip_prefix(dir).and_then(|r| r.strip_prefix('/')) else {
+            return false;
+        };
+        // `tests/foo.rs` or `tests/foo/main.rs`
+        !rest.contains('/') || rest.split('/').count() == 2 && rest.ends_with("/main.rs")
+    })
+}
+
+/// Cargo packages owning the given files, plus the members of any workspace root
+fn find_packages<'p>(project_path: &Path, paths: impl Iterator<Item = &'p PathBuf>) -> Vec<Package> {
+    let mut manifest_dirs: HashSet<PathBuf> = HashSet::new();
+    for path in paths {
+        if let Some(dir) = path
+            .ancestors()
+            .skip(1)
+            .find(|dir| project_path.join(dir).join("Cargo.toml").is_file())
+        {
+            manifest_dirs.insert(dir.to_path_buf());
+        }
+    }
+
+    // Workspace roots list members that may not own any file we saw above
+    let roots: Vec<PathBuf> = manifest_dirs
+        .iter()
+        .flat_map(|dir| dir.ancestors().map(Path::to_path_buf).collect::<Vec<_>>())
+        .collect();
+    for dir in roots {
+        let Some(manifest) = read_manifest(project_path, &dir) else { continue };
+        for member in workspace_members(&manifest) {
+            manifest_dirs.extend(expand_member(project_path, &dir, &member));
+        }
+    }
+
+    let mut packages: Vec<Package> = manifest_dirs
+        .into_iter()
+        .filter_map(|dir| {
+            let manifest = read_manifest(project_path, &dir)?;
+            parse_package(dir, &manifest)
+        })
+        .collect();
+    packages.sort_by(|a, b| a.dir.cmp(&b.dir));
+    packages
+}
+
+fn read_manifest(project_path: &Path, dir: &Path) -> Option<toml::Table> {
+    let content = fs::read_to_string(project_path.join(dir).join("Cargo.toml")).ok()?;
+    content.parse().ok()
+}
+
+/// `[workspace] members`
+fn workspace_members(manifest: &toml::Table) -> Vec<String> {
+    manifest
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .map(|members| {
+            members
+                .iter()
+                .filter_map(|m| m.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Member directories of a workspace, expanding a trailing `/*` glob
+fn expand_member(project_path: &Path, workspace_dir: &Path, member: &str) -> Vec<PathBuf> {
+    match member.strip_suffix("/*") {
+        Some(parent) => {
+            let parent = normalize_path(&workspace_dir.join(parent));
+            fs::read_dir(project_path.join(&parent))
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .filter(|e| e.path().join("Cargo.toml").is_file())
+                        .map(|e| parent.join(e.file_name()))
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+        None => vec![normalize_path(&workspace_dir.join(member))],
+    }
+}
+
+/// Name, library root and explicit target paths of a `[package]` manifest
+fn parse_package(dir: PathBuf, manifest: &toml::Table) -> Option<Package> {
+    let package_name = manifest.get("package")?.get("name")?.as_str()?;
+    let lib = manifest.get("lib");
+
+    let name = lib
+        .and_then(|l| l.get("name"))
+        .and_then(|n| n.as_str())
+        .unwrap_or(package_name)
+        .replace('-', "_");
+    let lib_path = lib
+        .and_then(|l| l.get("path"))
+        .and_then(|p| p.as_str())
+        .unwrap_or("src/lib.rs");
+
+    let targets = ["bin", "example", "test", "bench"]
+        .iter()
+        .filter_map(|kind| manifest.get(*kind)?.as_array())
+        .flatten()
+        .filter_map(|target| target.get("path")?.as_str())
+        .map(|path| normalize_path(&dir.join(path)))
+        .collect();
+
+    Some(Package {
+        lib: Some(normalize_path(&dir.join(lib_path))),
+        name,
+        targets,
+        dir,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::parser::{Parser, SupportedLanguage};
+    use crate::analysis::resolvers::file_record;
+    use tempfile::tempdir;
+
+    /// Write the given Rust files, parse them and build a resolver over them
+    fn setup(dir: &Path, sources: &[(&str, &str)]) -> (Vec<FileRecord>, HashMap<String, ParseResult>) {
+        let parser = Parser::new();
+        let mut files = Vec::new();
+        let mut parsed = HashMap::new();
+
+        for (path, source) in sources {
+            let full_path = dir.join(path);
+            fs::create_dir_all(full_path.parent().unwrap()).unwrap();
+            fs::write(&full_path, source).unwrap();
+            if !path.ends_with(".rs") {
+                continue;
+            }
+            parsed.insert(path.to_string(), parser.parse_file(path, SupportedLanguage::Rust, source).unwrap());
+            files.push(file_record(path));
+        }
+
+        (files, parsed)
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_module_tree_and_use_paths() {
+        let dir = tempdir().unwrap();
+        let (files, parsed) = setup(dir.path(), &[
+            ("Cargo.toml", "[package]\nname = \"app\"\n"),
+            ("src/lib.rs", "pub mod storage;\nmod analysis;\n#[path = \"platform/unix.rs\"]\nmod sys;\n"),
+            ("src/storage/mod.rs", "mod models;\npub use models::*;\n"),
+            ("src/storage/models.rs", "use super::super::analysis::Engine;\n"),
+            ("src/analysis.rs", "mod parser;\n"),
+            ("src/analysis/parser.rs", "use crate::{storage, sys::Handle};\n"),
+            ("src/platform/unix.rs", ""),
+        ]);
+        let resolver = ImportResolver::new(dir.path(), &files, &parsed);
+
+        assert_eq!(resolver.resolve_rust("self", &names(&["storage"]), "src/lib.rs"), vec!["src/storage/mod.rs"]);
+        assert_eq!(resolver.resolve_rust("self", &names(&["sys"]), "src/lib.rs"), vec!["src/platform/unix.rs"]);
+        assert_eq!(resolver.resolve_rust("models", &names(&["*"]), "src/storage/mod.rs"), vec!["src/storage/models.rs"]);
+        assert_eq!(
+            resolver.resolve_rust("super::super::analysis", &names(&["Engine"]), "src/storage/models.rs"),
+            vec!["src/analysis.rs"]
+        );
+        assert_eq!(
+            resolver.resolve_rust("crate", &names(&["storage"]), "src/analysis/parser.rs"),
+            vec!["src/storage/mod.rs"]
+        );
+        assert_eq!(
+            resolver.resolve_rust("crate::sys", &names(&["Handle"]), "src/analysis/parser.rs"),
+            vec!["src/platform/unix.rs"]
+        );
+        assert!(resolver.resolve_rust("serde", &names(&["Serialize"]), "src/lib.rs").is_empty());
+    }
+
+    #[test]
+    fn test_workspace_crates() {
+        let dir = tempdir().unwrap();
+        let (files, parsed) = setup(dir.path(), &[
+            ("Cargo.toml", "[workspace]\nmembers = [\"crates/*\", \"app\"]\n"),
+            ("crates/core-types/Cargo.toml", "[package]\nname = \"core-types\"\n"),
+            ("crates/core-types/src/lib.rs", "pub mod ids;\n"),
+            ("crates/core-types/src/ids.rs", ""),
+            ("app/Cargo.toml", "[package]\nname = \"app\"\n\n[[bin]]\nname = \"cli\"\npath = \"cli/main.rs\"\n"),
+            ("app/src/main.rs", "use core_types::ids::FileId;\n"),
+            ("app/cli/main.rs", "use core_types;\n"),
+            ("app/tests/smoke.rs", "use core_types::ids;\n"),
+        ]);
+        let resolver = ImportResolver::new(dir.path(), &files, &parsed);
+
+        assert_eq!(
+            resolver.resolve_rust("core_types::ids", &names(&["FileId"]), "app/src/main.rs"),
+            vec!["crates/core-types/src/ids.rs"]
+        );
+        assert_eq!(resolver.resolve_rust("core_types", &[], "app/cli/main.rs"), vec!["crates/core-types/src/lib.rs"]);
+        assert_eq!(
+            resolver.resolve_rust("core_types", &names(&["ids"]), "app/tests/smoke.rs"),
+            vec!["crates/core-types/src/ids.rs"]
+        );
+    }
+}
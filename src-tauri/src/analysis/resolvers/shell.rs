@@ -0,0 +1,41 @@
+use std::path::Path;
+
+use super::{normalize_path, ImportResolver};
+
+impl<'a> ImportResolver<'a> {
+    /// Resolve a sourced or invoked script: relative to the calling script,
+    /// then to the project root, where CI and Makefiles usually run scripts from
+    pub(super) fn resolve_shell(&self, source: &str, current_path: &str) -> Option<&'a str> {
+        let current_dir = Path::new(current_path).parent().unwrap_or(Path::new(""));
+        [current_dir, Path::new("")]
+            .iter()
+            .find_map(|dir| self.file_id(&normalize_path(&dir.join(source))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::parser::ImportInfo;
+    use crate::analysis::resolvers::file_record;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_resolve_scripts() {
+        let files = vec![
+            file_record("scripts/deploy.sh"),
+            file_record("scripts/lib/common.sh"),
+            file_record("tools/check"),
+        ];
+        let resolver = ImportResolver::new(Path::new("/proj"), &files, &HashMap::new());
+        let import = |source: &str| ImportInfo {
+            source: source.to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(resolver.resolve(&import("./lib/common.sh"), &files[0]), vec!["scripts/lib/common.sh"]);
+        assert_eq!(resolver.resolve(&import("tools/check"), &files[0]), vec!["tools/check"]);
+        assert_eq!(resolver.resolve(&import("../deploy.sh"), &files[1]), vec!["scripts/deploy.sh"]);
+        assert!(resolver.resolve(&import("/usr/local/bin/tool"), &files[0]).is_empty());
+    }
+}
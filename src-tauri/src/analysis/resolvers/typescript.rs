@@ -0,0 +1,390 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use super::{normalize_path, parse_jsonc, ImportResolver};
+use crate::storage::FileRecord;
+
+/// Extensions tried, in order, when resolving a TypeScript/JavaScript module path
+const TS_EXTENSIONS: &[&str] = &["", ".ts", ".tsx", ".d.ts", ".js", ".jsx", ".mjs", ".cjs", ".json"];
+
+/// Index files tried when an import points at a directory
+const TS_INDEX_FILES: &[&str] = &["index.ts", "index.tsx", "index.js", "index.jsx"];
+
+/// Config file names that govern the files in their directory tree
+const ROOT_CONFIG_NAMES: &[&str] = &["tsconfig.json", "jsconfig.json"];
+
+/// Maximum `extends` chain length, guards against cycles
+const MAX_EXTENDS_DEPTH: usize = 8;
+
+/// Module resolution settings of one tsconfig.json/jsconfig.json, with `extends` applied.
+/// All paths are relative to the project root.
+#[derive(Debug, Clone, Default)]
+pub struct TsConfig {
+    /// Directory containing the config file
+    dir: PathBuf,
+    /// `compilerOptions.baseUrl`
+    base_url: Option<PathBuf>,
+    /// `compilerOptions.paths`, targets already joined onto their base directory
+    paths: Vec<(String, Vec<String>)>,
+    /// Literal prefixes of the `include` (or `files`) patterns; None covers the whole directory
+    include: Option<Vec<PathBuf>>,
+    /// Config files listed in `references`
+    references: Vec<PathBuf>,
+}
+
+impl TsConfig {
+    /// Whether this config's `include` covers the given file
+    fn includes(&self, file_path: &Path) -> bool {
+        match &self.include {
+            Some(prefixes) => prefixes.iter().any(|prefix| file_path.starts_with(prefix)),
+            None => file_path.starts_with(&self.dir),
+        }
+    }
+
+    /// Take over settings from an extended config
+    fn inherit(&mut self, base: TsConfig) {
+        if base.base_url.is_some() {
+            self.base_url = base.base_url;
+        }
+        if !base.paths.is_empty() {
+            self.paths = base.paths;
+        }
+        if base.include.is_some() {
+            self.include = base.include;
+        }
+    }
+
+    /// Candidate module paths for a non-relative import, from `paths` then `baseUrl`
+    fn candidates(&self, import_source: &str) -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+
+        // Like tsc, the pattern with the longest prefix before `*` wins
+        let best_match = self
+            .paths
+            .iter()
+            .filter_map(|(pattern, targets)| {
+                match_path_pattern(pattern, import_source).map(|captured| (pattern, targets, captured))
+            })
+            .max_by_key(|(pattern, _, _)| pattern.find('*').unwrap_or(pattern.len()));
+
+        if let Some((_, targets, captured)) = best_match {
+            candidates.extend(
+                targets
+                    .iter()
+                    .map(|target| normalize_path(Path::new(&target.replacen('*', captured, 1)))),
+            );
+        }
+
+        if let Some(base_url) = &self.base_url {
+            candidates.push(normalize_path(&base_url.join(import_source)));
+        }
+
+        candidates
+    }
+}
+
+/// All tsconfig/jsconfig files in a project
+#[derive(Debug, Default)]
+pub struct TsConfigSet {
+    /// Config file path -> parsed config
+    configs: HashMap<PathBuf, TsConfig>,
+}
+
+impl TsConfigSet {
+    pub fn load(project_path: &Path, files: &[FileRecord]) -> Self {
+        let configs = files
+            .iter()
+            .filter(|f| is_config_file_name(&f.name))
+            .filter_map(|f| {
+                let path = PathBuf::from(&f.path);
+                load_config(project_path, &path, 0).map(|config| (path, config))
+            })
+            .collect();
+
+        Self { configs }
+    }
+
+    /// The config governing a file: the nearest tsconfig.json/jsconfig.json above it,
+    /// or the first of its project references whose `include` covers the file
+    pub fn config_for(&self, file_path: &Path) -> Option<&TsConfig> {
+        let nearest = file_path.ancestors().skip(1).find_map(|dir| {
+            ROOT_CONFIG_NAMES
+                .iter()
+                .find_map(|name| self.configs.get(&dir.join(name)))
+        })?;
+
+        if nearest.includes(file_path) {
+            return Some(nearest);
+        }
+
+        nearest
+            .references
+            .iter()
+            .filter_map(|reference| self.configs.get(reference))
+            .find(|config| config.includes(file_path))
+            .or(Some(nearest))
+    }
+}
+
+impl<'a> ImportResolver<'a> {
+    /// Resolve a TypeScript/JavaScript import: relative paths first, then the governing
+    /// tsconfig's `paths` and `baseUrl`. Projects without a tsconfig fall back to name matching.
+    pub(super) fn resolve_typescript(&self, import_source: &str, current_path: &str) -> Option<&'a str> {
+        if is_relative_specifier(import_source) {
+            let current_dir = Path::new(current_path).parent()?;
+            return self.resolve_ts_module(&normalize_path(&current_dir.join(import_source)));
+        }
+
+        match self.tsconfigs.config_for(Path::new(current_path)) {
+            Some(config) => config
+                .candidates(import_source)
+                .iter()
+                .find_map(|candidate| self.resolve_ts_module(candidate)),
+            None => self.resolve_by_path_or_name(import_source, current_path),
+        }
+    }
+
+    /// Resolve a module path the way TypeScript does: extensions, `.js` -> `.ts`, index files
+    fn resolve_ts_module(&self, path: &Path) -> Option<&'a str> {
+        if let Some(id) = self.file_id_with_extensions(path, TS_EXTENSIONS) {
+            return Some(id);
+        }
+
+        // ESM-style `import './foo.js'` refers to foo.ts in TypeScript sources
+        let is_js = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| matches!(e, "js" | "jsx" | "mjs" | "cjs"));
+        if is_js {
+            let stem = path.with_extension("");
+            if let Some(id) = self.file_id_with_extensions(&stem, &[".ts", ".tsx", ".mts", ".cts"]) {
+                return Some(id);
+            }
+        }
+
+        TS_INDEX_FILES
+            .iter()
+            .find_map(|index| self.file_id(&path.join(index)))
+    }
+}
+
+/// Whether an import specifier is a relative path (`./x`, `../x`, `.`)
+pub fn is_relative_specifier(specifier: &str) -> bool {
+    specifier == "." || specifier == ".." || specifier.starts_with("./") || specifier.starts_with("../")
+}
+
+fn is_config_file_name(name: &str) -> bool {
+    (name.starts_with("tsconfig") || name.starts_with("jsconfig")) && name.ends_with(".json")
+}
+
+/// Load a config file and everything it extends
+fn load_config(project_path: &Path, config_path: &Path, depth: usize) -> Option<TsConfig> {
+    let content = fs::read_to_string(project_path.join(config_path)).ok()?;
+    let json = match parse_jsonc(&content) {
+        Ok(json) => json,
+        Err(e) => {
+            tracing::warn!("Failed to parse {}: {}", config_path.display(), e);
+            return None;
+        }
+    };
+
+    let dir = config_path.parent().map(Path::to_path_buf).unwrap_or_default();
+    let mut config = TsConfig::default();
+
+    // Extended configs first, later entries overriding earlier ones
+    if depth < MAX_EXTENDS_DEPTH {
+        for base_path in extends_paths(&json["extends"], &dir) {
+            if let Some(base) = load_config(project_path, &base_path, depth + 1) {
+                config.inherit(base);
+            }
+        }
+    }
+
+    config.dir = dir.clone();
+
+    let options = &json["compilerOptions"];
+    if let Some(base_url) = options["baseUrl"].as_str() {
+        config.base_url = Some(normalize_path(&dir.join(base_url)));
+    }
+
+    if let Some(paths) = options["paths"].as_object() {
+        // `paths` targets are relative to baseUrl, or to this config when there is none
+        let base = config.base_url.clone().unwrap_or_else(|| dir.clone());
+        config.paths = paths
+            .iter()
+            .map(|(pattern, targets)| {
+                let targets = targets
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .map(|target| base.join(target).to_string_lossy().to_string())
+                    .collect();
+                (pattern.clone(), targets)
+            })
+            .collect();
+    }
+
+    if let Some(include) = json["include"].as_array().or_else(|| json["files"].as_array()) {
+        config.include = Some(
+            include
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|pattern| normalize_path(&dir.join(literal_prefix(pattern))))
+                .collect(),
+        );
+    }
+
+    if let Some(references) = json["references"].as_array() {
+        config.references = references
+            .iter()
+            .filter_map(|r| r["path"].as_str())
+            .map(|reference| {
+                let path = normalize_path(&dir.join(reference));
+                if path.extension().is_some_and(|e| e == "json") {
+                    path
+                } else {
+                    path.join("tsconfig.json")
+                }
+            })
+            .collect();
+    }
+
+    Some(config)
+}
+
+/// Config paths named by `extends` (a string, or an array since TypeScript 5.0)
+pub(super) fn extends_paths(extends: &Value, dir: &Path) -> Vec<PathBuf> {
+    let specifiers: Vec<&str> = match extends {
+        Value::String(s) => vec![s.as_str()],
+        Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+        _ => return vec![],
+    };
+
+    specifiers
+        .into_iter()
+        .map(|specifier| {
+            if is_relative_specifier(specifier) {
+                let path = normalize_path(&dir.join(specifier));
+                if specifier.ends_with(".json") {
+                    path
+                } else {
+                    let mut with_extension = path.into_os_string();
+                    with_extension.push(".json");
+                    PathBuf::from(with_extension)
+                }
+            } else {
+                // Shared configs from packages, e.g. "@tsconfig/node20/tsconfig.json"
+                let path = Path::new("node_modules").join(specifier);
+                if specifier.ends_with(".json") {
+                    path
+                } else {
+                    path.join("tsconfig.json")
+                }
+            }
+        })
+        .collect()
+}
+
+/// Match an import against a `paths` pattern, returning the text captured by `*`
+fn match_path_pattern<'s>(pattern: &str, import_source: &'s str) -> Option<&'s str> {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => {
+            if import_source.len() < prefix.len() + suffix.len() {
+                return None;
+            }
+            import_source.strip_prefix(prefix)?.strip_suffix(suffix)
+        }
+        None => (pattern == import_source).then_some(""),
+    }
+}
+
+/// The leading components of a glob pattern that contain no wildcards
+fn literal_prefix(pattern: &str) -> PathBuf {
+    Path::new(pattern)
+        .components()
+        .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?']))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::resolvers::file_record;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_match_path_pattern() {
+        assert_eq!(match_path_pattern("@/*", "@/components/Foo"), Some("components/Foo"));
+        assert_eq!(match_path_pattern("~lib/*.js", "~lib/util.js"), Some("util"));
+        assert_eq!(match_path_pattern("config", "config"), Some(""));
+        assert_eq!(match_path_pattern("@/*", "react"), None);
+    }
+
+    #[test]
+    fn test_resolve_paths_through_extends() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("app/src/components")).unwrap();
+        fs::write(dir.path().join("tsconfig.base.json"), r#"{
+            // Shared settings
+            "compilerOptions": {
+                "baseUrl": ".",
+                "paths": { "@/*": ["app/src/*"], },
+            },
+        }"#).unwrap();
+        fs::write(dir.path().join("app/tsconfig.json"), r#"{ "extends": "../tsconfig.base" }"#).unwrap();
+
+        let files = vec![
+            file_record("tsconfig.base.json"),
+            file_record("app/tsconfig.json"),
+            file_record("app/src/main.ts"),
+            file_record("app/src/components/Foo.tsx"),
+            file_record("app/src/components/index.ts"),
+            file_record("other/Foo.ts"),
+        ];
+        let resolver = ImportResolver::new(dir.path(), &files, &HashMap::new());
+        let main = &files[2];
+
+        assert_eq!(resolver.resolve_typescript("@/components/Foo", &main.path), Some("app/src/components/Foo.tsx"));
+        assert_eq!(resolver.resolve_typescript("@/components", &main.path), Some("app/src/components/index.ts"));
+        // baseUrl makes non-relative imports project-rooted
+        assert_eq!(resolver.resolve_typescript("other/Foo", &main.path), Some("other/Foo.ts"));
+        // Bare packages stay unresolved instead of matching a same-named file
+        assert_eq!(resolver.resolve_typescript("Foo", &main.path), None);
+        assert_eq!(resolver.resolve_typescript("./components/Foo.js", &main.path), Some("app/src/components/Foo.tsx"));
+    }
+
+    #[test]
+    fn test_project_references_select_config_by_include() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("tsconfig.json"), r#"{
+            "files": [],
+            "references": [{ "path": "./tsconfig.app.json" }, { "path": "./tools" }]
+        }"#).unwrap();
+        fs::write(dir.path().join("tsconfig.app.json"), r#"{
+            "compilerOptions": { "paths": { "@/*": ["./src/*"] } },
+            "include": ["src/**/*"]
+        }"#).unwrap();
+        fs::create_dir_all(dir.path().join("tools")).unwrap();
+        fs::write(dir.path().join("tools/tsconfig.json"), r#"{
+            "compilerOptions": { "paths": { "@/*": ["./lib/*"] } }
+        }"#).unwrap();
+
+        let files = vec![
+            file_record("tsconfig.json"),
+            file_record("tsconfig.app.json"),
+            file_record("tools/tsconfig.json"),
+            file_record("src/App.tsx"),
+            file_record("src/util.ts"),
+            file_record("tools/build.ts"),
+            file_record("tools/lib/util.ts"),
+        ];
+        let resolver = ImportResolver::new(dir.path(), &files, &HashMap::new());
+
+        assert_eq!(resolver.resolve_typescript("@/util", &files[3].path), Some("src/util.ts"));
+        assert_eq!(resolver.resolve_typescript("@/util", &files[5].path), Some("tools/lib/util.ts"));
+    }
+}
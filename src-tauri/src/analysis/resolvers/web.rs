@@ -0,0 +1,64 @@
+use std::path::{Path, PathBuf};
+
+use super::{normalize_path, ImportResolver};
+
+/// Extensions Sass and Less try for an `@import` without one
+const STYLESHEET_EXTENSIONS: &[&str] = &["", ".css", ".scss", ".sass", ".less"];
+
+impl<'a> ImportResolver<'a> {
+    /// Resolve a file an HTML page or stylesheet loads. Root-relative URLs
+    /// (`/src/main.tsx`) are tried against the page's directory, which dev
+    /// servers like Vite serve from, the project root and the `public/`
+    /// directories of both. `@import "variables"` also finds the Sass partial
+    /// `_variables.scss`.
+    pub(super) fn resolve_web(&self, source: &str, current_path: &str) -> Option<&'a str> {
+        let current_dir = Path::new(current_path).parent().unwrap_or(Path::new(""));
+
+        let candidates: Vec<PathBuf> = match source.strip_prefix('/') {
+            Some(rooted) => [current_dir, Path::new(""), &current_dir.join("public"), Path::new("public")]
+                .iter()
+                .map(|base| normalize_path(&base.join(rooted)))
+                .collect(),
+            None => vec![normalize_path(&current_dir.join(source))],
+        };
+
+        candidates.iter().find_map(|path| {
+            self.file_id_with_extensions(path, STYLESHEET_EXTENSIONS).or_else(|| {
+                let name = path.file_name()?.to_str()?;
+                self.file_id_with_extensions(&path.with_file_name(format!("_{}", name)), STYLESHEET_EXTENSIONS)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::parser::ImportInfo;
+    use crate::analysis::resolvers::file_record;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_resolve_web_references() {
+        let files = vec![
+            file_record("web/index.html"),
+            file_record("web/src/main.tsx"),
+            file_record("web/public/favicon.svg"),
+            file_record("styles/main.scss"),
+            file_record("styles/_variables.scss"),
+            file_record("styles/base.css"),
+        ];
+        let resolver = ImportResolver::new(Path::new("/proj"), &files, &HashMap::new());
+        let import = |source: &str| ImportInfo {
+            source: source.to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(resolver.resolve(&import("/src/main.tsx"), &files[0]), vec!["web/src/main.tsx"]);
+        assert_eq!(resolver.resolve(&import("/favicon.svg"), &files[0]), vec!["web/public/favicon.svg"]);
+        assert_eq!(resolver.resolve(&import("../styles/base.css"), &files[0]), vec!["styles/base.css"]);
+        assert_eq!(resolver.resolve(&import("variables"), &files[3]), vec!["styles/_variables.scss"]);
+        assert_eq!(resolver.resolve(&import("base"), &files[3]), vec!["styles/base.css"]);
+        assert!(resolver.resolve(&import("missing.css"), &files[3]).is_empty());
+    }
+}
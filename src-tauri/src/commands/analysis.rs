@@ -4,6 +4,7 @@ use tauri::{ipc::Channel, State};
 
 use crate::analysis::{AnalysisEngine, AnalysisProgress};
 use crate::error::{NexusError, NexusResult};
+use crate::storage::{BrokenLinkRecord, UnresolvedImportRecord};
 use crate::AppState;
 
 /// Start analyzing a project
@@ -41,8 +42,20 @@ pub async fn start_analysis(
     // Clear existing project data synchronously (fast operation)
     repository.clear_project_data(&project_id)?;
 
+    let include_dirs = repository
+        .get_include_dirs(&project_id)?
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+
+    let follow_re_exports = repository.get_follow_re_exports(&project_id)?;
+
     // Create analysis engine
-    let engine = Arc::new(AnalysisEngine::new());
+    let engine = Arc::new(
+        AnalysisEngine::new()
+            .with_include_dirs(include_dirs)
+            .with_follow_re_exports(follow_re_exports),
+    );
     let engine_clone = engine.clone();
 
     // Store engine for potential cancellation
@@ -82,31 +95,47 @@ pub async fn start_analysis(
                     }
                 }
 
+                if !analysis_result.packages.is_empty() {
+                    if let Err(e) = repository.batch_insert_packages(&analysis_result.packages) {
+                        tracing::error!("Failed to insert packages: {}", e);
+                    }
+                }
+
                 if !analysis_result.relationships.is_empty() {
                     if let Err(e) = repository.batch_insert_relationships(&analysis_result.relationships) {
                         tracing::error!("Failed to insert relationships: {}", e);
                     }
                 }
 
+                if !analysis_result.unresolved_imports.is_empty() {
+                    if let Err(e) = repository.batch_insert_unresolved_imports(&analysis_result.unresolved_imports) {
+                        tracing::error!("Failed to insert unresolved imports: {}", e);
+                    }
+                }
+
+                if !analysis_result.broken_links.is_empty() {
+                    if let Err(e) = repository.batch_insert_broken_links(&analysis_result.broken_links) {
+                        tracing::error!("Failed to insert broken links: {}", e);
+                    }
+                }
+
                 // Update project last analyzed time
                 if let Err(e) = repository.update_project_analyzed(&pid) {
                     tracing::error!("Failed to update project analyzed time: {}", e);
                 }
 
                 tracing::info!(
-                    "Analysis complete: {} files, {} symbols, {} relationships",
+                    "Analysis complete: {} files, {} symbols, {} relationships, {} unresolved imports, {} broken links",
                     analysis_result.files.len(),
                     analysis_result.symbols.len(),
-                    analysis_result.relationships.len()
+                    analysis_result.relationships.len(),
+                    analysis_result.unresolved_imports.len(),
+                    analysis_result.broken_links.len()
                 );
 
                 // Send "complete" status AFTER all DB writes are done
                 // This ensures frontend won't fetch stale data
-                let _ = channel.send(AnalysisProgress::completed(
-                    analysis_result.files.len(),
-                    analysis_result.symbols.len(),
-                    analysis_result.relationships.len(),
-                ));
+                let _ = channel.send(AnalysisProgress::completed(&analysis_result));
             }
             Err(e) => {
                 tracing::error!("Analysis failed: {}", e);
@@ -133,6 +162,28 @@ pub async fn cancel_analysis(project_id: String, state: State<'_, AppState>) ->
     Ok(())
 }
 
+/// Get the imports of a project's last analysis that did not resolve to a
+/// project file, with the reason for each
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn get_unresolved_imports(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> NexusResult<Vec<UnresolvedImportRecord>> {
+    state.repository.get_unresolved_imports(&project_id)
+}
+
+/// Get the links in a project's documents, found by its last analysis, that
+/// point at files that do not exist
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn get_broken_links(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> NexusResult<Vec<BrokenLinkRecord>> {
+    state.repository.get_broken_links(&project_id)
+}
+
 #[cfg(test)]
 mod tests {
     // Integration tests would be needed for these commands
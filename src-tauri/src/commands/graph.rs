@@ -3,7 +3,7 @@ use tauri::State;
 
 use crate::error::NexusResult;
 use crate::graph::{FilterState, GraphData};
-use crate::storage::{FileRecord, RelationshipRecord, SymbolRecord};
+use crate::storage::{FileRecord, PackageRecord, RelationshipRecord, SymbolRecord};
 use crate::AppState;
 
 /// Get graph data for a project
@@ -44,11 +44,18 @@ pub async fn get_graph_data(
         all_symbols.retain(|s| filters.symbol_kinds.contains(&s.kind));
     }
 
+    // Get third-party packages
+    let packages = if filters.show_packages {
+        state.repository.get_packages_for_project(&project_id)?
+    } else {
+        Vec::new()
+    };
+
     // Get relationships
     let relationships = state.repository.get_relationships_for_project(&project_id)?;
 
     // Build graph
-    let graph = GraphData::from_analysis(&files, &all_symbols, &relationships, filters.view_mode);
+    let graph = GraphData::from_analysis(&files, &all_symbols, &packages, &relationships, filters.view_mode);
 
     tracing::debug!(
         "Graph data: {} nodes, {} edges",
@@ -94,7 +101,10 @@ pub async fn get_node_details(
             file: Some(file),
             symbol: None,
             containing_file: None,
+            parent_symbol: None,
+            child_symbols: None,
             symbols_in_file: Some(symbols),
+            package: None,
             incoming_relationships: incoming,
             outgoing_relationships: outgoing,
         });
@@ -102,8 +112,13 @@ pub async fn get_node_details(
 
     // Try to find as symbol
     if let Some(symbol) = state.repository.get_symbol(&node_id)? {
-        // Get the containing file
+        // Get the containing file, the enclosing symbol and any members
         let containing_file = state.repository.get_file(&symbol.file_id)?;
+        let parent_symbol = match &symbol.parent_id {
+            Some(parent_id) => state.repository.get_symbol(parent_id)?,
+            None => None,
+        };
+        let child_symbols = state.repository.get_child_symbols(&node_id)?;
 
         return Ok(NodeDetails {
             id: node_id,
@@ -111,7 +126,27 @@ pub async fn get_node_details(
             file: None,
             symbol: Some(symbol),
             containing_file,
+            parent_symbol,
+            child_symbols: Some(child_symbols),
+            symbols_in_file: None,
+            package: None,
+            incoming_relationships: incoming,
+            outgoing_relationships: outgoing,
+        });
+    }
+
+    // Try to find as package; incoming relationships are the files using it
+    if let Some(package) = state.repository.get_package(&node_id)? {
+        return Ok(NodeDetails {
+            id: node_id,
+            node_type: "package".to_string(),
+            file: None,
+            symbol: None,
+            containing_file: None,
+            parent_symbol: None,
+            child_symbols: None,
             symbols_in_file: None,
+            package: Some(package),
             incoming_relationships: incoming,
             outgoing_relationships: outgoing,
         });
@@ -124,7 +159,10 @@ pub async fn get_node_details(
         file: None,
         symbol: None,
         containing_file: None,
+        parent_symbol: None,
+        child_symbols: None,
         symbols_in_file: None,
+        package: None,
         incoming_relationships: incoming,
         outgoing_relationships: outgoing,
     })
@@ -148,7 +186,7 @@ pub async fn set_file_visibility(
 pub struct NodeDetails {
     /// The node ID
     pub id: String,
-    /// Type of node: "file", "symbol", or "unknown"
+    /// Type of node: "file", "symbol", "package", or "unknown"
     pub node_type: String,
     /// File details (if node is a file)
     pub file: Option<FileRecord>,
@@ -156,8 +194,14 @@ pub struct NodeDetails {
     pub symbol: Option<SymbolRecord>,
     /// The file containing this symbol (if node is a symbol)
     pub containing_file: Option<FileRecord>,
+    /// The symbol this symbol is a member of (if node is a symbol)
+    pub parent_symbol: Option<SymbolRecord>,
+    /// Members of this symbol (if node is a symbol)
+    pub child_symbols: Option<Vec<SymbolRecord>>,
     /// Symbols defined in this file (if node is a file)
     pub symbols_in_file: Option<Vec<SymbolRecord>>,
+    /// Package details (if node is a third-party package)
+    pub package: Option<PackageRecord>,
     /// Relationships where this node is the target
     pub incoming_relationships: Vec<RelationshipRecord>,
     /// Relationships where this node is the source
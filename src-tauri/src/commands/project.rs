@@ -62,7 +62,7 @@ fn determine_file_type(path: &Path) -> FileType {
     match ext.as_str() {
         // Code files
         "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" | "py" | "pyw" | "go" | "rs" | "c" | "h"
-        | "cpp" | "hpp" | "swift" | "kt" | "java" | "rb" | "php" | "cs" | "vb" | "lua" | "pl"
+        | "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" | "swift" | "kt" | "kts" | "java" | "rb" | "php" | "cs" | "vb" | "lua" | "pl"
         | "r" | "scala" | "clj" | "ex" | "exs" | "hs" | "ml" | "fs" | "dart" | "vue" | "svelte" => {
             FileType::Code
         }
@@ -171,6 +171,50 @@ pub async fn list_project_files(
     Ok(files)
 }
 
+/// Get the extra C/C++ include directories configured for a project
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn get_include_dirs(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> NexusResult<Vec<String>> {
+    state.repository.get_include_dirs(&project_id)
+}
+
+/// Set the extra C/C++ include directories for a project (absolute or
+/// relative to the project root). Takes effect on the next analysis.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn set_include_dirs(
+    project_id: String,
+    dirs: Vec<String>,
+    state: State<'_, AppState>,
+) -> NexusResult<()> {
+    state.repository.set_include_dirs(&project_id, &dirs)
+}
+
+/// Get whether imports through barrel files (`export * from`) are attributed
+/// to the files that define the imported names
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn get_follow_re_exports(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> NexusResult<bool> {
+    state.repository.get_follow_re_exports(&project_id)
+}
+
+/// Set whether to follow re-export chains. Takes effect on the next analysis.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn set_follow_re_exports(
+    project_id: String,
+    follow: bool,
+    state: State<'_, AppState>,
+) -> NexusResult<()> {
+    state.repository.set_follow_re_exports(&project_id, follow)
+}
+
 /// Discover ALL files in a directory, respecting .gitignore
 fn discover_all_files(path: &Path) -> NexusResult<Vec<ProjectFile>> {
     let walker = WalkBuilder::new(path)
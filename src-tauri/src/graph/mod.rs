@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::storage::{FileRecord, RelationshipRecord, SymbolRecord};
+use crate::storage::{FileRecord, PackageRecord, RelationshipRecord, SymbolRecord};
 
 /// Graph data returned to frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,7 +9,7 @@ pub struct GraphData {
     pub edges: Vec<GraphEdge>,
 }
 
-/// Node in the graph (file or symbol) - aligned with frontend types
+/// Node in the graph (file, symbol or package) - aligned with frontend types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GraphNode {
@@ -17,11 +17,13 @@ pub struct GraphNode {
     pub id: String,
     /// Display name
     pub name: String,
-    /// Type of node: "file" or "symbol"
+    /// Type of node: "file", "symbol" or "package"
     #[serde(rename = "type")]
     pub node_type: String,
     /// Programming language (for file nodes)
     pub language: Option<String>,
+    /// Package ecosystem: "npm", "crates", "pypi", "go", "rubygems" or "nuget" (for package nodes)
+    pub ecosystem: Option<String>,
     /// Symbol kind (for symbol nodes)
     pub symbol_kind: Option<String>,
     /// File path (for file nodes)
@@ -32,7 +34,7 @@ pub struct GraphNode {
     pub line_count: Option<i32>,
     /// Whether this symbol is exported/public
     pub is_exported: bool,
-    /// Number of connections (for sizing)
+    /// Number of connections (for sizing); for packages, the number of files using it
     pub connection_count: i32,
     /// Visual state - frontend manages position, we provide initial state
     #[serde(default = "default_node_state")]
@@ -59,6 +61,7 @@ impl GraphData {
     pub fn from_analysis(
         files: &[FileRecord],
         symbols: &[SymbolRecord],
+        packages: &[PackageRecord],
         relationships: &[RelationshipRecord],
         view_mode: ViewMode,
     ) -> Self {
@@ -85,6 +88,7 @@ impl GraphData {
                         name: file.name.clone(),
                         node_type: "file".to_string(),
                         language: Some(file.language.clone()),
+                        ecosystem: None,
                         symbol_kind: None,
                         path: Some(file.path.clone()),
                         line: None,
@@ -95,10 +99,36 @@ impl GraphData {
                     });
                 }
 
-                // Only include file-to-file relationships
+                // Third-party packages the files import, grouped by package root
+                for package in packages {
+                    nodes.push(GraphNode {
+                        id: package.id.clone(),
+                        name: package.name.clone(),
+                        node_type: "package".to_string(),
+                        language: None,
+                        ecosystem: Some(package.ecosystem.clone()),
+                        symbol_kind: None,
+                        path: None,
+                        line: None,
+                        line_count: None,
+                        is_exported: true,
+                        connection_count: *connection_counts.get(&package.id).unwrap_or(&0),
+                        state: "default".to_string(),
+                    });
+                }
+
+                // Only include file-to-file, file-to-package and package-to-package
+                // (C# project reference) relationships
                 let file_ids: std::collections::HashSet<_> = files.iter().map(|f| &f.id).collect();
+                let package_ids: std::collections::HashSet<_> = packages.iter().map(|p| &p.id).collect();
                 for rel in relationships {
-                    if file_ids.contains(&rel.source_id) && file_ids.contains(&rel.target_id) {
+                    let visible = if package_ids.contains(&rel.source_id) {
+                        package_ids.contains(&rel.target_id)
+                    } else {
+                        file_ids.contains(&rel.source_id)
+                            && (file_ids.contains(&rel.target_id) || package_ids.contains(&rel.target_id))
+                    };
+                    if visible {
                         edges.push(GraphEdge {
                             id: rel.id.clone(),
                             source: rel.source_id.clone(),
@@ -116,6 +146,7 @@ impl GraphData {
                         name: symbol.name.clone(),
                         node_type: "symbol".to_string(),
                         language: None,
+                        ecosystem: None,
                         symbol_kind: Some(symbol.kind.clone()),
                         path: None,
                         line: Some(symbol.line),
@@ -152,7 +183,7 @@ pub enum ViewMode {
 }
 
 /// Filter state for graph queries - aligned with frontend types
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FilterState {
     /// View mode: file or symbol level
@@ -176,6 +207,28 @@ pub struct FilterState {
     /// Search query for filtering nodes by name
     #[serde(default)]
     pub search_query: Option<String>,
+    /// Show third-party package nodes (file view)
+    #[serde(default = "default_show_packages")]
+    pub show_packages: bool,
+}
+
+fn default_show_packages() -> bool {
+    true
+}
+
+impl Default for FilterState {
+    fn default() -> Self {
+        Self {
+            view_mode: ViewMode::default(),
+            languages: Vec::new(),
+            node_types: Vec::new(),
+            relationship_types: Vec::new(),
+            symbol_kinds: Vec::new(),
+            clusters: Vec::new(),
+            search_query: None,
+            show_packages: default_show_packages(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -219,9 +272,82 @@ mod tests {
             metadata: None,
         }];
 
-        let graph = GraphData::from_analysis(&files, &[], &relationships, ViewMode::File);
+        let graph = GraphData::from_analysis(&files, &[], &[], &relationships, ViewMode::File);
+
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges.len(), 1);
+    }
+
+    #[test]
+    fn test_graph_data_packages() {
+        let files = vec![FileRecord {
+            id: "file-1".to_string(),
+            project_id: "proj".to_string(),
+            name: "app.ts".to_string(),
+            path: "src/app.ts".to_string(),
+            absolute_path: "/src/app.ts".to_string(),
+            language: "typescript".to_string(),
+            line_count: 100,
+            is_hidden: false,
+            content_hash: None,
+            last_modified: None,
+        }];
+        let packages = vec![PackageRecord {
+            id: "pkg-1".to_string(),
+            project_id: "proj".to_string(),
+            name: "react".to_string(),
+            ecosystem: "npm".to_string(),
+        }];
+        let relationships = vec![RelationshipRecord {
+            id: "rel-1".to_string(),
+            source_id: "file-1".to_string(),
+            target_id: "pkg-1".to_string(),
+            kind: "imports".to_string(),
+            metadata: None,
+        }];
+
+        let graph = GraphData::from_analysis(&files, &[], &packages, &relationships, ViewMode::File);
+
+        let package = graph.nodes.iter().find(|n| n.node_type == "package").unwrap();
+        assert_eq!(package.ecosystem.as_deref(), Some("npm"));
+        assert_eq!(package.connection_count, 1);
+        assert_eq!(graph.edges.len(), 1);
+
+        let graph = GraphData::from_analysis(&files, &[], &[], &relationships, ViewMode::File);
+        assert_eq!(graph.nodes.len(), 1);
+        assert!(graph.edges.is_empty());
+    }
+
+    #[test]
+    fn test_graph_data_project_references() {
+        let package = |id: &str, name: &str| PackageRecord {
+            id: id.to_string(),
+            project_id: "proj".to_string(),
+            name: name.to_string(),
+            ecosystem: "nuget".to_string(),
+        };
+        let packages = vec![package("pkg-1", "App"), package("pkg-2", "Core")];
+        let relationships = vec![RelationshipRecord {
+            id: "rel-1".to_string(),
+            source_id: "pkg-1".to_string(),
+            target_id: "pkg-2".to_string(),
+            kind: "imports".to_string(),
+            metadata: None,
+        }];
 
+        let graph = GraphData::from_analysis(&[], &[], &packages, &relationships, ViewMode::File);
         assert_eq!(graph.nodes.len(), 2);
         assert_eq!(graph.edges.len(), 1);
+
+        // Hidden with the packages
+        let graph = GraphData::from_analysis(&[], &[], &[], &relationships, ViewMode::File);
+        assert!(graph.edges.is_empty());
+    }
+
+    #[test]
+    fn test_filter_state_defaults() {
+        let filters: FilterState = serde_json::from_str(r#"{"viewMode":"file"}"#).unwrap();
+        assert!(filters.show_packages);
+        assert!(FilterState::default().show_packages);
     }
 }
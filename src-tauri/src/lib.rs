@@ -72,8 +72,14 @@ pub fn run() {
             commands::get_project,
             commands::delete_project,
             commands::list_project_files,
+            commands::get_include_dirs,
+            commands::set_include_dirs,
+            commands::get_follow_re_exports,
+            commands::set_follow_re_exports,
             commands::start_analysis,
             commands::cancel_analysis,
+            commands::get_unresolved_imports,
+            commands::get_broken_links,
             commands::get_graph_data,
             commands::get_node_details,
             commands::set_file_visibility,
@@ -2,7 +2,10 @@ mod schema;
 pub mod repository;
 
 pub use schema::run_migrations;
-pub use repository::{Repository, Project, FileRecord, SymbolRecord, RelationshipRecord};
+pub use repository::{
+    Repository, Project, FileRecord, SymbolRecord, PackageRecord, RelationshipRecord, UnresolvedImportRecord,
+    BrokenLinkRecord,
+};
 
 use r2d2::{Pool, PooledConnection};
 use r2d2_sqlite::SqliteConnectionManager;
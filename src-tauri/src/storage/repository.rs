@@ -58,6 +58,47 @@ pub struct SymbolRecord {
     pub parent_id: Option<String>,
 }
 
+/// A third-party package (npm, crates.io, PyPI, Go module) imported by project files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageRecord {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+    pub ecosystem: String,
+}
+
+/// An import that did not resolve to a project file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnresolvedImportRecord {
+    pub id: String,
+    pub project_id: String,
+    pub file_id: String,
+    pub file_path: String,
+    pub language: String,
+    /// The import as written: module path, package name or header
+    pub source: String,
+    pub line: i32,
+    /// "external", "builtin", "missing-file" or "unknown"
+    pub reason: String,
+}
+
+/// A link in a document to a file that does not exist
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokenLinkRecord {
+    pub id: String,
+    pub project_id: String,
+    pub file_id: String,
+    pub file_path: String,
+    /// The linked path as written, without any `#fragment`
+    pub target: String,
+    pub line: i32,
+    /// "link" or "image"
+    pub kind: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RelationshipRecord {
@@ -178,6 +219,10 @@ impl Repository {
     pub fn delete_project(&self, id: &str) -> NexusResult<()> {
         let conn = self.pool.get()?;
         conn.execute("DELETE FROM projects WHERE id = ?1", [id])?;
+        conn.execute(
+            "DELETE FROM settings WHERE key IN (?1, ?2)",
+            params![include_dirs_key(id), follow_re_exports_key(id)],
+        )?;
         Ok(())
     }
 
@@ -388,6 +433,38 @@ impl Repository {
         Ok(symbol)
     }
 
+    /// Members of a symbol (methods, fields, nested types), in any file
+    #[tracing::instrument(skip(self))]
+    pub fn get_child_symbols(&self, parent_id: &str) -> NexusResult<Vec<SymbolRecord>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, file_id, name, kind, line, column, end_line, end_column, signature, documentation, is_exported, parent_id
+             FROM symbols WHERE parent_id = ?1 ORDER BY file_id, line",
+        )?;
+
+        let symbols = stmt
+            .query_map([parent_id], |row| {
+                Ok(SymbolRecord {
+                    id: row.get(0)?,
+                    file_id: row.get(1)?,
+                    name: row.get(2)?,
+                    kind: row.get(3)?,
+                    line: row.get(4)?,
+                    column: row.get(5)?,
+                    end_line: row.get(6)?,
+                    end_column: row.get(7)?,
+                    signature: row.get(8)?,
+                    documentation: row.get(9)?,
+                    is_exported: row.get::<_, i32>(10)? != 0,
+                    parent_id: row.get(11)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(symbols)
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn delete_symbols_for_file(&self, file_id: &str) -> NexusResult<()> {
         let conn = self.pool.get()?;
@@ -395,6 +472,192 @@ impl Repository {
         Ok(())
     }
 
+    // ========================================================================
+    // Package Operations
+    // ========================================================================
+
+    #[tracing::instrument(skip(self, packages))]
+    pub fn batch_insert_packages(&self, packages: &[PackageRecord]) -> NexusResult<()> {
+        let mut conn = self.pool.get()?;
+        let tx = conn.transaction()?;
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR IGNORE INTO packages (id, project_id, name, ecosystem)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+
+            for package in packages {
+                stmt.execute(params![package.id, package.project_id, package.name, package.ecosystem])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn get_packages_for_project(&self, project_id: &str) -> NexusResult<Vec<PackageRecord>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, project_id, name, ecosystem
+             FROM packages WHERE project_id = ?1 ORDER BY ecosystem, name",
+        )?;
+
+        let packages = stmt
+            .query_map([project_id], |row| {
+                Ok(PackageRecord {
+                    id: row.get(0)?,
+                    project_id: row.get(1)?,
+                    name: row.get(2)?,
+                    ecosystem: row.get(3)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(packages)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn get_package(&self, id: &str) -> NexusResult<Option<PackageRecord>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, project_id, name, ecosystem FROM packages WHERE id = ?1",
+        )?;
+
+        let package = stmt
+            .query_row([id], |row| {
+                Ok(PackageRecord {
+                    id: row.get(0)?,
+                    project_id: row.get(1)?,
+                    name: row.get(2)?,
+                    ecosystem: row.get(3)?,
+                })
+            })
+            .ok();
+
+        Ok(package)
+    }
+
+    // ========================================================================
+    // Unresolved Import Operations
+    // ========================================================================
+
+    #[tracing::instrument(skip(self, imports))]
+    pub fn batch_insert_unresolved_imports(&self, imports: &[UnresolvedImportRecord]) -> NexusResult<()> {
+        let mut conn = self.pool.get()?;
+        let tx = conn.transaction()?;
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO unresolved_imports (id, project_id, file_id, file_path, language, source, line, reason)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+
+            for import in imports {
+                stmt.execute(params![
+                    import.id,
+                    import.project_id,
+                    import.file_id,
+                    import.file_path,
+                    import.language,
+                    import.source,
+                    import.line,
+                    import.reason,
+                ])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn get_unresolved_imports(&self, project_id: &str) -> NexusResult<Vec<UnresolvedImportRecord>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, project_id, file_id, file_path, language, source, line, reason
+             FROM unresolved_imports WHERE project_id = ?1 ORDER BY file_path, line",
+        )?;
+
+        let imports = stmt
+            .query_map([project_id], |row| {
+                Ok(UnresolvedImportRecord {
+                    id: row.get(0)?,
+                    project_id: row.get(1)?,
+                    file_id: row.get(2)?,
+                    file_path: row.get(3)?,
+                    language: row.get(4)?,
+                    source: row.get(5)?,
+                    line: row.get(6)?,
+                    reason: row.get(7)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(imports)
+    }
+
+    // ========================================================================
+    // Broken Link Operations
+    // ========================================================================
+
+    #[tracing::instrument(skip(self, links))]
+    pub fn batch_insert_broken_links(&self, links: &[BrokenLinkRecord]) -> NexusResult<()> {
+        let mut conn = self.pool.get()?;
+        let tx = conn.transaction()?;
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO broken_links (id, project_id, file_id, file_path, target, line, kind)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+
+            for link in links {
+                stmt.execute(params![
+                    link.id,
+                    link.project_id,
+                    link.file_id,
+                    link.file_path,
+                    link.target,
+                    link.line,
+                    link.kind,
+                ])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn get_broken_links(&self, project_id: &str) -> NexusResult<Vec<BrokenLinkRecord>> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, project_id, file_id, file_path, target, line, kind
+             FROM broken_links WHERE project_id = ?1 ORDER BY file_path, line",
+        )?;
+
+        let links = stmt
+            .query_map([project_id], |row| {
+                Ok(BrokenLinkRecord {
+                    id: row.get(0)?,
+                    project_id: row.get(1)?,
+                    file_id: row.get(2)?,
+                    file_path: row.get(3)?,
+                    target: row.get(4)?,
+                    line: row.get(5)?,
+                    kind: row.get(6)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(links)
+    }
+
     // ========================================================================
     // Relationship Operations
     // ========================================================================
@@ -428,13 +691,13 @@ impl Repository {
     #[tracing::instrument(skip(self))]
     pub fn get_relationships_for_project(&self, project_id: &str) -> NexusResult<Vec<RelationshipRecord>> {
         let conn = self.pool.get()?;
-        let mut stmt = conn.prepare(
-            "SELECT r.id, r.source_id, r.target_id, r.kind, r.metadata
-             FROM relationships r
-             INNER JOIN files f ON (r.source_id = f.id OR r.target_id = f.id)
-             WHERE f.project_id = ?1
-             GROUP BY r.id",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, source_id, target_id, kind, metadata
+             FROM relationships
+             WHERE source_id IN ({0}) OR target_id IN ({0})
+             ORDER BY id",
+            PROJECT_NODE_IDS
+        ))?;
 
         let relationships = stmt
             .query_map([project_id], |row| {
@@ -481,10 +744,12 @@ impl Repository {
     pub fn clear_project_data(&self, project_id: &str) -> NexusResult<()> {
         let conn = self.pool.get()?;
 
-        // Delete relationships involving project files
+        // Delete relationships involving project files, symbols or packages
         conn.execute(
-            "DELETE FROM relationships WHERE source_id IN (SELECT id FROM files WHERE project_id = ?1)
-             OR target_id IN (SELECT id FROM files WHERE project_id = ?1)",
+            &format!(
+                "DELETE FROM relationships WHERE source_id IN ({0}) OR target_id IN ({0})",
+                PROJECT_NODE_IDS
+            ),
             [project_id],
         )?;
 
@@ -494,8 +759,11 @@ impl Repository {
             [project_id],
         )?;
 
-        // Delete files
+        // Delete files, the packages they imported, their unresolved imports and broken links
+        conn.execute("DELETE FROM unresolved_imports WHERE project_id = ?1", [project_id])?;
+        conn.execute("DELETE FROM broken_links WHERE project_id = ?1", [project_id])?;
         conn.execute("DELETE FROM files WHERE project_id = ?1", [project_id])?;
+        conn.execute("DELETE FROM packages WHERE project_id = ?1", [project_id])?;
 
         Ok(())
     }
@@ -523,6 +791,42 @@ impl Repository {
         )?;
         Ok(())
     }
+
+    /// Extra C/C++ include directories configured for a project
+    pub fn get_include_dirs(&self, project_id: &str) -> NexusResult<Vec<String>> {
+        let value = self.get_setting(&include_dirs_key(project_id))?;
+        Ok(value
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default())
+    }
+
+    pub fn set_include_dirs(&self, project_id: &str, dirs: &[String]) -> NexusResult<()> {
+        let value = serde_json::to_string(dirs)?;
+        self.set_setting(&include_dirs_key(project_id), &value)
+    }
+
+    /// Whether imports through barrel files are attributed to the defining files (default on)
+    pub fn get_follow_re_exports(&self, project_id: &str) -> NexusResult<bool> {
+        let value = self.get_setting(&follow_re_exports_key(project_id))?;
+        Ok(value.as_deref() != Some("false"))
+    }
+
+    pub fn set_follow_re_exports(&self, project_id: &str, follow: bool) -> NexusResult<()> {
+        self.set_setting(&follow_re_exports_key(project_id), &follow.to_string())
+    }
+}
+
+/// IDs of the graph nodes of project `?1`: its files, their symbols and its packages
+const PROJECT_NODE_IDS: &str = "SELECT id FROM files WHERE project_id = ?1
+     UNION SELECT s.id FROM symbols s INNER JOIN files f ON s.file_id = f.id WHERE f.project_id = ?1
+     UNION SELECT id FROM packages WHERE project_id = ?1";
+
+fn include_dirs_key(project_id: &str) -> String {
+    format!("include_dirs:{}", project_id)
+}
+
+fn follow_re_exports_key(project_id: &str) -> String {
+    format!("follow_re_exports:{}", project_id)
 }
 
 /// Get current timestamp in ISO 8601 format (UTC)
@@ -639,6 +943,43 @@ mod tests {
         assert_eq!(value, Some("cursor".to_string()));
     }
 
+    #[test]
+    fn test_include_dirs() {
+        let (repo, _dir) = test_repo();
+
+        assert!(repo.get_include_dirs("proj-1").unwrap().is_empty());
+
+        let dirs = vec!["include".to_string(), "/opt/sdk/include".to_string()];
+        repo.set_include_dirs("proj-1", &dirs).unwrap();
+        assert_eq!(repo.get_include_dirs("proj-1").unwrap(), dirs);
+        assert!(repo.get_include_dirs("proj-2").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_delete_project_removes_settings() {
+        let (repo, _dir) = test_repo();
+        let project = repo.create_project("Test", "/path").unwrap();
+
+        repo.set_include_dirs(&project.id, &["include".to_string()]).unwrap();
+        repo.set_follow_re_exports(&project.id, false).unwrap();
+        repo.delete_project(&project.id).unwrap();
+
+        assert!(repo.get_project(&project.id).unwrap().is_none());
+        assert!(repo.get_setting(&include_dirs_key(&project.id)).unwrap().is_none());
+        assert!(repo.get_setting(&follow_re_exports_key(&project.id)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_follow_re_exports() {
+        let (repo, _dir) = test_repo();
+
+        assert!(repo.get_follow_re_exports("proj-1").unwrap());
+
+        repo.set_follow_re_exports("proj-1", false).unwrap();
+        assert!(!repo.get_follow_re_exports("proj-1").unwrap());
+        assert!(repo.get_follow_re_exports("proj-2").unwrap());
+    }
+
     #[test]
     fn test_chrono_now_format() {
         let timestamp = super::chrono_now();
@@ -725,9 +1066,28 @@ mod tests {
                 is_exported: true,
                 parent_id: None,
             },
+            SymbolRecord {
+                id: "symbol-124".to_string(),
+                file_id: "file-456".to_string(),
+                name: "run".to_string(),
+                kind: "method".to_string(),
+                line: 12,
+                column: 5,
+                end_line: Some(14),
+                end_column: Some(5),
+                signature: None,
+                documentation: None,
+                is_exported: false,
+                parent_id: Some("symbol-123".to_string()),
+            },
         ];
         repo.batch_insert_symbols(&symbols).unwrap();
 
+        let children = repo.get_child_symbols("symbol-123").unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].id, "symbol-124");
+        assert!(repo.get_child_symbols("symbol-124").unwrap().is_empty());
+
         // Test get_symbol
         let fetched = repo.get_symbol("symbol-123").unwrap();
         assert!(fetched.is_some());
@@ -742,6 +1102,159 @@ mod tests {
         assert!(not_found.is_none());
     }
 
+    #[test]
+    fn test_symbol_relationships() {
+        let (repo, _dir) = test_repo();
+        let project = repo.create_project("Test", "/path").unwrap();
+
+        let file = FileRecord {
+            id: "file-789".to_string(),
+            project_id: project.id.clone(),
+            name: "test.ts".to_string(),
+            path: "src/test.ts".to_string(),
+            absolute_path: "/path/src/test.ts".to_string(),
+            language: "typescript".to_string(),
+            line_count: 20,
+            is_hidden: false,
+            content_hash: None,
+            last_modified: None,
+        };
+        repo.upsert_file(&file).unwrap();
+
+        let symbol = |id: &str, name: &str| SymbolRecord {
+            id: id.to_string(),
+            file_id: "file-789".to_string(),
+            name: name.to_string(),
+            kind: "function".to_string(),
+            line: 1,
+            column: 1,
+            end_line: None,
+            end_column: None,
+            signature: None,
+            documentation: None,
+            is_exported: false,
+            parent_id: None,
+        };
+        repo.batch_insert_symbols(&[symbol("symbol-1", "run"), symbol("symbol-2", "load")]).unwrap();
+
+        let call = RelationshipRecord {
+            id: "rel-1".to_string(),
+            source_id: "symbol-1".to_string(),
+            target_id: "symbol-2".to_string(),
+            kind: "calls".to_string(),
+            metadata: None,
+        };
+        repo.batch_insert_relationships(&[call]).unwrap();
+
+        let relationships = repo.get_relationships_for_project(&project.id).unwrap();
+        assert_eq!(relationships.len(), 1);
+        assert_eq!(relationships[0].kind, "calls");
+        assert!(repo.get_relationships_for_project("other-project").unwrap().is_empty());
+
+        repo.clear_project_data(&project.id).unwrap();
+        assert!(repo.get_relationships_for_node("symbol-1").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_packages() {
+        let (repo, _dir) = test_repo();
+        let project = repo.create_project("Test", "/path").unwrap();
+
+        let package = |id: &str, name: &str, ecosystem: &str| PackageRecord {
+            id: id.to_string(),
+            project_id: project.id.clone(),
+            name: name.to_string(),
+            ecosystem: ecosystem.to_string(),
+        };
+        repo.batch_insert_packages(&[package("pkg-1", "react", "npm"), package("pkg-2", "serde", "crates")])
+            .unwrap();
+
+        let packages = repo.get_packages_for_project(&project.id).unwrap();
+        let names: Vec<_> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["serde", "react"]);
+        assert_eq!(repo.get_package("pkg-1").unwrap().unwrap().ecosystem, "npm");
+
+        repo.clear_project_data(&project.id).unwrap();
+        assert!(repo.get_packages_for_project(&project.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unresolved_imports() {
+        let (repo, _dir) = test_repo();
+        let project = repo.create_project("Test", "/path").unwrap();
+        let file = FileRecord {
+            id: "file-1".to_string(),
+            project_id: project.id.clone(),
+            name: "app.ts".to_string(),
+            path: "src/app.ts".to_string(),
+            absolute_path: "/path/src/app.ts".to_string(),
+            language: "typescript".to_string(),
+            line_count: 10,
+            is_hidden: false,
+            content_hash: None,
+            last_modified: None,
+        };
+        repo.upsert_file(&file).unwrap();
+
+        let import = |line: i32, source: &str, reason: &str| UnresolvedImportRecord {
+            id: Uuid::new_v4().to_string(),
+            project_id: project.id.clone(),
+            file_id: file.id.clone(),
+            file_path: file.path.clone(),
+            language: file.language.clone(),
+            source: source.to_string(),
+            line,
+            reason: reason.to_string(),
+        };
+        repo.batch_insert_unresolved_imports(&[import(4, "./gone", "missing-file"), import(1, "react", "external")])
+            .unwrap();
+
+        let imports = repo.get_unresolved_imports(&project.id).unwrap();
+        let found: Vec<_> = imports.iter().map(|i| (i.line, i.reason.as_str())).collect();
+        assert_eq!(found, vec![(1, "external"), (4, "missing-file")]);
+
+        repo.clear_project_data(&project.id).unwrap();
+        assert!(repo.get_unresolved_imports(&project.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_broken_links() {
+        let (repo, _dir) = test_repo();
+        let project = repo.create_project("Test", "/path").unwrap();
+        let file = FileRecord {
+            id: "file-1".to_string(),
+            project_id: project.id.clone(),
+            name: "README.md".to_string(),
+            path: "README.md".to_string(),
+            absolute_path: "/path/README.md".to_string(),
+            language: "markdown".to_string(),
+            line_count: 10,
+            is_hidden: false,
+            content_hash: None,
+            last_modified: None,
+        };
+        repo.upsert_file(&file).unwrap();
+
+        let link = |line: i32, target: &str, kind: &str| BrokenLinkRecord {
+            id: Uuid::new_v4().to_string(),
+            project_id: project.id.clone(),
+            file_id: file.id.clone(),
+            file_path: file.path.clone(),
+            target: target.to_string(),
+            line,
+            kind: kind.to_string(),
+        };
+        repo.batch_insert_broken_links(&[link(7, "docs/gone.md", "link"), link(2, "logo.png", "image")])
+            .unwrap();
+
+        let links = repo.get_broken_links(&project.id).unwrap();
+        let found: Vec<_> = links.iter().map(|l| (l.line, l.target.as_str())).collect();
+        assert_eq!(found, vec![(2, "logo.png"), (7, "docs/gone.md")]);
+
+        repo.clear_project_data(&project.id).unwrap();
+        assert!(repo.get_broken_links(&project.id).unwrap().is_empty());
+    }
+
     #[test]
     fn test_get_relationships_for_node() {
         let (repo, _dir) = test_repo();
@@ -2,7 +2,7 @@ use rusqlite::Connection;
 use crate::error::NexusResult;
 
 /// Database schema version for migrations
-const SCHEMA_VERSION: i32 = 1;
+const SCHEMA_VERSION: i32 = 4;
 
 /// Run all database migrations
 pub fn run_migrations(conn: &Connection) -> NexusResult<()> {
@@ -16,6 +16,21 @@ pub fn run_migrations(conn: &Connection) -> NexusResult<()> {
             migrate_v1(conn)?;
         }
 
+        // Migration 1 -> 2: External packages
+        if current_version < 2 {
+            migrate_v2(conn)?;
+        }
+
+        // Migration 2 -> 3: Unresolved import diagnostics
+        if current_version < 3 {
+            migrate_v3(conn)?;
+        }
+
+        // Migration 3 -> 4: Broken documentation links
+        if current_version < 4 {
+            migrate_v4(conn)?;
+        }
+
         set_schema_version(conn, SCHEMA_VERSION)?;
     }
 
@@ -135,6 +150,85 @@ fn migrate_v1(conn: &Connection) -> NexusResult<()> {
     Ok(())
 }
 
+/// Third-party packages that imports point into
+fn migrate_v2(conn: &Connection) -> NexusResult<()> {
+    tracing::debug!("Applying migration v2: External packages");
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS packages (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            ecosystem TEXT NOT NULL,
+            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+            UNIQUE (project_id, ecosystem, name)
+        )",
+        [],
+    )?;
+
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_packages_project ON packages(project_id)", [])?;
+
+    tracing::debug!("Migration v2 complete");
+    Ok(())
+}
+
+/// Imports that did not resolve to a project file, and why
+fn migrate_v3(conn: &Connection) -> NexusResult<()> {
+    tracing::debug!("Applying migration v3: Unresolved imports");
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS unresolved_imports (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            file_id TEXT NOT NULL,
+            file_path TEXT NOT NULL,
+            language TEXT NOT NULL,
+            source TEXT NOT NULL,
+            line INTEGER NOT NULL,
+            reason TEXT NOT NULL,
+            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+            FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_unresolved_imports_project ON unresolved_imports(project_id)",
+        [],
+    )?;
+
+    tracing::debug!("Migration v3 complete");
+    Ok(())
+}
+
+/// Links in documents to files that do not exist
+fn migrate_v4(conn: &Connection) -> NexusResult<()> {
+    tracing::debug!("Applying migration v4: Broken links");
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS broken_links (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            file_id TEXT NOT NULL,
+            file_path TEXT NOT NULL,
+            target TEXT NOT NULL,
+            line INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+            FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_broken_links_project ON broken_links(project_id)",
+        [],
+    )?;
+
+    tracing::debug!("Migration v4 complete");
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -509,8 +509,8 @@ export function GraphCanvas({
     return {
       content: {
         name: node.name,
-        type: node.type === 'file' ? 'file' : node.symbolKind || 'function',
-        path: '',
+        type: node.type === 'symbol' ? node.symbolKind || 'function' : node.type,
+        path: node.ecosystem ?? '',
         language: node.language,
         incomingCount: incoming,
         outgoingCount: outgoing,
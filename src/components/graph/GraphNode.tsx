@@ -7,7 +7,7 @@ interface GraphNodeProps {
   y: number
   state: 'default' | 'hover' | 'selected' | 'faded' | 'search-match'
   name: string
-  type: 'file' | 'symbol'
+  type: 'file' | 'symbol' | 'package'
   language?: Language
   symbolKind?: SymbolKind
   connectionCount: number
@@ -24,6 +24,11 @@ const typeColors: Record<string, { bg: string; border: string; text: string }> =
     border: 'stroke-violet-500/50',
     text: 'fill-violet-300',
   },
+  package: {
+    bg: 'fill-zinc-500/20',
+    border: 'stroke-zinc-500/50',
+    text: 'fill-zinc-300',
+  },
   function: {
     bg: 'fill-cyan-500/20',
     border: 'stroke-cyan-500/50',
@@ -91,7 +96,7 @@ export const GraphNode = memo(function GraphNode({
   onClick,
   onDoubleClick,
 }: GraphNodeProps) {
-  const nodeType = type === 'file' ? 'file' : symbolKind || 'function'
+  const nodeType = type === 'symbol' ? symbolKind || 'function' : type
   const colors = typeColors[nodeType] || typeColors.file
   const languageAccent = language ? languageAccents[language] || 'stroke-zinc-400' : 'stroke-zinc-400'
 
@@ -197,6 +202,16 @@ export const GraphNode = memo(function GraphNode({
             strokeLinecap="round"
             strokeLinejoin="round"
           />
+        ) : type === 'package' ? (
+          // Package icon (box)
+          <path
+            d="M-7,-4 L0,-8 L7,-4 L7,4 L0,8 L-7,4 Z M-7,-4 L0,0 L7,-4 M0,0 L0,8"
+            fill="none"
+            stroke="currentColor"
+            strokeWidth={1.5}
+            strokeLinecap="round"
+            strokeLinejoin="round"
+          />
         ) : nodeType === 'function' || nodeType === 'method' ? (
           // Function icon (fn)
           <text
@@ -1,9 +1,9 @@
-import { FileCode, FunctionSquare, Box, Type, ArrowDownLeft, ArrowUpRight } from 'lucide-react'
+import { FileCode, FunctionSquare, Box, Package, Type, ArrowDownLeft, ArrowUpRight } from 'lucide-react'
 import type { Language, SymbolKind } from '@/types'
 
 export interface NodePopoverContent {
   name: string
-  type: 'file' | SymbolKind
+  type: 'file' | 'package' | SymbolKind
   path: string
   language?: Language
   incomingCount: number
@@ -17,6 +17,7 @@ interface NodePopoverProps {
 
 const typeIcons: Record<string, React.ComponentType<{ className?: string }>> = {
   file: FileCode,
+  package: Package,
   function: FunctionSquare,
   method: FunctionSquare,
   class: Box,
@@ -32,6 +33,7 @@ const typeIcons: Record<string, React.ComponentType<{ className?: string }>> = {
 
 const typeLabels: Record<string, string> = {
   file: 'File',
+  package: 'Package',
   function: 'Function',
   method: 'Method',
   class: 'Class',
@@ -59,6 +61,12 @@ const languageColors: Partial<Record<Language, string>> = {
   csharp: 'bg-green-600',
   ruby: 'bg-red-600',
   php: 'bg-indigo-500',
+  vue: 'bg-emerald-500',
+  svelte: 'bg-orange-400',
+  shell: 'bg-lime-600',
+  html: 'bg-orange-700',
+  css: 'bg-sky-500',
+  markdown: 'bg-slate-400',
 }
 
 export function NodePopover({ content }: NodePopoverProps) {
@@ -101,9 +109,11 @@ export function NodePopover({ content }: NodePopoverProps) {
           <ArrowUpRight className="w-3 h-3" />
           <span>{content.outgoingCount} out</span>
         </div>
-        <div className="text-zinc-500">
-          {content.type === 'file' ? `${content.lineInfo} lines` : `Line ${content.lineInfo}`}
-        </div>
+        {content.type !== 'package' && (
+          <div className="text-zinc-500">
+            {content.type === 'file' ? `${content.lineInfo} lines` : `Line ${content.lineInfo}`}
+          </div>
+        )}
       </div>
     </div>
   )
@@ -35,6 +35,12 @@ const LANGUAGES: { value: Language; label: string; color: string }[] = [
   { value: 'csharp', label: 'C#', color: 'bg-violet-500' },
   { value: 'ruby', label: 'Ruby', color: 'bg-red-600' },
   { value: 'php', label: 'PHP', color: 'bg-indigo-500' },
+  { value: 'vue', label: 'Vue', color: 'bg-emerald-500' },
+  { value: 'svelte', label: 'Svelte', color: 'bg-orange-400' },
+  { value: 'shell', label: 'Shell', color: 'bg-lime-600' },
+  { value: 'html', label: 'HTML', color: 'bg-orange-700' },
+  { value: 'css', label: 'CSS', color: 'bg-sky-500' },
+  { value: 'markdown', label: 'Markdown', color: 'bg-slate-400' },
 ]
 
 const SYMBOL_KINDS: { value: SymbolKind; label: string }[] = [
@@ -170,6 +176,8 @@ export function FilterPanel({ isOpen, onClose }: FilterPanelProps) {
     languages: true,
     symbolKinds: false,
     relationships: false,
+    dependencies: false,
+    documentation: false,
   })
 
   if (!isOpen) return null
@@ -211,6 +219,8 @@ export function FilterPanel({ isOpen, onClose }: FilterPanelProps) {
       symbolKinds: [],
       clusters: [],
       searchQuery: null,
+      showPackages: true,
+      undocumentedOnly: false,
     })
     setViewMode('file')
   }
@@ -220,7 +230,9 @@ export function FilterPanel({ isOpen, onClose }: FilterPanelProps) {
     filterState.symbolKinds.length +
     (filterState.relationshipTypes.length < RELATIONSHIP_KINDS.length
       ? RELATIONSHIP_KINDS.length - filterState.relationshipTypes.length
-      : 0)
+      : 0) +
+    (filterState.showPackages ? 0 : 1) +
+    (filterState.undocumentedOnly ? 1 : 0)
 
   return (
     <div className="absolute top-full right-0 mt-1 w-80 bg-white dark:bg-zinc-900 border border-zinc-200 dark:border-zinc-700 rounded-lg shadow-lg overflow-hidden z-50">
@@ -324,6 +336,34 @@ export function FilterPanel({ isOpen, onClose }: FilterPanelProps) {
             ))}
           </div>
         </FilterSection>
+
+        {/* External dependencies */}
+        <FilterSection
+          title="Dependencies"
+          isExpanded={expandedSections.dependencies}
+          onToggle={() => toggleSection('dependencies')}
+          count={filterState.showPackages ? undefined : 1}
+        >
+          <CheckboxItem
+            label="Show packages"
+            checked={filterState.showPackages}
+            onChange={() => setFilterState({ ...filterState, showPackages: !filterState.showPackages })}
+          />
+        </FilterSection>
+
+        {/* Documentation coverage */}
+        <FilterSection
+          title="Documentation"
+          isExpanded={expandedSections.documentation}
+          onToggle={() => toggleSection('documentation')}
+          count={filterState.undocumentedOnly ? 1 : undefined}
+        >
+          <CheckboxItem
+            label="Only code without docs"
+            checked={filterState.undocumentedOnly}
+            onChange={() => setFilterState({ ...filterState, undocumentedOnly: !filterState.undocumentedOnly })}
+          />
+        </FilterSection>
       </div>
     </div>
   )
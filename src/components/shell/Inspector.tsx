@@ -4,6 +4,7 @@ import {
   FileCode,
   FunctionSquare,
   Box,
+  Package,
   Type,
   ArrowDownLeft,
   ArrowUpRight,
@@ -19,7 +20,7 @@ import {
 import { useAppStore } from '@/stores/useAppStore'
 import * as api from '@/lib/tauri'
 import type { NodeDetails, Relationship } from '@/lib/tauri'
-import type { SymbolKind } from '@/types'
+import type { Symbol, SymbolKind } from '@/types'
 
 // =============================================================================
 // Helper Components
@@ -29,6 +30,9 @@ function NodeTypeIcon({ type, symbolKind }: { type: string; symbolKind?: SymbolK
   if (type === 'file') {
     return <FileCode className="w-5 h-5 text-cyan-500" />
   }
+  if (type === 'package') {
+    return <Package className="w-5 h-5 text-zinc-400" />
+  }
 
   const icons: Partial<Record<SymbolKind, typeof FunctionSquare>> = {
     function: FunctionSquare,
@@ -117,6 +121,29 @@ function CollapsibleSection({
   )
 }
 
+interface SymbolListProps {
+  symbols: Symbol[]
+  onNavigate: (nodeId: string) => void
+}
+
+function SymbolList({ symbols, onNavigate }: SymbolListProps) {
+  return (
+    <div className="space-y-1 max-h-40 overflow-y-auto">
+      {symbols.map((symbol) => (
+        <button
+          key={symbol.id}
+          onClick={() => onNavigate(symbol.id)}
+          className="w-full flex items-center gap-2 px-2 py-1 text-left text-xs bg-zinc-100 dark:bg-zinc-800/50 hover:bg-zinc-200 dark:hover:bg-zinc-800 rounded transition-colors"
+        >
+          <NodeTypeIcon type="symbol" symbolKind={symbol.kind as SymbolKind} />
+          <span className="flex-1 truncate font-mono">{symbol.name}</span>
+          <span className="text-zinc-400">:{symbol.line}</span>
+        </button>
+      ))}
+    </div>
+  )
+}
+
 interface RelationshipListProps {
   relationships: Relationship[]
   direction: 'incoming' | 'outgoing'
@@ -286,13 +313,15 @@ export function Inspector() {
               />
               <div className="flex-1 min-w-0">
                 <h4 className="font-medium text-sm truncate">
-                  {details.file?.name || details.symbol?.name || 'Unknown'}
+                  {details.file?.name || details.symbol?.name || details.package?.name || 'Unknown'}
                 </h4>
                 <div className="flex items-center gap-2 mt-0.5">
                   <p className="text-xs text-zinc-500 capitalize">
                     {details.nodeType === 'file'
                       ? 'File'
-                      : details.symbol?.kind || 'Symbol'}
+                      : details.package
+                        ? `${details.package.ecosystem} package`
+                        : details.symbol?.kind || 'Symbol'}
                   </p>
                   <LanguageBadge language={details.file?.language} />
                 </div>
@@ -351,19 +380,26 @@ export function Inspector() {
                 count={details.symbolsInFile.length}
                 defaultOpen={details.symbolsInFile.length <= 10}
               >
-                <div className="space-y-1 max-h-40 overflow-y-auto">
-                  {details.symbolsInFile.map((symbol) => (
-                    <button
-                      key={symbol.id}
-                      onClick={() => handleNavigate(symbol.id)}
-                      className="w-full flex items-center gap-2 px-2 py-1 text-left text-xs bg-zinc-100 dark:bg-zinc-800/50 hover:bg-zinc-200 dark:hover:bg-zinc-800 rounded transition-colors"
-                    >
-                      <NodeTypeIcon type="symbol" symbolKind={symbol.kind as SymbolKind} />
-                      <span className="flex-1 truncate font-mono">{symbol.name}</span>
-                      <span className="text-zinc-400">:{symbol.line}</span>
-                    </button>
-                  ))}
-                </div>
+                <SymbolList symbols={details.symbolsInFile} onNavigate={handleNavigate} />
+              </CollapsibleSection>
+            )}
+
+            {/* Enclosing symbol */}
+            {details.parentSymbol && (
+              <CollapsibleSection title="Member of" icon={<Box className="w-3 h-3" />}>
+                <SymbolList symbols={[details.parentSymbol]} onNavigate={handleNavigate} />
+              </CollapsibleSection>
+            )}
+
+            {/* Members of this symbol */}
+            {details.childSymbols && details.childSymbols.length > 0 && (
+              <CollapsibleSection
+                title="Members"
+                icon={<Code className="w-3 h-3" />}
+                count={details.childSymbols.length}
+                defaultOpen={details.childSymbols.length <= 10}
+              >
+                <SymbolList symbols={details.childSymbols} onNavigate={handleNavigate} />
               </CollapsibleSection>
             )}
 
@@ -1,5 +1,9 @@
 import { useMemo } from 'react'
 import { useAppStore } from '@/stores/useAppStore'
+import type { Language } from '@/types'
+
+/** Languages of documents, configuration and assets rather than code */
+const NON_CODE_LANGUAGES: Language[] = ['markdown', 'json', 'yaml', 'toml', 'msbuild', 'plist', 'image', 'html', 'css']
 
 /**
  * Hook that filters graph nodes and edges based on the current filter state and search query.
@@ -36,11 +40,24 @@ export function useFilteredGraph() {
     return matches
   }, [nodes, searchQuery])
 
+  // Files a Markdown document links to or mentions
+  const documentedIds = useMemo(() => {
+    const documents = new Set(nodes.filter((n) => n.language === 'markdown').map((n) => n.id))
+    return new Set(
+      edges.filter((e) => e.type === 'references' && documents.has(e.source)).map((e) => e.target)
+    )
+  }, [nodes, edges])
+
   // Filter nodes based on filter state
   const filteredNodes = useMemo(() => {
     console.log('[FilteredGraph] Filtering - nodes:', nodes.length, 'viewMode:', viewMode)
 
     const result = nodes.filter((node) => {
+      // Package nodes sit beside files at file level and have their own toggle
+      if (node.type === 'package') {
+        return viewMode === 'file' && filterState.showPackages
+      }
+
       // View mode filter: file vs symbol level
       if (viewMode === 'file' && node.type !== 'file') {
         return false
@@ -50,6 +67,13 @@ export function useFilteredGraph() {
         // For now, show everything in symbol mode
       }
 
+      // Documentation filter: code files no document points at
+      if (filterState.undocumentedOnly && node.type === 'file') {
+        if (!node.language || NON_CODE_LANGUAGES.includes(node.language) || documentedIds.has(node.id)) {
+          return false
+        }
+      }
+
       // Language filter
       if (filterState.languages.length > 0) {
         if (!node.language || !filterState.languages.includes(node.language)) {
@@ -76,7 +100,7 @@ export function useFilteredGraph() {
 
     console.log('[FilteredGraph] Result - filteredNodes:', result.length, 'node types:', [...new Set(result.map(n => n.type))])
     return result
-  }, [nodes, filterState, viewMode])
+  }, [nodes, filterState, viewMode, documentedIds])
 
   // Get set of filtered node IDs for edge filtering
   const filteredNodeIds = useMemo(
@@ -125,6 +149,8 @@ export function useFilteredGraph() {
         filterState.languages.length > 0 ||
         filterState.symbolKinds.length > 0 ||
         filterState.relationshipTypes.length < 7 ||
+        !filterState.showPackages ||
+        filterState.undocumentedOnly ||
         !!searchQuery.trim(),
     }),
     [nodes.length, filteredNodes.length, edges.length, filteredEdges.length, searchMatchIds.size, filterState, searchQuery]
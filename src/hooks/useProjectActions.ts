@@ -30,7 +30,15 @@ export function useProjectActions() {
     totalFiles: 0,
     percentComplete: 0,
     errorMessage: null,
-    statistics: { totalRelationships: 0, totalSymbols: 0, totalFiles: 0 },
+    statistics: {
+      totalRelationships: 0,
+      totalSymbols: 0,
+      totalFiles: 0,
+      totalImports: 0,
+      unresolvedImports: 0,
+      importCoverage: [],
+      brokenLinks: 0,
+    },
   }
 
   /**
@@ -264,7 +272,11 @@ export function useProjectActions() {
     // If project was analyzed, load graph data and set complete status
     if (project.lastAnalyzedAt) {
       try {
-        const graphData = await api.getGraphData(project.id)
+        const [graphData, unresolvedImports, brokenLinks] = await Promise.all([
+          api.getGraphData(project.id),
+          api.getUnresolvedImports(project.id),
+          api.getBrokenLinks(project.id),
+        ])
         setNodes(graphData.nodes)
         setEdges(graphData.edges)
 
@@ -294,6 +306,10 @@ export function useProjectActions() {
             totalRelationships: graphData.edges.length,
             totalSymbols: graphData.nodes.filter(n => n.type === 'symbol').length,
             totalFiles: graphData.nodes.filter(n => n.type === 'file').length,
+            totalImports: 0,
+            unresolvedImports: unresolvedImports.length,
+            importCoverage: [],
+            brokenLinks: brokenLinks.length,
           },
         })
       } catch (err) {
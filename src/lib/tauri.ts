@@ -8,6 +8,9 @@ import type {
   Project,
   File,
   Symbol,
+  Package,
+  UnresolvedImport,
+  BrokenLink,
   GraphNode,
   GraphEdge,
   AnalysisProgress,
@@ -25,11 +28,14 @@ export interface GraphData {
 
 export interface NodeDetails {
   id: string
-  nodeType: 'file' | 'symbol' | 'unknown'
+  nodeType: 'file' | 'symbol' | 'package' | 'unknown'
   file: File | null
   symbol: Symbol | null
   containingFile: File | null
+  parentSymbol: Symbol | null
+  childSymbols: Symbol[] | null
   symbolsInFile: Symbol[] | null
+  package: Package | null
   incomingRelationships: Relationship[]
   outgoingRelationships: Relationship[]
 }
@@ -79,6 +85,36 @@ export async function deleteProject(id: string): Promise<void> {
   return invoke<void>('delete_project', { id })
 }
 
+/**
+ * Get the extra C/C++ include directories configured for a project
+ */
+export async function getIncludeDirs(projectId: string): Promise<string[]> {
+  return invoke<string[]>('get_include_dirs', { projectId })
+}
+
+/**
+ * Set the extra C/C++ include directories for a project
+ * (absolute or relative to the project root; used on the next analysis)
+ */
+export async function setIncludeDirs(projectId: string, dirs: string[]): Promise<void> {
+  return invoke<void>('set_include_dirs', { projectId, dirs })
+}
+
+/**
+ * Get whether imports through barrel files are attributed to the files
+ * that define the imported names
+ */
+export async function getFollowReExports(projectId: string): Promise<boolean> {
+  return invoke<boolean>('get_follow_re_exports', { projectId })
+}
+
+/**
+ * Set whether to follow re-export chains (used on the next analysis)
+ */
+export async function setFollowReExports(projectId: string, follow: boolean): Promise<void> {
+  return invoke<void>('set_follow_re_exports', { projectId, follow })
+}
+
 // =============================================================================
 // Project File Browser Commands
 // =============================================================================
@@ -140,6 +176,20 @@ export async function cancelAnalysis(projectId: string): Promise<void> {
   return invoke<void>('cancel_analysis', { projectId })
 }
 
+/**
+ * Get the imports of the last analysis that did not resolve to a project file
+ */
+export async function getUnresolvedImports(projectId: string): Promise<UnresolvedImport[]> {
+  return invoke<UnresolvedImport[]>('get_unresolved_imports', { projectId })
+}
+
+/**
+ * Get the links in documents that point at files that do not exist
+ */
+export async function getBrokenLinks(projectId: string): Promise<BrokenLink[]> {
+  return invoke<BrokenLink[]>('get_broken_links', { projectId })
+}
+
 // =============================================================================
 // Graph Commands
 // =============================================================================
@@ -125,6 +125,8 @@ const defaultFilterState: FilterState = {
   symbolKinds: [],
   clusters: [],
   searchQuery: null,
+  showPackages: true,
+  undocumentedOnly: false,
 }
 
 const defaultAnalysisProgress: AnalysisProgress = {
@@ -138,6 +140,10 @@ const defaultAnalysisProgress: AnalysisProgress = {
     totalRelationships: 0,
     totalSymbols: 0,
     totalFiles: 0,
+    totalImports: 0,
+    unresolvedImports: 0,
+    importCoverage: [],
+    brokenLinks: 0,
   },
 }
 
@@ -65,6 +65,21 @@ export interface Symbol {
   isExported: boolean
 }
 
+/**
+ * A third-party package imported by project files, grouped by package root
+ * (`@scope/name`, crate name, top-level Python module, Go module path).
+ */
+export interface Package {
+  /** Unique identifier for the package */
+  id: string
+  /** Package root name */
+  name: string
+  /** Package registry the name belongs to */
+  ecosystem: PackageEcosystem
+  /** ID of the project importing this package */
+  projectId: string
+}
+
 /**
  * A directional connection between two symbols or files.
  * Represents imports, function calls, type references, etc.
@@ -117,21 +132,25 @@ export type Language =
   | 'go'
   | 'rust'
   | 'c'
-  // Discovery-only (included in graph but no symbol extraction)
-  | 'json'
-  | 'yaml'
-  | 'markdown'
-  | 'html'
-  | 'css'
-  | 'plist'
-  | 'shell'
-  // Legacy/placeholder (kept for compatibility)
+  | 'cpp'
   | 'java'
   | 'kotlin'
-  | 'cpp'
   | 'csharp'
   | 'ruby'
   | 'php'
+  | 'vue'
+  | 'svelte'
+  | 'shell'
+  | 'html'
+  | 'css'
+  | 'markdown'
+  // Discovery-only (included in graph but no symbol extraction)
+  | 'json'
+  | 'yaml'
+  | 'toml'
+  | 'msbuild'
+  | 'plist'
+  | 'image'
   | 'unknown'
 
 /**
@@ -150,6 +169,11 @@ export type SymbolKind =
   | 'module'
   | 'namespace'
 
+/**
+ * Package registries that external dependencies are grouped by
+ */
+export type PackageEcosystem = 'npm' | 'crates' | 'pypi' | 'go' | 'rubygems' | 'nuget'
+
 /**
  * Types of relationships between nodes
  */
@@ -185,9 +209,11 @@ export interface GraphNode {
   /** Display name */
   name: string
   /** Type of node */
-  type: 'file' | 'symbol'
+  type: 'file' | 'symbol' | 'package'
   /** Programming language (for file nodes) */
   language?: Language
+  /** Package registry (for package nodes) */
+  ecosystem?: PackageEcosystem
   /** Symbol kind (for symbol nodes) */
   symbolKind?: SymbolKind
   /** File path (for file nodes) */
@@ -198,7 +224,7 @@ export interface GraphNode {
   lineCount?: number
   /** Whether this symbol is exported/public */
   isExported: boolean
-  /** Number of connections (for sizing) */
+  /** Number of connections (for sizing); for packages, the number of files using it */
   connectionCount: number
   /** Visual state - provided by backend, can be overridden by frontend */
   state: NodeState
@@ -254,6 +280,65 @@ export interface AnalysisStatistics {
   totalSymbols: number
   /** Total files analyzed */
   totalFiles: number
+  /** Total imports found (reported by a fresh analysis only) */
+  totalImports: number
+  /** Imports that did not resolve to a project file, external ones included */
+  unresolvedImports: number
+  /** How well imports resolved, per language (reported by a fresh analysis only) */
+  importCoverage: ImportCoverage[]
+  /** Links in documents to files that do not exist */
+  brokenLinks: number
+}
+
+/**
+ * Import resolution counts for one language
+ */
+export interface ImportCoverage {
+  language: string
+  /** Imports found */
+  total: number
+  /** Imports resolved to project files */
+  resolved: number
+  /** Imports of third-party packages and built-in modules */
+  external: number
+  /** Imports of missing files, or that could not be classified at all */
+  missing: number
+}
+
+/**
+ * Why an import did not resolve to a project file
+ */
+export type UnresolvedReason = 'external' | 'builtin' | 'missing-file' | 'unknown'
+
+/**
+ * An import that did not resolve to a project file
+ */
+export interface UnresolvedImport {
+  id: string
+  projectId: string
+  fileId: string
+  /** Path of the importing file relative to the project root */
+  filePath: string
+  language: string
+  /** The import as written: module path, package name or header */
+  source: string
+  line: number
+  reason: UnresolvedReason
+}
+
+/**
+ * A link in a document to a file that does not exist
+ */
+export interface BrokenLink {
+  id: string
+  projectId: string
+  fileId: string
+  /** Path of the document relative to the project root */
+  filePath: string
+  /** The linked path as written, without any #fragment */
+  target: string
+  line: number
+  kind: 'link' | 'image'
 }
 
 /**
@@ -294,6 +379,10 @@ export interface FilterState {
   clusters: string[]
   /** Search query for filtering nodes by name */
   searchQuery: string | null
+  /** Show third-party package nodes */
+  showPackages: boolean
+  /** Only show code files no document links to or mentions */
+  undocumentedOnly: boolean
 }
 
 /**
@@ -55,6 +55,7 @@ describe('GraphCanvas', () => {
         symbolKinds: [],
         clusters: [],
         searchQuery: null,
+        showPackages: true,
       },
     } as ReturnType<typeof useAppStore>)
   })
@@ -137,6 +138,7 @@ describe('GraphCanvas', () => {
         symbolKinds: [],
         clusters: [],
         searchQuery: null,
+        showPackages: true,
       },
     } as ReturnType<typeof useAppStore>)
 
@@ -226,6 +228,7 @@ describe('GraphCanvas', () => {
         symbolKinds: [],
         clusters: [],
         searchQuery: null,
+        showPackages: true,
       },
     } as ReturnType<typeof useAppStore>)
 
@@ -261,6 +264,7 @@ describe('GraphCanvas', () => {
         symbolKinds: [],
         clusters: [],
         searchQuery: null,
+        showPackages: true,
       },
     } as ReturnType<typeof useAppStore>)
 
@@ -300,6 +304,7 @@ describe('GraphCanvas', () => {
         symbolKinds: [],
         clusters: [],
         searchQuery: null,
+        showPackages: true,
       },
     } as ReturnType<typeof useAppStore>)
 
@@ -12,6 +12,7 @@ describe('FilterPanel', () => {
     symbolKinds: [],
     clusters: [],
     searchQuery: null,
+    showPackages: true,
   }
 
   beforeEach(() => {
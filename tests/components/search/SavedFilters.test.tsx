@@ -12,6 +12,7 @@ describe('SavedFilters', () => {
     symbolKinds: [],
     clusters: [],
     searchQuery: null,
+    showPackages: true,
   }
 
   const sampleSavedFilters = [
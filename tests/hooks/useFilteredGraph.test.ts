@@ -27,6 +27,7 @@ describe('useFilteredGraph', () => {
     symbolKinds: [],
     clusters: [],
     searchQuery: null,
+    showPackages: true,
   }
 
   beforeEach(() => {
@@ -47,6 +48,25 @@ describe('useFilteredGraph', () => {
     expect(result.current.edges).toHaveLength(2) // Only edges between visible file nodes
   })
 
+  it('should show package nodes at file level unless toggled off', () => {
+    useAppStore.setState({
+      nodes: [
+        ...sampleNodes,
+        { id: 'pkg-1', name: 'react', type: 'package' as const, ecosystem: 'npm' as const, state: 'default' as const, isExported: true, connectionCount: 1 },
+      ],
+      edges: [...sampleEdges, { id: 'edge-5', source: 'file-1', target: 'pkg-1', type: 'imports' as const }],
+    })
+
+    const { result } = renderHook(() => useFilteredGraph())
+    expect(result.current.nodes.map(n => n.id)).toContain('pkg-1')
+    expect(result.current.edges.map(e => e.id)).toContain('edge-5')
+
+    useAppStore.setState({ filterState: { ...defaultFilterState, showPackages: false } })
+    const { result: hidden } = renderHook(() => useFilteredGraph())
+    expect(hidden.current.nodes.map(n => n.id)).not.toContain('pkg-1')
+    expect(hidden.current.edges.map(e => e.id)).not.toContain('edge-5')
+  })
+
   it('should filter nodes by language', () => {
     useAppStore.setState({
       filterState: { ...defaultFilterState, languages: ['typescript'] },